@@ -10,10 +10,11 @@ use solana_program::{
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
+    system_program,
     sysvar::Sysvar,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
-use crate::GMCError;
+use crate::{discriminator, GMCError};
 
 // 🛡️ OWASP SC05: Input validation constants
 pub const MAX_AFFILIATE_LEVELS: u8 = 6;
@@ -24,6 +25,14 @@ pub const MIN_STAKE_FOR_AFFILIATE: u64 = 1_000_000; // 1M tokens minimum
 #[allow(dead_code)]
 pub const ANTI_SYBIL_COOLDOWN: i64 = 86400; // 24 hours in seconds
 
+// 🔑 Seed of the affiliate record PDA ("affiliate" + affiliate pubkey)
+pub const AFFILIATE_RECORD_SEED: &[u8] = b"affiliate";
+
+/// Derive the affiliate record address for a wallet
+pub fn find_affiliate_record_address(program_id: &Pubkey, affiliate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AFFILIATE_RECORD_SEED, affiliate.as_ref()], program_id)
+}
+
 // 🚀 OPTIMIZED: Affiliate Level Configuration with better memory layout
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, Copy)]
 #[repr(C)] // 🚀 OPTIMIZATION: Explicit memory layout
@@ -192,6 +201,9 @@ pub enum AffiliateInstruction {
     InitializeAffiliateSystem,
     
     /// Register as a new affiliate
+    ///
+    /// Accounts: affiliate (signer), affiliate record PDA, config, system program,
+    /// pause registry, and the referrer's affiliate record when `referrer` is set
     RegisterAffiliate {
         referrer: Option<Pubkey>,
     },
@@ -219,8 +231,39 @@ pub enum AffiliateInstruction {
     },
 }
 
+// 🤝 Router function to process affiliate instructions
+///
+/// Receives an AffiliateInstruction and routes it to the appropriate handler
+pub fn process_instruction_router(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: AffiliateInstruction,
+) -> ProgramResult {
+    match instruction {
+        AffiliateInstruction::InitializeAffiliateSystem => {
+            process_initialize_affiliate_system(program_id, accounts)
+        },
+        AffiliateInstruction::RegisterAffiliate { referrer } => {
+            process_register_affiliate(program_id, accounts, referrer)
+        },
+        AffiliateInstruction::RecordReferral { affiliate_id, volume } => {
+            process_record_referral(program_id, accounts, affiliate_id, volume)
+        },
+        AffiliateInstruction::ClaimCommissions { affiliate_id } => {
+            process_claim_commissions(accounts, affiliate_id)
+        },
+        AffiliateInstruction::UpgradeLevel { affiliate_id } => {
+            process_upgrade_level(accounts, affiliate_id)
+        },
+        AffiliateInstruction::UpdateAntiSybilScore { affiliate_id, new_score } => {
+            process_update_anti_sybil_score(accounts, affiliate_id, new_score)
+        },
+    }
+}
+
 // 🛡️ Affiliate instruction processors with TDD implementation
 pub fn process_initialize_affiliate_system(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     msg!("🤝 Processing: InitializeAffiliateSystem");
@@ -245,20 +288,21 @@ pub fn process_initialize_affiliate_system(
         return Err(ProgramError::AccountNotRentExempt);
     }
     
-    // 🛡️ Check if already initialized
-    if config_info.data_len() >= AffiliateSystemConfig::LEN {
-        let existing_data = AffiliateSystemConfig::try_from_slice(&config_info.data.borrow())?;
-        if existing_data.total_affiliates > 0 {
-            msg!("🚨 Security Alert: Affiliate system already initialized");
-            return Err(ProgramError::AccountAlreadyInitialized);
-        }
+    if config_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 🛡️ Check if already initialized (only a fresh, untagged account becomes the config)
+    if !discriminator::is_untagged(&config_info.data.borrow()) {
+        msg!("🚨 Security Alert: Affiliate system already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
     }
     
     // 🛡️ Initialize affiliate system with secure defaults
     let config_data = AffiliateSystemConfig::new(*authority_info.key);
     
     // 🛡️ OWASP SC05: Serialize with error handling
-    config_data.serialize(&mut &mut config_info.data.borrow_mut()[..])
+    discriminator::store(program_id, config_info, &config_data)
         .map_err(|_| {
             msg!("🚨 Security Alert: Failed to serialize affiliate config");
             ProgramError::AccountDataTooSmall
@@ -273,6 +317,7 @@ pub fn process_initialize_affiliate_system(
 }
 
 pub fn process_register_affiliate(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     referrer: Option<Pubkey>,
 ) -> ProgramResult {
@@ -284,7 +329,8 @@ pub fn process_register_affiliate(
     let affiliate_info = next_account_info(account_info_iter)?;
     let affiliate_record_info = next_account_info(account_info_iter)?;
     let config_info = next_account_info(account_info_iter)?;
-    let rent_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let pause_registry_info = next_account_info(account_info_iter)?;
     
     // 🛡️ OWASP SC04: Affiliate signature validation
     if !affiliate_info.is_signer {
@@ -292,8 +338,14 @@ pub fn process_register_affiliate(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
+    crate::pause::require_not_paused(program_id, pause_registry_info, crate::pause::PAUSE_AFFILIATE_REGISTRATION)?;
+    
+    if config_info.owner != program_id || *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
     // 🛡️ Load affiliate system config
-    let mut config_data = AffiliateSystemConfig::try_from_slice(&config_info.data.borrow())?;
+    let mut config_data: AffiliateSystemConfig = discriminator::load(program_id, config_info)?;
     
     // 🛡️ Check if registration is open
     if !config_data.is_registration_open {
@@ -301,31 +353,52 @@ pub fn process_register_affiliate(
         return Err(ProgramError::Custom(GMCError::OperationNotAllowed as u32));
     }
     
-    // 🛡️ OWASP SC05: Rent exemption validation
-    let rent = solana_program::sysvar::rent::Rent::from_account_info(rent_info)?;
-    if !rent.is_exempt(affiliate_record_info.lamports(), affiliate_record_info.data_len()) {
-        msg!("🚨 Security Alert: Affiliate record account not rent exempt");
-        return Err(ProgramError::AccountNotRentExempt);
+    // 🛡️ One record per wallet, at its derived address
+    let (record_key, record_bump) = find_affiliate_record_address(program_id, affiliate_info.key);
+    if *affiliate_record_info.key != record_key {
+        msg!("🚨 Security Alert: Affiliate record is not the derived address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if affiliate_record_info.owner == program_id && affiliate_record_info.data_len() > 0 {
+        msg!("🚨 Security Alert: Affiliate already registered");
+        return Err(ProgramError::AccountAlreadyInitialized);
     }
     
-    // 🛡️ Check if affiliate already exists
-    if affiliate_record_info.data_len() >= AffiliateRecord::LEN {
-        let existing_record = AffiliateRecord::try_from_slice(&affiliate_record_info.data.borrow());
-        if existing_record.is_ok() && existing_record.unwrap().is_active {
-            msg!("🚨 Security Alert: Affiliate already registered");
-            return Err(ProgramError::AccountAlreadyInitialized);
+    // 🛡️ Validate referrer against its on-chain record
+    if let Some(referrer_key) = referrer {
+        if referrer_key == *affiliate_info.key {
+            msg!("🚨 Security Alert: Self-referral is not allowed");
+            return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+        }
+        
+        let referrer_record_info = next_account_info(account_info_iter)?;
+        if *referrer_record_info.key != find_affiliate_record_address(program_id, &referrer_key).0 {
+            return Err(ProgramError::InvalidSeeds);
         }
+        
+        let referrer_record: AffiliateRecord = discriminator::load(program_id, referrer_record_info)?;
+        if referrer_record.affiliate_id != referrer_key || !referrer_record.is_active {
+            msg!("🚨 Security Alert: Referrer is not an active affiliate");
+            return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+        }
+        
+        msg!("📝 Referrer provided: {}", referrer_key);
     }
     
     // 🛡️ OWASP SC03: Get current timestamp
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
     
-    // 🛡️ Validate referrer if provided
-    if let Some(referrer_key) = referrer {
-        // In a full implementation, we would validate the referrer exists and is active
-        msg!("📝 Referrer provided: {}", referrer_key);
-    }
+    // 📝 Create the record at its derived address
+    crate::create_pda_account(
+        program_id,
+        affiliate_info,
+        affiliate_record_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + AffiliateRecord::LEN,
+        &[AFFILIATE_RECORD_SEED, affiliate_info.key.as_ref(), &[record_bump]],
+    )?;
     
     // 🛡️ Create new affiliate record with secure defaults
     let affiliate_record = AffiliateRecord {
@@ -342,7 +415,7 @@ pub fn process_register_affiliate(
     };
     
     // 🛡️ OWASP SC05: Serialize with error handling
-    affiliate_record.serialize(&mut &mut affiliate_record_info.data.borrow_mut()[..])
+    discriminator::store(program_id, affiliate_record_info, &affiliate_record)
         .map_err(|_| {
             msg!("🚨 Security Alert: Failed to serialize affiliate record");
             ProgramError::AccountDataTooSmall
@@ -357,7 +430,7 @@ pub fn process_register_affiliate(
         })?;
     
     // 🛡️ Update config
-    config_data.serialize(&mut &mut config_info.data.borrow_mut()[..])
+    discriminator::store(program_id, config_info, &config_data)
         .map_err(|_| {
             msg!("🚨 Security Alert: Failed to update affiliate config");
             ProgramError::AccountDataTooSmall
@@ -373,6 +446,7 @@ pub fn process_register_affiliate(
 }
 
 pub fn process_record_referral(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     affiliate_id: Pubkey,
     volume: u64,
@@ -399,7 +473,7 @@ pub fn process_record_referral(
     }
     
     // 🛡️ Load affiliate record
-    let mut affiliate_record = AffiliateRecord::try_from_slice(&affiliate_record_info.data.borrow())?;
+    let mut affiliate_record: AffiliateRecord = discriminator::load(program_id, affiliate_record_info)?;
     
     // 🛡️ Validate affiliate ID matches
     if affiliate_record.affiliate_id != affiliate_id {
@@ -423,7 +497,7 @@ pub fn process_record_referral(
     }
     
     // 🛡️ Load config for commission calculation
-    let mut config_data = AffiliateSystemConfig::try_from_slice(&config_info.data.borrow())?;
+    let mut config_data: AffiliateSystemConfig = discriminator::load(program_id, config_info)?;
     let level_config = &config_data.level_configs[(affiliate_record.current_level - 1) as usize];
     
     // 🛡️ OWASP SC02: Safe arithmetic operations
@@ -469,13 +543,13 @@ pub fn process_record_referral(
         })?;
     
     // 🛡️ OWASP SC05: Serialize with error handling
-    affiliate_record.serialize(&mut &mut affiliate_record_info.data.borrow_mut()[..])
+    discriminator::store(program_id, affiliate_record_info, &affiliate_record)
         .map_err(|_| {
             msg!("🚨 Security Alert: Failed to serialize affiliate record");
             ProgramError::AccountDataTooSmall
         })?;
     
-    config_data.serialize(&mut &mut config_info.data.borrow_mut()[..])
+    discriminator::store(program_id, config_info, &config_data)
         .map_err(|_| {
             msg!("🚨 Security Alert: Failed to update affiliate config");
             ProgramError::AccountDataTooSmall
@@ -0,0 +1,387 @@
+// 🔑 GMC Token - Two-step authority rotation
+// The current authority of GlobalState or of a module state proposes a new key;
+// the handover only happens when the new key signs `AcceptAuthority`, optionally
+// after the critical time-lock. The pending proposal lives in a PDA per state account.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{
+    affiliate::AffiliateSystemConfig, discriminator, emit_event, ranking::RankingState, treasury::TreasuryState,
+    require_global_state_key, validate_pubkey_not_default, vesting::VestingConfig, GMCError, GlobalState,
+    CRITICAL_TIME_LOCK_HOURS,
+};
+
+/// Seed of the pending rotation PDAs (`["authority_transfer", state_account]`)
+pub const AUTHORITY_TRANSFER_SEED: &[u8] = b"authority_transfer";
+
+/// Delay applied to time-locked rotations
+pub const AUTHORITY_TIME_LOCK_SECS: i64 = CRITICAL_TIME_LOCK_HOURS as i64 * 3600;
+
+/// State account whose authority is rotated
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthorityTarget {
+    /// `GlobalState.admin`
+    GlobalState,
+    /// `TreasuryState.authority`
+    Treasury,
+    /// `VestingConfig.authority`
+    Vesting,
+    /// `RankingState.authority`
+    Ranking,
+    /// `AffiliateSystemConfig.authority`
+    Affiliate,
+}
+
+impl AuthorityTarget {
+    /// The admin controls the whole program; its rotation is always time-locked
+    pub fn requires_time_lock(&self) -> bool {
+        matches!(self, AuthorityTarget::GlobalState)
+    }
+
+    /// GlobalState is a singleton: only its canonical PDA may be rotated
+    pub fn check_state_account(&self, program_id: &Pubkey, state_info: &AccountInfo) -> ProgramResult {
+        if state_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if *self == AuthorityTarget::GlobalState {
+            require_global_state_key(program_id, state_info)?;
+        }
+        Ok(())
+    }
+
+    /// Current authority stored in the state account
+    pub fn read_authority(&self, data: &[u8]) -> Result<Pubkey, ProgramError> {
+        Ok(match self {
+            AuthorityTarget::GlobalState => discriminator::unpack::<GlobalState>(data)?.admin,
+            AuthorityTarget::Treasury => discriminator::unpack::<TreasuryState>(data)?.authority,
+            AuthorityTarget::Vesting => discriminator::unpack::<VestingConfig>(data)?.authority,
+            AuthorityTarget::Ranking => discriminator::unpack::<RankingState>(data)?.authority,
+            AuthorityTarget::Affiliate => discriminator::unpack::<AffiliateSystemConfig>(data)?.authority,
+        })
+    }
+
+    /// Replace the authority stored in the state account
+    pub fn write_authority(&self, data: &mut [u8], new_authority: Pubkey) -> ProgramResult {
+        match self {
+            AuthorityTarget::GlobalState => {
+                let mut state: GlobalState = discriminator::unpack(data)?;
+                state.admin = new_authority;
+                discriminator::pack(&state, data)?;
+            }
+            AuthorityTarget::Treasury => {
+                let mut state: TreasuryState = discriminator::unpack(data)?;
+                state.authority = new_authority;
+                discriminator::pack(&state, data)?;
+            }
+            AuthorityTarget::Vesting => {
+                let mut state: VestingConfig = discriminator::unpack(data)?;
+                state.authority = new_authority;
+                discriminator::pack(&state, data)?;
+            }
+            AuthorityTarget::Ranking => {
+                let mut state: RankingState = discriminator::unpack(data)?;
+                state.authority = new_authority;
+                discriminator::pack(&state, data)?;
+            }
+            AuthorityTarget::Affiliate => {
+                let mut state: AffiliateSystemConfig = discriminator::unpack(data)?;
+                state.authority = new_authority;
+                discriminator::pack(&state, data)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pending rotation stored at `["authority_transfer", state_account]`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PendingAuthority {
+    pub is_initialized: bool,
+    pub target: AuthorityTarget,
+    pub state_account: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+    pub proposed_at: i64,
+    pub effective_at: i64,
+}
+
+impl PendingAuthority {
+    pub const LEN: usize = 1 + 1 + 32 + 32 + 32 + 8 + 8; // 114 bytes
+
+    /// The proposed key may accept once the delay elapsed, if nobody rotated the state meanwhile
+    pub fn check_accept(&self, signer: &Pubkey, stored_authority: &Pubkey, now: i64) -> ProgramResult {
+        if !self.is_initialized || self.proposed_authority != *signer {
+            msg!("🚨 Security Alert: Signer is not the proposed authority");
+            return Err(GMCError::InvalidAuthority.into());
+        }
+
+        if self.current_authority != *stored_authority {
+            msg!("❌ Proposal is stale: the authority changed after it was made");
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+
+        if now < self.effective_at {
+            msg!("⏳ Authority rotation effective at {}", self.effective_at);
+            return Err(GMCError::InsufficientTimelock.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// 📣 Event logged (as `sol_log_data`) when a rotation is proposed or accepted
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct AuthorityEvent {
+    pub target: AuthorityTarget,
+    pub state_account: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub effective_at: i64,
+}
+
+fn log_event(name: &str, event: &AuthorityEvent) {
+    emit_event(name, event);
+    msg!("📣 {}: {:?} {} -> {} (effective at {})",
+         name, event.target, event.previous_authority, event.new_authority, event.effective_at);
+}
+
+/// Derive the pending rotation address of a state account
+pub fn find_authority_transfer_address(program_id: &Pubkey, state_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTHORITY_TRANSFER_SEED, state_account.as_ref()], program_id)
+}
+
+/// 🔑 Propose a new authority (replaces any pending proposal); GlobalState
+/// rotations always wait `AUTHORITY_TIME_LOCK_SECS`, whatever `time_locked` says
+///
+/// Accounts expected:
+/// 0. `[]` State account of `target` (the `["global_state"]` PDA for `GlobalState`)
+/// 1. `[writable, signer]` Current authority (payer)
+/// 2. `[writable]` Pending rotation PDA (`["authority_transfer", state_account]`)
+/// 3. `[]` System program
+pub fn process_propose_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target: AuthorityTarget,
+    new_authority: Pubkey,
+    time_locked: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let state_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    target.check_state_account(program_id, state_info)?;
+
+    let current_authority = target.read_authority(&state_info.data.borrow())?;
+    if current_authority != *authority_info.key {
+        msg!("🚨 Security Alert: Only the current authority can propose a rotation");
+        return Err(GMCError::InvalidAuthority.into());
+    }
+
+    validate_pubkey_not_default(&new_authority)?;
+    if new_authority == current_authority {
+        return Err(GMCError::InvalidInput.into());
+    }
+
+    let (pending_key, bump) = find_authority_transfer_address(program_id, state_info.key);
+    if *pending_info.key != pending_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if pending_info.data_len() == 0 {
+        if *system_program_info.key != system_program::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        crate::create_pda_account(
+            program_id,
+            authority_info,
+            pending_info,
+            system_program_info,
+            discriminator::ACCOUNT_HEADER_LEN + PendingAuthority::LEN,
+            &[AUTHORITY_TRANSFER_SEED, state_info.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        // 🏷️ Only an earlier proposal may be replaced
+        discriminator::load::<PendingAuthority>(program_id, pending_info)?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let time_locked = time_locked || target.requires_time_lock();
+    let pending = PendingAuthority {
+        is_initialized: true,
+        target,
+        state_account: *state_info.key,
+        current_authority,
+        proposed_authority: new_authority,
+        proposed_at: now,
+        effective_at: if time_locked { now + AUTHORITY_TIME_LOCK_SECS } else { now },
+    };
+    discriminator::store(program_id, pending_info, &pending)?;
+
+    log_event("AuthorityProposed", &AuthorityEvent {
+        target,
+        state_account: *state_info.key,
+        previous_authority: current_authority,
+        new_authority,
+        effective_at: pending.effective_at,
+    });
+    Ok(())
+}
+
+/// ✅ Accept a proposed rotation; the pending PDA is closed and its rent returned
+///
+/// Accounts expected:
+/// 0. `[writable]` State account of `target`
+/// 1. `[signer]` Proposed authority
+/// 2. `[writable]` Pending rotation PDA
+/// 3. `[writable]` Previous authority (receives the PDA rent)
+pub fn process_accept_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    target: AuthorityTarget,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let state_info = next_account_info(account_info_iter)?;
+    let new_authority_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let previous_authority_info = next_account_info(account_info_iter)?;
+
+    if !new_authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    target.check_state_account(program_id, state_info)?;
+    if pending_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if *pending_info.key != find_authority_transfer_address(program_id, state_info.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let pending: PendingAuthority = discriminator::load(program_id, pending_info)?;
+    if pending.target != target || pending.state_account != *state_info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let stored_authority = target.read_authority(&state_info.data.borrow())?;
+    pending.check_accept(new_authority_info.key, &stored_authority, Clock::get()?.unix_timestamp)?;
+
+    if *previous_authority_info.key != pending.current_authority {
+        return Err(GMCError::InvalidAuthority.into());
+    }
+
+    target.write_authority(&mut state_info.data.borrow_mut(), *new_authority_info.key)?;
+
+    // 🧹 Close the proposal
+    let rent = pending_info.lamports();
+    **pending_info.try_borrow_mut_lamports()? = 0;
+    **previous_authority_info.try_borrow_mut_lamports()? = previous_authority_info
+        .lamports()
+        .checked_add(rent)
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    pending_info.data.borrow_mut().fill(0);
+
+    log_event("AuthorityAccepted", &AuthorityEvent {
+        target,
+        state_account: *state_info.key,
+        previous_authority: pending.current_authority,
+        new_authority: *new_authority_info.key,
+        effective_at: pending.effective_at,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authority_is_read_and_written_for_every_target() {
+        let old = Pubkey::new_unique();
+        let new = Pubkey::new_unique();
+
+        let mut treasury = TreasuryState::default();
+        treasury.authority = old;
+        let mut ranking = RankingState::default();
+        ranking.authority = old;
+        let vesting = VestingConfig {
+            authority: old,
+            total_vested_amount: 0,
+            total_released_amount: 0,
+            total_schedules: 0,
+            active_schedules: 0,
+            completed_schedules: 0,
+            emergency_release_enabled: true,
+            paused: false,
+            initialized: true,
+            _padding: 0,
+        };
+
+        for (target, mut data) in [
+            (AuthorityTarget::Treasury, discriminator::to_tagged_vec(&treasury).unwrap()),
+            (AuthorityTarget::Vesting, discriminator::to_tagged_vec(&vesting).unwrap()),
+            (AuthorityTarget::Ranking, discriminator::to_tagged_vec(&ranking).unwrap()),
+            (AuthorityTarget::Affiliate, discriminator::to_tagged_vec(&AffiliateSystemConfig::new(old)).unwrap()),
+        ] {
+            assert_eq!(target.read_authority(&data).unwrap(), old);
+            target.write_authority(&mut data, new).unwrap();
+            assert_eq!(target.read_authority(&data).unwrap(), new);
+        }
+    }
+
+    #[test]
+    fn test_only_the_proposed_key_accepts_after_the_delay() {
+        let current = Pubkey::new_unique();
+        let proposed = Pubkey::new_unique();
+        let now = 1_700_000_000;
+        let pending = PendingAuthority {
+            is_initialized: true,
+            target: AuthorityTarget::Treasury,
+            state_account: Pubkey::new_unique(),
+            current_authority: current,
+            proposed_authority: proposed,
+            proposed_at: now,
+            effective_at: now + AUTHORITY_TIME_LOCK_SECS,
+        };
+        assert_eq!(pending.try_to_vec().unwrap().len(), PendingAuthority::LEN);
+
+        assert_eq!(pending.check_accept(&current, &current, now + AUTHORITY_TIME_LOCK_SECS), Err(GMCError::InvalidAuthority.into()));
+        assert_eq!(pending.check_accept(&proposed, &current, now), Err(GMCError::InsufficientTimelock.into()));
+        assert!(pending.check_accept(&proposed, &current, now + AUTHORITY_TIME_LOCK_SECS).is_ok());
+
+        // A rotation completed through another path invalidates the proposal
+        assert_eq!(
+            pending.check_accept(&proposed, &Pubkey::new_unique(), now + AUTHORITY_TIME_LOCK_SECS),
+            Err(GMCError::OperationNotAllowed.into())
+        );
+    }
+
+    #[test]
+    fn test_global_state_rotation_is_always_time_locked() {
+        assert!(AuthorityTarget::GlobalState.requires_time_lock());
+        for target in [
+            AuthorityTarget::Treasury,
+            AuthorityTarget::Vesting,
+            AuthorityTarget::Ranking,
+            AuthorityTarget::Affiliate,
+        ] {
+            assert!(!target.requires_time_lock());
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// 🏷️ GMC Token - Account discriminators
+// Every program state account starts with an 8-byte type discriminator and a
+// 1-byte schema version. Loading checks the owner, the tag and the version, so one
+// account type can no longer be passed where another is expected.
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Length of the type discriminator
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Discriminator plus schema version, prepended to every tagged account
+pub const ACCOUNT_HEADER_LEN: usize = DISCRIMINATOR_LEN + 1;
+
+/// State stored behind a discriminator/version header
+pub trait TaggedAccount: BorshSerialize + BorshDeserialize {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN];
+    const SCHEMA_VERSION: u8 = 1;
+}
+
+impl TaggedAccount for crate::GlobalState {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:glob";
+}
+
+impl TaggedAccount for crate::TokenAccount {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:tokn";
+}
+
+impl TaggedAccount for crate::staking::StakingPool {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:pool";
+}
+
+impl TaggedAccount for crate::staking::StakeRecord {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:stak";
+}
+
+impl TaggedAccount for crate::treasury::TreasuryState {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:trsy";
+    // v2: signer set version
+    const SCHEMA_VERSION: u8 = 2;
+}
+
+impl TaggedAccount for crate::treasury::PendingTransaction {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:ptxn";
+    // v2: rejection votes, v3: signer set version and payload, v4: owning treasury
+    const SCHEMA_VERSION: u8 = 4;
+}
+
+impl TaggedAccount for crate::vesting::VestingSchedule {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:vsch";
+}
+
+impl TaggedAccount for crate::vesting::VestingConfig {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:vcfg";
+}
+
+impl TaggedAccount for crate::ranking::RankingState {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:rank";
+}
+
+impl TaggedAccount for crate::affiliate::AffiliateRecord {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:affl";
+}
+
+impl TaggedAccount for crate::affiliate::AffiliateSystemConfig {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:afcf";
+}
+
+impl TaggedAccount for crate::staking::EmissionSchedule {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:emis";
+}
+
+impl TaggedAccount for crate::roles::RoleRegistry {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:role";
+}
+
+impl TaggedAccount for crate::pause::PauseRegistry {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:paus";
+}
+
+impl TaggedAccount for crate::fee_exemption::FeeExemptionList {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:fexm";
+}
+
+impl TaggedAccount for crate::timelock::TimeLockAccount {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:tlck";
+}
+
+impl TaggedAccount for crate::authority::PendingAuthority {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = *b"gmc:auth";
+}
+
+/// Decode a tagged account body (trailing bytes past the body are ignored)
+pub fn unpack<T: TaggedAccount>(data: &[u8]) -> Result<T, ProgramError> {
+    if data.len() < ACCOUNT_HEADER_LEN || data[..DISCRIMINATOR_LEN] != T::DISCRIMINATOR {
+        msg!("🚨 Security Alert: Account discriminator mismatch");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if data[DISCRIMINATOR_LEN] != T::SCHEMA_VERSION {
+        msg!("❌ Unsupported account schema version {} (expected {})", data[DISCRIMINATOR_LEN], T::SCHEMA_VERSION);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    T::deserialize(&mut &data[ACCOUNT_HEADER_LEN..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Encode the header and body into `data`
+pub fn pack<T: TaggedAccount>(value: &T, data: &mut [u8]) -> ProgramResult {
+    if data.len() < ACCOUNT_HEADER_LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    data[..DISCRIMINATOR_LEN].copy_from_slice(&T::DISCRIMINATOR);
+    data[DISCRIMINATOR_LEN] = T::SCHEMA_VERSION;
+    value.serialize(&mut &mut data[ACCOUNT_HEADER_LEN..])?;
+    Ok(())
+}
+
+/// Header and body as a fresh buffer (clients, tests)
+pub fn to_tagged_vec<T: TaggedAccount>(value: &T) -> Result<Vec<u8>, ProgramError> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.push(T::SCHEMA_VERSION);
+    value.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// A fresh account has never been tagged (its header is still zeroed)
+pub fn is_untagged(data: &[u8]) -> bool {
+    data.iter().take(ACCOUNT_HEADER_LEN).all(|byte| *byte == 0)
+}
+
+/// Load a tagged account owned by the program
+pub fn load<T: TaggedAccount>(program_id: &Pubkey, info: &AccountInfo) -> Result<T, ProgramError> {
+    if info.owner != program_id {
+        msg!("🚨 Security Alert: State account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    unpack(&info.data.borrow())
+}
+
+/// Store a tagged account owned by the program
+pub fn store<T: TaggedAccount>(program_id: &Pubkey, info: &AccountInfo, value: &T) -> ProgramResult {
+    if info.owner != program_id {
+        msg!("🚨 Security Alert: State account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    pack(value, &mut info.data.borrow_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{staking::StakeRecord, treasury::TreasuryState};
+
+    #[test]
+    fn test_accounts_only_load_as_their_own_type() {
+        let mut treasury = TreasuryState::default();
+        treasury.is_initialized = true;
+        let mut data = to_tagged_vec(&treasury).unwrap();
+
+        assert!(unpack::<TreasuryState>(&data).unwrap().is_initialized);
+        assert_eq!(unpack::<StakeRecord>(&data).unwrap_err(), ProgramError::InvalidAccountData);
+
+        // Untagged legacy layouts and unknown schema versions are rejected
+        assert!(unpack::<TreasuryState>(&treasury.try_to_vec().unwrap()).is_err());
+        data[DISCRIMINATOR_LEN] = TreasuryState::SCHEMA_VERSION + 1;
+        assert!(unpack::<TreasuryState>(&data).is_err());
+
+        let mut fresh = vec![0u8; ACCOUNT_HEADER_LEN + 8];
+        assert!(is_untagged(&fresh));
+        pack(&treasury, &mut vec![0u8; data.len()]).unwrap();
+        assert!(pack(&treasury, &mut fresh).is_err());
+    }
+
+    #[test]
+    fn test_discriminators_are_unique() {
+        let tags = [
+            crate::GlobalState::DISCRIMINATOR,
+            crate::TokenAccount::DISCRIMINATOR,
+            crate::staking::StakingPool::DISCRIMINATOR,
+            StakeRecord::DISCRIMINATOR,
+            crate::staking::EmissionSchedule::DISCRIMINATOR,
+            TreasuryState::DISCRIMINATOR,
+            crate::treasury::PendingTransaction::DISCRIMINATOR,
+            crate::vesting::VestingSchedule::DISCRIMINATOR,
+            crate::vesting::VestingConfig::DISCRIMINATOR,
+            crate::ranking::RankingState::DISCRIMINATOR,
+            crate::affiliate::AffiliateRecord::DISCRIMINATOR,
+            crate::affiliate::AffiliateSystemConfig::DISCRIMINATOR,
+            crate::roles::RoleRegistry::DISCRIMINATOR,
+            crate::pause::PauseRegistry::DISCRIMINATOR,
+            crate::fee_exemption::FeeExemptionList::DISCRIMINATOR,
+            crate::timelock::TimeLockAccount::DISCRIMINATOR,
+            crate::authority::PendingAuthority::DISCRIMINATOR,
+        ];
+        for (i, tag) in tags.iter().enumerate() {
+            assert!(!tags[i + 1..].contains(tag), "duplicate discriminator {:?}", tag);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+// 🧾 GMC Token - Fee-exempt account allowlist
+// Protocol accounts (staking vaults, treasury, ranking fund, vesting vaults) and
+// negotiated partners skip the 0.5% transfer fee. The list lives in a PDA next to
+// GlobalState; additions only take effect after the minimum time-lock.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{discriminator, emit_event, load_global_state, GMCError, MIN_TIME_LOCK_HOURS};
+
+/// Seed of the allowlist PDA
+pub const FEE_EXEMPTIONS_SEED: &[u8] = b"fee_exemptions";
+
+/// Maximum number of exempt accounts
+pub const MAX_FEE_EXEMPTIONS: usize = 32;
+
+/// Delay before a new exemption takes effect
+pub const FEE_EXEMPTION_DELAY_SECS: i64 = MIN_TIME_LOCK_HOURS as i64 * 3600;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct FeeExemption {
+    /// Token account that does not pay the transfer fee as source or destination
+    pub account: Pubkey,
+    /// Unix timestamp from which the exemption applies
+    pub effective_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct FeeExemptionList {
+    pub is_initialized: bool,
+    pub entries: Vec<FeeExemption>,
+}
+
+impl FeeExemptionList {
+    pub const LEN: usize = 1 + 4 + MAX_FEE_EXEMPTIONS * (32 + 8);
+
+    pub fn is_exempt(&self, account: &Pubkey, now: i64) -> bool {
+        self.entries.iter().any(|entry| entry.account == *account && now >= entry.effective_at)
+    }
+
+    /// Queue an exemption; it applies once the time-lock has elapsed
+    pub fn add(&mut self, account: Pubkey, now: i64) -> Result<i64, ProgramError> {
+        if self.entries.iter().any(|entry| entry.account == account) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if self.entries.len() >= MAX_FEE_EXEMPTIONS {
+            msg!("❌ Fee exemption list is full ({} entries)", MAX_FEE_EXEMPTIONS);
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+
+        let effective_at = now
+            .checked_add(FEE_EXEMPTION_DELAY_SECS)
+            .ok_or(GMCError::ArithmeticOverflow)?;
+        self.entries.push(FeeExemption { account, effective_at });
+        Ok(effective_at)
+    }
+
+    /// Remove an exemption immediately (revoking a privilege needs no delay)
+    pub fn remove(&mut self, account: &Pubkey) -> ProgramResult {
+        let index = self.entries
+            .iter()
+            .position(|entry| entry.account == *account)
+            .ok_or(ProgramError::InvalidArgument)?;
+        self.entries.swap_remove(index);
+        Ok(())
+    }
+}
+
+/// 📣 Event logged (as `sol_log_data`) whenever the allowlist changes
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct FeeExemptionEvent {
+    pub account: Pubkey,
+    pub exempt: bool,
+    pub effective_at: i64,
+    pub authority: Pubkey,
+}
+
+fn log_event(name: &str, event: &FeeExemptionEvent) -> ProgramResult {
+    emit_event(name, event);
+    msg!("📣 {}: {} (effective at {})", name, event.account, event.effective_at);
+    Ok(())
+}
+
+/// Derive the allowlist address
+pub fn find_fee_exemptions_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_EXEMPTIONS_SEED], program_id)
+}
+
+/// Load the allowlist passed to a transfer, if any
+pub fn load_optional(
+    program_id: &Pubkey,
+    list_info: Option<&AccountInfo>,
+) -> Result<Option<FeeExemptionList>, ProgramError> {
+    let Some(list_info) = list_info else {
+        return Ok(None);
+    };
+
+    if *list_info.key != find_fee_exemptions_address(program_id).0 {
+        msg!("🚨 Security Alert: Invalid fee exemption list account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(Some(discriminator::load(program_id, list_info)?))
+}
+
+/// A transfer is fee-free when its source or destination is exempt
+pub fn is_transfer_exempt(
+    list: Option<&FeeExemptionList>,
+    from: &Pubkey,
+    to: &Pubkey,
+) -> Result<bool, ProgramError> {
+    let Some(list) = list else {
+        return Ok(false);
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    Ok(list.is_exempt(from, now) || list.is_exempt(to, now))
+}
+
+fn load_admin_and_list<'a, 'info>(
+    program_id: &Pubkey,
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>), ProgramError> {
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let list_info = next_account_info(account_info_iter)?;
+
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can manage fee exemptions");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    if *list_info.key != find_fee_exemptions_address(program_id).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok((admin_info, list_info))
+}
+
+/// 🏗️ Create the allowlist PDA
+///
+/// Accounts expected:
+/// 0. `[]` Global state PDA (`["global_state"]`)
+/// 1. `[writable, signer]` Admin (payer)
+/// 2. `[writable]` Fee exemption list PDA (`["fee_exemptions"]`)
+/// 3. `[]` System program
+pub fn process_initialize_fee_exemptions(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, list_info) = load_admin_and_list(program_id, account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if list_info.owner == program_id && list_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let (_, bump) = find_fee_exemptions_address(program_id);
+    crate::create_pda_account(
+        program_id,
+        admin_info,
+        list_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + FeeExemptionList::LEN,
+        &[FEE_EXEMPTIONS_SEED, &[bump]],
+    )?;
+
+    let list = FeeExemptionList { is_initialized: true, entries: Vec::new() };
+    discriminator::store(program_id, list_info, &list)?;
+
+    msg!("🧾 Fee exemption list initialized");
+    Ok(())
+}
+
+/// ➕ Queue a fee exemption (effective after `FEE_EXEMPTION_DELAY_SECS`)
+///
+/// Accounts expected:
+/// 0. `[]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Fee exemption list PDA
+pub fn process_add_fee_exemption(program_id: &Pubkey, accounts: &[AccountInfo], account: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, list_info) = load_admin_and_list(program_id, account_info_iter)?;
+
+    let mut list: FeeExemptionList = discriminator::load(program_id, list_info)?;
+    if !list.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let effective_at = list.add(account, Clock::get()?.unix_timestamp)?;
+    discriminator::store(program_id, list_info, &list)?;
+
+    log_event("FeeExemptionAdded", &FeeExemptionEvent {
+        account,
+        exempt: true,
+        effective_at,
+        authority: *admin_info.key,
+    })
+}
+
+/// ➖ Remove a fee exemption (effective immediately)
+///
+/// Accounts expected:
+/// 0. `[]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Fee exemption list PDA
+pub fn process_remove_fee_exemption(program_id: &Pubkey, accounts: &[AccountInfo], account: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, list_info) = load_admin_and_list(program_id, account_info_iter)?;
+
+    let mut list: FeeExemptionList = discriminator::load(program_id, list_info)?;
+    if !list.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    list.remove(&account)?;
+    // Shrinking leaves stale bytes past the Vec; loading ignores them
+    discriminator::store(program_id, list_info, &list)?;
+
+    log_event("FeeExemptionRemoved", &FeeExemptionEvent {
+        account,
+        exempt: false,
+        effective_at: Clock::get()?.unix_timestamp,
+        authority: *admin_info.key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_exemptions_are_time_locked_and_bounded() {
+        let mut list = FeeExemptionList { is_initialized: true, entries: Vec::new() };
+        let exchange = Pubkey::new_unique();
+        let now = 1_700_000_000;
+
+        let effective_at = list.add(exchange, now).unwrap();
+        assert_eq!(effective_at, now + 24 * 3600);
+        assert!(!list.is_exempt(&exchange, now));
+        assert!(list.is_exempt(&exchange, effective_at));
+        assert_eq!(list.add(exchange, now), Err(ProgramError::AccountAlreadyInitialized));
+
+        // Serialized list fits the account even when full
+        while list.entries.len() < MAX_FEE_EXEMPTIONS {
+            list.add(Pubkey::new_unique(), now).unwrap();
+        }
+        assert!(list.add(Pubkey::new_unique(), now).is_err());
+        let mut data = discriminator::to_tagged_vec(&list).unwrap();
+        assert_eq!(data.len(), discriminator::ACCOUNT_HEADER_LEN + FeeExemptionList::LEN);
+
+        // Removal is immediate and stale trailing bytes are ignored
+        list.remove(&exchange).unwrap();
+        discriminator::pack(&list, &mut data).unwrap();
+        let reloaded: FeeExemptionList = discriminator::unpack(&data).unwrap();
+        assert_eq!(reloaded.entries.len(), MAX_FEE_EXEMPTIONS - 1);
+        assert!(!reloaded.is_exempt(&exchange, effective_at));
+        assert!(list.remove(&exchange).is_err());
+    }
+}
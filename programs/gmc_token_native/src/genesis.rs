@@ -0,0 +1,368 @@
+// 🎬 GMC Token - On-chain genesis distribution
+// Moves the 100M supply into the ecosystem wallets following `InitialDistribution`
+// (70/8/10/2/6/2/2). Team and strategic reserve allocations go to vesting vaults,
+// token accounts controlled by a program PDA instead of a wallet. Runs only once.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{
+    credit_token_account, emit_event, load_global_state, setup_initial_distribution, store_global_state, token_mode,
+    GMCError, GlobalState, InitialDistribution, TokenAccount,
+};
+
+/// Seed of the PDAs that own the vesting vaults (`["vesting_vault", beneficiary]`)
+pub const VESTING_VAULT_SEED: &[u8] = b"vesting_vault";
+pub const TEAM_VESTING_VAULT: &[u8] = b"team";
+pub const STRATEGIC_RESERVE_VESTING_VAULT: &[u8] = b"strategic_reserve";
+
+/// Derive the authority of a vesting vault
+pub fn find_vesting_vault_authority(program_id: &Pubkey, beneficiary: &[u8]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VESTING_VAULT_SEED, beneficiary], program_id)
+}
+
+/// Where an allocation must land
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GenesisDestination {
+    /// Token account registered in `EcosystemWallets`
+    Wallet(Pubkey),
+    /// Token account owned by the vesting vault PDA of this beneficiary
+    VestingVault(&'static [u8]),
+}
+
+/// Allocations in the order of the destination accounts of `ExecuteGenesisDistribution`
+pub fn genesis_allocations(
+    distribution: &InitialDistribution,
+    global_state: &GlobalState,
+) -> [(GenesisDestination, u64); 7] {
+    let wallets = &global_state.ecosystem_wallets;
+    [
+        (GenesisDestination::Wallet(wallets.staking_fund), distribution.staking_pool),
+        (GenesisDestination::Wallet(wallets.presale), distribution.presale),
+        (GenesisDestination::VestingVault(STRATEGIC_RESERVE_VESTING_VAULT), distribution.strategic_reserve),
+        (GenesisDestination::Wallet(wallets.treasury), distribution.treasury),
+        (GenesisDestination::Wallet(wallets.marketing), distribution.marketing),
+        (GenesisDestination::Wallet(wallets.airdrop), distribution.airdrop),
+        (GenesisDestination::VestingVault(TEAM_VESTING_VAULT), distribution.team),
+    ]
+}
+
+/// 📣 Event logged (as `sol_log_data`) once the supply has been distributed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct GenesisDistributedEvent {
+    pub staking_pool: u64,
+    pub presale: u64,
+    pub strategic_reserve: u64,
+    pub treasury: u64,
+    pub marketing: u64,
+    pub airdrop: u64,
+    pub team: u64,
+    pub authority: Pubkey,
+}
+
+fn validate_destination(
+    program_id: &Pubkey,
+    global_state: &GlobalState,
+    destination: GenesisDestination,
+    account_info: &AccountInfo,
+) -> ProgramResult {
+    // 🪙 Every destination must be a GMC token account of the active ledger
+    let account_owner = match global_state.token_mode.token_program_id() {
+        None => TokenAccount::unpack(&account_info.data.borrow())?.owner,
+        Some(token_program_id) => {
+            if *account_info.owner != token_program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let account = token_mode::token_account_state(account_info)?;
+            if account.mint != global_state.gmc_mint {
+                msg!("❌ Genesis destination {} does not hold the GMC mint", account_info.key);
+                return Err(ProgramError::InvalidAccountData);
+            }
+            account.owner
+        }
+    };
+
+    let vault_authority = match destination {
+        GenesisDestination::Wallet(wallet) => {
+            if *account_info.key != wallet || wallet == Pubkey::default() {
+                msg!("❌ Genesis destination {} does not match the ecosystem wallets", account_info.key);
+                return Err(GMCError::InvalidWallet.into());
+            }
+            return Ok(());
+        }
+        GenesisDestination::VestingVault(beneficiary) => find_vesting_vault_authority(program_id, beneficiary).0,
+    };
+
+    if account_owner != vault_authority {
+        msg!("❌ Vesting vault {} is not owned by its vault PDA", account_info.key);
+        return Err(GMCError::InvalidWallet.into());
+    }
+    Ok(())
+}
+
+/// 🎬 Distribute the initial supply (admin only, once)
+///
+/// In `Native` mode the allocations are credited to the program's token accounts;
+/// in SPL modes they are transferred from the admin's token account holding the minted supply.
+///
+/// Accounts expected:
+/// 0. `[writable]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Staking fund token account (70M, `ecosystem_wallets.staking_fund`)
+/// 3. `[writable]` Presale token account (8M, `ecosystem_wallets.presale`)
+/// 4. `[writable]` Strategic reserve vesting vault (10M, owned by `["vesting_vault", "strategic_reserve"]`)
+/// 5. `[writable]` Treasury token account (2M, `ecosystem_wallets.treasury`)
+/// 6. `[writable]` Marketing token account (6M, `ecosystem_wallets.marketing`)
+/// 7. `[writable]` Airdrop token account (2M, `ecosystem_wallets.airdrop`)
+/// 8. `[writable]` Team vesting vault (2M, owned by `["vesting_vault", "team"]`)
+/// 9. `[writable]` Admin token account holding the supply (SPL modes only)
+/// 10. `[]` GMC mint (SPL modes only)
+/// 11. `[]` Token program (SPL modes only)
+pub fn process_execute_genesis_distribution(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let destination_infos = [
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+    ];
+
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut global_state = load_global_state(program_id, global_state_info)?;
+    if !global_state.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can execute the genesis distribution");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    if global_state.genesis_distributed {
+        msg!("❌ Genesis distribution already executed");
+        return Err(GMCError::GenesisAlreadyDistributed.into());
+    }
+
+    let distribution = setup_initial_distribution()?;
+    if global_state.total_supply != distribution.total_supply {
+        msg!("❌ Global state supply {} does not match the tokenomics", global_state.total_supply);
+        return Err(GMCError::InvalidDistribution.into());
+    }
+
+    let allocations = genesis_allocations(&distribution, &global_state);
+    for ((destination, _), account_info) in allocations.iter().zip(destination_infos) {
+        validate_destination(program_id, &global_state, *destination, account_info)?;
+    }
+
+    match global_state.token_mode.token_program_id() {
+        None => {
+            for ((_, amount), account_info) in allocations.iter().zip(destination_infos) {
+                if account_info.owner != program_id {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                credit_token_account(account_info, *amount)?;
+            }
+        }
+        Some(_) => {
+            let source_info = next_account_info(account_info_iter)?;
+            let mint_info = next_account_info(account_info_iter)?;
+            let token_program_info = next_account_info(account_info_iter)?;
+            let token_program_id = token_mode::validate_mint_and_program(&global_state, mint_info, token_program_info)?;
+            let decimals = token_mode::mint_decimals(mint_info)?;
+
+            for ((_, amount), account_info) in allocations.iter().zip(destination_infos) {
+                invoke(
+                    &token_mode::transfer_checked_instruction(
+                        &token_program_id,
+                        source_info.key,
+                        mint_info.key,
+                        account_info.key,
+                        admin_info.key,
+                        *amount,
+                        decimals,
+                    ),
+                    &[
+                        source_info.clone(),
+                        mint_info.clone(),
+                        account_info.clone(),
+                        admin_info.clone(),
+                        token_program_info.clone(),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    global_state.circulating_supply = distribution.circulating_supply;
+    global_state.genesis_distributed = true;
+    store_global_state(program_id, global_state_info, &global_state)?;
+
+    emit_event("GenesisDistributed", &GenesisDistributedEvent {
+        staking_pool: distribution.staking_pool,
+        presale: distribution.presale,
+        strategic_reserve: distribution.strategic_reserve,
+        treasury: distribution.treasury,
+        marketing: distribution.marketing,
+        airdrop: distribution.airdrop,
+        team: distribution.team,
+        authority: *admin_info.key,
+    });
+    msg!("🎬 Genesis distribution completed: {} GMC distributed", distribution.circulating_supply);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{discriminator, find_global_state_address, EcosystemWallets, GMC_TOTAL_SUPPLY, TRANSFER_FEE_BASIS_POINTS};
+    use solana_program::clock::Epoch;
+
+    #[test]
+    fn test_genesis_distribution_runs_once_into_wallets_and_vaults() {
+        let program_id = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let state = GlobalState {
+            total_supply: GMC_TOTAL_SUPPLY,
+            circulating_supply: 0,
+            burned_supply: 0,
+            admin,
+            ecosystem_wallets: EcosystemWallets {
+                team: Pubkey::new_unique(),
+                treasury: keys[5],
+                marketing: keys[6],
+                airdrop: keys[7],
+                presale: keys[3],
+                staking_fund: keys[2],
+                ranking_fund: Pubkey::new_unique(),
+            },
+            is_initialized: true,
+            burn_stopped: false,
+            mint_authority_revoked: false,
+            token_mode: token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
+        };
+
+        let token = |owner: Pubkey| {
+            let mut data = discriminator::to_tagged_vec(&TokenAccount { owner, balance: 0, is_initialized: true, delegated_amount: 0, delegate: None })
+                .unwrap();
+            data.resize(TokenAccount::ACCOUNT_LEN, 0);
+            data
+        };
+        let team_vault = find_vesting_vault_authority(&program_id, TEAM_VESTING_VAULT).0;
+        let reserve_vault = find_vesting_vault_authority(&program_id, STRATEGIC_RESERVE_VESTING_VAULT).0;
+        let mut data = vec![discriminator::to_tagged_vec(&state).unwrap(), vec![]];
+        for i in 2..9 {
+            data.push(match i {
+                4 => token(reserve_vault),
+                8 => token(team_vault),
+                _ => token(Pubkey::new_unique()),
+            });
+        }
+        let mut lamports = vec![0u64; 9];
+        let global_state_key = find_global_state_address(&program_id).0;
+        let signer_keys: Vec<Pubkey> = (0..9)
+            .map(|i| match i {
+                0 => global_state_key,
+                1 => admin,
+                _ => keys[i],
+            })
+            .collect();
+        let accounts: Vec<AccountInfo> = data.iter_mut().zip(lamports.iter_mut()).enumerate()
+            .map(|(i, (d, l))| AccountInfo::new(&signer_keys[i], i == 1, true, l, d, &program_id, false, Epoch::default()))
+            .collect();
+
+        process_execute_genesis_distribution(&program_id, &accounts).unwrap();
+
+        let balances: Vec<u64> = (2..9)
+            .map(|i| TokenAccount::unpack(&accounts[i].data.borrow()).unwrap().balance)
+            .collect();
+        assert_eq!(balances, vec![
+            crate::STAKING_POOL_ALLOCATION,
+            crate::PRESALE_ALLOCATION,
+            crate::STRATEGIC_RESERVE,
+            crate::TREASURY_ALLOCATION,
+            crate::MARKETING_ALLOCATION,
+            crate::AIRDROP_ALLOCATION,
+            crate::TEAM_ALLOCATION,
+        ]);
+        assert_eq!(balances.iter().sum::<u64>(), GMC_TOTAL_SUPPLY);
+
+        let state: GlobalState = discriminator::unpack(&accounts[0].data.borrow()).unwrap();
+        assert!(state.genesis_distributed);
+        assert_eq!(state.circulating_supply, GMC_TOTAL_SUPPLY);
+
+        // A second run is refused
+        assert_eq!(
+            process_execute_genesis_distribution(&program_id, &accounts),
+            Err(GMCError::GenesisAlreadyDistributed.into())
+        );
+    }
+
+    #[test]
+    fn test_vesting_vault_must_be_owned_by_its_pda() {
+        let program_id = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = discriminator::to_tagged_vec(&TokenAccount {
+            owner: find_vesting_vault_authority(&program_id, STRATEGIC_RESERVE_VESTING_VAULT).0,
+            balance: 0,
+            is_initialized: true,
+            delegated_amount: 0,
+            delegate: None,
+        }).unwrap();
+        let vault = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, Epoch::default());
+        let state = GlobalState {
+            total_supply: GMC_TOTAL_SUPPLY,
+            circulating_supply: 0,
+            burned_supply: 0,
+            admin: Pubkey::default(),
+            ecosystem_wallets: EcosystemWallets {
+                team: Pubkey::default(),
+                treasury: Pubkey::default(),
+                marketing: Pubkey::default(),
+                airdrop: Pubkey::default(),
+                presale: Pubkey::default(),
+                staking_fund: Pubkey::default(),
+                ranking_fund: Pubkey::default(),
+            },
+            is_initialized: true,
+            burn_stopped: false,
+            mint_authority_revoked: false,
+            token_mode: token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
+        };
+
+        let reserve = GenesisDestination::VestingVault(STRATEGIC_RESERVE_VESTING_VAULT);
+        let team = GenesisDestination::VestingVault(TEAM_VESTING_VAULT);
+        assert!(validate_destination(&program_id, &state, reserve, &vault).is_ok());
+        assert_eq!(
+            validate_destination(&program_id, &state, team, &vault),
+            Err(GMCError::InvalidWallet.into())
+        );
+        // Unset ecosystem wallets never receive an allocation
+        assert!(validate_destination(&program_id, &state, GenesisDestination::Wallet(Pubkey::default()), &vault).is_err());
+    }
+}
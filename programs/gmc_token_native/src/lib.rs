@@ -3,8 +3,13 @@ use solana_program::{
     entrypoint,
     entrypoint::ProgramResult,
     msg,
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    system_program,
+    sysvar::Sysvar,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 
@@ -27,6 +32,7 @@ pub const TEAM_ALLOCATION: u64 = 2_000_000_000_000_000;          // 2M GMC - Equ
 
 // 💸 Taxa de Transferência: 0.5%
 pub const TRANSFER_FEE_BASIS_POINTS: u16 = 50; // 0.5% = 50 basis points
+pub const MAX_TRANSFER_FEE_BASIS_POINTS: u16 = 100; // Teto para alterações via time-lock (1%)
 
 // 🔥 Distribuição da Taxa de Transferência (0.5%)
 pub const TRANSFER_FEE_BURN_PERCENT: u16 = 50;    // 50% para queima
@@ -34,7 +40,7 @@ pub const TRANSFER_FEE_STAKING_PERCENT: u16 = 40;  // 40% para fundo de staking
 pub const TRANSFER_FEE_RANKING_PERCENT: u16 = 10;  // 10% para programa de ranking
 
 // 💼 Carteiras do Ecossistema
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
 pub struct EcosystemWallets {
     pub team: Pubkey,
     pub treasury: Pubkey,
@@ -82,6 +88,12 @@ pub enum GMCError {
     MintAuthorityAlreadyRevoked = 0x1028,
     OnlyDeployerCanRevokeMint = 0x1029,
     MintRevokedCannotMint = 0x1030,
+    // 🚫 Formato legado de instruções codificadas no amount do Transfer
+    LegacyInstructionEncoding = 0x1031,
+    // 🎬 Distribuição inicial só pode ser executada uma vez
+    GenesisAlreadyDistributed = 0x1032,
+    // ⏸️ Módulo pausado no registro de pausa do protocolo
+    ProtocolPaused = 0x1033,
 }
 
 // 🔄 Implementar conversão para ProgramError (necessário para ?)
@@ -122,6 +134,9 @@ impl From<GMCError> for ProgramError {
             GMCError::MintAuthorityAlreadyRevoked => ProgramError::Custom(0x1028),
             GMCError::OnlyDeployerCanRevokeMint => ProgramError::MissingRequiredSignature,
             GMCError::MintRevokedCannotMint => ProgramError::Custom(0x1030),
+            GMCError::LegacyInstructionEncoding => ProgramError::Custom(0x1031),
+            GMCError::GenesisAlreadyDistributed => ProgramError::Custom(0x1032),
+            GMCError::ProtocolPaused => ProgramError::Custom(0x1033),
         }
     }
 }
@@ -129,18 +144,148 @@ impl From<GMCError> for ProgramError {
 // Declarar entrypoint
 entrypoint!(process_instruction);
 
+/// 🔢 Versão do conjunto de instruções exposto pelo entrypoint
+/// Incrementar sempre que variantes forem adicionadas ao `GMCInstruction`.
+/// A ordem das variantes É o formato de fio (tag Borsh): novas variantes
+/// entram sempre no final para não quebrar clientes existentes.
+pub const GMC_INSTRUCTION_SET_VERSION: u8 = 12;
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum GMCInstruction {
     Initialize { initial_supply: u64 },
     Transfer { amount: u64 },
     // 🔐 CRITICAL SECURITY: Revoke mint authority permanently
     RevokeMintAuthority,
+    // 🏗️ Instruções dos módulos (v1) - roteadas para os processadores reais
+    /// 🥩 Staking: pools, stake, claim, unstake e burn-for-boost
+    Staking(staking::StakingInstruction),
+    /// 🏛️ Treasury multisig
+    Treasury(treasury::TreasuryInstruction),
+    /// 📅 Vesting de equipe/investidores
+    Vesting(vesting::VestingInstruction),
+    /// 🏆 Ranking e distribuição de prêmios
+    Ranking(ranking::RankingInstruction),
+    /// 🤝 Sistema de afiliados
+    Affiliate(affiliate::AffiliateInstruction),
+    // 👛 Contas de token com dono e delegado (v3)
+    /// Inicializa uma conta de token para um dono
+    InitializeAccount,
+    /// Autoriza um delegado a gastar até `amount` da conta
+    Approve { amount: u64 },
+    /// Remove o delegado da conta
+    Revoke,
+    /// Transferência (com taxa) assinada pelo delegado
+    TransferFrom { amount: u64 },
+    // 🪙 Modo SPL Token / Token-2022 (v4)
+    /// Define o ledger do caminho de transferência (somente admin)
+    SetTokenMode { mode: token_mode::TokenMode },
+    /// Coleta as taxas retidas pelo Token-2022 e distribui 50/40/10
+    HarvestAndDistribute,
+    // 🧾 Lista de contas isentas da taxa de transferência (v5)
+    /// Cria a lista de isenções (somente admin)
+    InitializeFeeExemptions,
+    /// Agenda uma isenção (vale após o time-lock mínimo)
+    AddFeeExemption { account: Pubkey },
+    /// Remove uma isenção imediatamente
+    RemoveFeeExemption { account: Pubkey },
+    // 🎬 Distribuição inicial on-chain (v6)
+    /// Distribui o supply conforme `InitialDistribution` (somente admin, uma única vez)
+    ExecuteGenesisDistribution,
+    // ⏳ Alterações de parâmetros com time-lock (v7)
+    /// Agenda uma alteração de parâmetro (somente admin)
+    QueueTimeLockedChange { operation_id: u64, change: timelock::TimeLockedChange },
+    /// Executa uma alteração cujo atraso já expirou
+    ExecuteTimeLockedChange { operation_id: u64 },
+    /// Cancela uma alteração agendada
+    CancelTimeLockedChange { operation_id: u64 },
+    // 🔑 Rotação de autoridade em duas etapas (v8)
+    /// Propõe uma nova autoridade para o GlobalState (sempre com time-lock) ou um módulo (opcionalmente com time-lock)
+    ProposeAuthority { target: authority::AuthorityTarget, new_authority: Pubkey, time_locked: bool },
+    /// A nova autoridade assina e assume o controle
+    AcceptAuthority { target: authority::AuthorityTarget },
+    // ⏸️ Pausa de emergência por módulo (v9)
+    /// Cria o registro de pausa vinculado ao treasury (somente admin)
+    InitializePauseRegistry,
+    /// Define os módulos pausados (`pause::PAUSE_*`) e o modo "somente saída";
+    /// assinado pelo admin ou por um quórum de signatários do treasury
+    SetPause { paused_modules: u16, exit_only: bool },
+    // 🎭 Controle de acesso por papéis (v10)
+    /// Cria o registro de papéis (somente admin)
+    InitializeRoles,
+    /// Concede um papel operacional a uma chave (somente admin)
+    GrantRole { role: roles::Role, member: Pubkey },
+    /// Revoga um papel operacional (somente admin)
+    RevokeRole { role: roles::Role, member: Pubkey },
+    // 🧳 Migração de contas de token legadas (v11)
+    /// Acrescenta o cabeçalho do discriminador a uma conta de token sem marcação
+    MigrateTokenAccount,
+    // 🔢 Descoberta de versão (v12)
+    /// Devolve `GMC_INSTRUCTION_SET_VERSION` via return data; não usa contas
+    GetInstructionSetVersion,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct TokenAccount {
+    pub owner: Pubkey,
     pub balance: u64,
     pub is_initialized: bool,
+    // 👛 Delegado opcional e saldo que ele ainda pode gastar
+    pub delegated_amount: u64,
+    pub delegate: Option<Pubkey>,
+}
+
+impl TokenAccount {
+    pub const LEN: usize = 32 + 8 + 1 + 8 + 33; // 82 bytes
+
+    /// Tamanho da conta: cabeçalho do discriminador + corpo
+    pub const ACCOUNT_LEN: usize = discriminator::ACCOUNT_HEADER_LEN + Self::LEN;
+
+    /// Lê a conta marcada ignorando bytes finais (o delegado opcional tem tamanho variável)
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        discriminator::unpack(data)
+    }
+
+    /// Grava a conta com o cabeçalho do discriminador
+    pub fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        discriminator::pack(self, data)
+    }
+
+    /// Lê uma conta legada: corpo de 82 bytes sem cabeçalho, anterior aos discriminadores
+    pub fn unpack_legacy(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let account = Self::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)?;
+        if !account.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(account)
+    }
+
+    /// Autoriza um débito: o dono gasta livremente, o delegado consome sua permissão
+    pub fn authorize_debit(&mut self, authority: &Pubkey, amount: u64, as_delegate: bool) -> ProgramResult {
+        if !as_delegate {
+            if self.owner != *authority {
+                msg!("❌ Signer is not the owner of the source account");
+                return Err(GMCError::AccessDenied.into());
+            }
+            return Ok(());
+        }
+
+        if self.delegate != Some(*authority) {
+            msg!("❌ Signer is not the delegate of the source account");
+            return Err(GMCError::AccessDenied.into());
+        }
+
+        self.delegated_amount = self.delegated_amount
+            .checked_sub(amount)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        if self.delegated_amount == 0 {
+            self.delegate = None;
+        }
+        Ok(())
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
@@ -154,10 +299,88 @@ pub struct GlobalState {
     pub burn_stopped: bool, // Para quando atingir 12M GMC
     // 🔐 SECURITY: Track if mint authority has been permanently revoked
     pub mint_authority_revoked: bool,
+    // 🪙 Ledger do caminho de transferência e mint SPL correspondente
+    pub token_mode: token_mode::TokenMode,
+    pub gmc_mint: Pubkey,
+    // 🎬 Distribuição inicial (70/8/10/2/6/2/2) já executada on-chain
+    pub genesis_distributed: bool,
+    // 💸 Taxa de transferência vigente (alterável somente via time-lock)
+    pub transfer_fee_basis_points: u16,
+    // 💵 Mint USDT aceito nas taxas de entrada (alterável somente via time-lock)
+    pub usdt_mint: Pubkey,
+    // 💲 Preço de referência das taxas de entrada em micro-USDT por GMC (alterável somente via time-lock)
+    pub usdt_price_per_gmc: u64,
+}
+
+impl GlobalState {
+    pub const LEN: usize = 8 * 3 + 32 + 32 * 7 + 1 + 1 + 1 + 1 + 32 + 1 + 2 + 32 + 8; // 359 bytes
+}
+
+/// 🌐 Seed da PDA do GlobalState (`["global_state"]`)
+pub const GLOBAL_STATE_SEED: &[u8] = b"global_state";
+
+/// Endereço canônico do GlobalState (única conta aceita como estado global)
+pub fn find_global_state_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_STATE_SEED], program_id)
+}
+
+/// Exige que a conta seja a PDA canônica do GlobalState
+pub fn require_global_state_key(program_id: &Pubkey, info: &AccountInfo) -> ProgramResult {
+    if *info.key != find_global_state_address(program_id).0 {
+        msg!("🚨 Security Alert: Account is not the canonical GlobalState");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(())
+}
+
+/// Carrega o GlobalState canônico (outra conta marcada como GlobalState é rejeitada)
+pub fn load_global_state(program_id: &Pubkey, info: &AccountInfo) -> Result<GlobalState, ProgramError> {
+    require_global_state_key(program_id, info)?;
+    discriminator::load(program_id, info)
+}
+
+/// Grava o GlobalState canônico
+pub fn store_global_state(program_id: &Pubkey, info: &AccountInfo, global_state: &GlobalState) -> ProgramResult {
+    require_global_state_key(program_id, info)?;
+    discriminator::store(program_id, info, global_state)
+}
+
+/// 🏗️ Cria uma conta numa PDA do programa (dona `owner`), mesmo que o endereço já tenha lamports
+///
+/// `create_account` falha se o destino tiver saldo, e qualquer um pode enviar
+/// lamports a uma PDA previsível. Só a diferença até a isenção de aluguel é
+/// transferida; `allocate` e `assign` são assinados pelas seeds da PDA.
+pub fn create_pda_account<'a>(
+    owner: &Pubkey,
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(new_account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, new_account.key, shortfall),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(new_account.key, owner),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
 }
 
 // 🔥 Estrutura para Distribuição da Taxa de Transferência
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
 pub struct TransferFeeDistribution {
     pub burn_amount: u64,
     pub staking_amount: u64,
@@ -179,41 +402,185 @@ pub fn process_instruction(
         }
         GMCInstruction::Transfer { amount } => {
             msg!("GMC Token: Transfer amount {}", amount);
-            process_transfer(accounts, amount)
+            process_transfer(accounts, amount, program_id)
         }
         GMCInstruction::RevokeMintAuthority => {
             msg!("🔐 GMC Token: REVOKING MINT AUTHORITY PERMANENTLY");
-            process_revoke_mint_authority(accounts)
+            process_revoke_mint_authority(program_id, accounts)
+        }
+        GMCInstruction::Staking(instruction) => {
+            msg!("🥩 GMC Token: Staking instruction");
+            staking::process_instruction_router(program_id, accounts, instruction)
+        }
+        GMCInstruction::Treasury(instruction) => {
+            msg!("🏛️ GMC Token: Treasury instruction");
+            treasury::process_treasury_instruction(program_id, accounts, instruction)
+        }
+        GMCInstruction::Vesting(instruction) => {
+            msg!("📅 GMC Token: Vesting instruction");
+            vesting::process_instruction_router(program_id, accounts, instruction)
+        }
+        GMCInstruction::Ranking(instruction) => {
+            msg!("🏆 GMC Token: Ranking instruction");
+            ranking::process_ranking_instruction(program_id, accounts, instruction)
+        }
+        GMCInstruction::Affiliate(instruction) => {
+            msg!("🤝 GMC Token: Affiliate instruction");
+            affiliate::process_instruction_router(program_id, accounts, instruction)
+        }
+        GMCInstruction::InitializeAccount => {
+            msg!("👛 GMC Token: Initialize token account");
+            process_initialize_account(accounts, program_id)
+        }
+        GMCInstruction::Approve { amount } => {
+            msg!("👛 GMC Token: Approve delegate for {}", amount);
+            process_approve(accounts, amount, program_id)
+        }
+        GMCInstruction::Revoke => {
+            msg!("👛 GMC Token: Revoke delegate");
+            process_revoke(accounts, program_id)
+        }
+        GMCInstruction::TransferFrom { amount } => {
+            msg!("GMC Token: Delegated transfer amount {}", amount);
+            process_normal_transfer(accounts, amount, program_id, true)
+        }
+        GMCInstruction::SetTokenMode { mode } => {
+            msg!("🪙 GMC Token: Set token mode {:?}", mode);
+            token_mode::process_set_token_mode(program_id, accounts, mode)
+        }
+        GMCInstruction::HarvestAndDistribute => {
+            msg!("🧾 GMC Token: Harvest and distribute withheld fees");
+            token_mode::process_harvest_and_distribute(program_id, accounts)
+        }
+        GMCInstruction::InitializeFeeExemptions => {
+            msg!("🧾 GMC Token: Initialize fee exemptions");
+            fee_exemption::process_initialize_fee_exemptions(program_id, accounts)
+        }
+        GMCInstruction::AddFeeExemption { account } => {
+            msg!("🧾 GMC Token: Add fee exemption {}", account);
+            fee_exemption::process_add_fee_exemption(program_id, accounts, account)
+        }
+        GMCInstruction::RemoveFeeExemption { account } => {
+            msg!("🧾 GMC Token: Remove fee exemption {}", account);
+            fee_exemption::process_remove_fee_exemption(program_id, accounts, account)
+        }
+        GMCInstruction::ExecuteGenesisDistribution => {
+            msg!("🎬 GMC Token: Execute genesis distribution");
+            genesis::process_execute_genesis_distribution(program_id, accounts)
+        }
+        GMCInstruction::QueueTimeLockedChange { operation_id, change } => {
+            msg!("⏳ GMC Token: Queue time-locked change {}", operation_id);
+            timelock::process_queue(program_id, accounts, operation_id, change)
+        }
+        GMCInstruction::ExecuteTimeLockedChange { operation_id } => {
+            msg!("⏳ GMC Token: Execute time-locked change {}", operation_id);
+            timelock::process_execute(program_id, accounts, operation_id)
+        }
+        GMCInstruction::CancelTimeLockedChange { operation_id } => {
+            msg!("⏳ GMC Token: Cancel time-locked change {}", operation_id);
+            timelock::process_cancel(program_id, accounts, operation_id)
+        }
+        GMCInstruction::ProposeAuthority { target, new_authority, time_locked } => {
+            msg!("🔑 GMC Token: Propose {:?} authority {}", target, new_authority);
+            authority::process_propose_authority(program_id, accounts, target, new_authority, time_locked)
+        }
+        GMCInstruction::AcceptAuthority { target } => {
+            msg!("🔑 GMC Token: Accept {:?} authority", target);
+            authority::process_accept_authority(program_id, accounts, target)
+        }
+        GMCInstruction::InitializePauseRegistry => {
+            msg!("⏸️ GMC Token: Initialize pause registry");
+            pause::process_initialize_pause_registry(program_id, accounts)
+        }
+        GMCInstruction::SetPause { paused_modules, exit_only } => {
+            msg!("⏸️ GMC Token: Set pause {:#06x} (exit-only {})", paused_modules, exit_only);
+            pause::process_set_pause(program_id, accounts, paused_modules, exit_only)
+        }
+        GMCInstruction::InitializeRoles => {
+            msg!("🎭 GMC Token: Initialize role registry");
+            roles::process_initialize_roles(program_id, accounts)
+        }
+        GMCInstruction::GrantRole { role, member } => {
+            msg!("🎭 GMC Token: Grant {:?} to {}", role, member);
+            roles::process_grant_role(program_id, accounts, role, member)
+        }
+        GMCInstruction::RevokeRole { role, member } => {
+            msg!("🎭 GMC Token: Revoke {:?} from {}", role, member);
+            roles::process_revoke_role(program_id, accounts, role, member)
+        }
+        GMCInstruction::MigrateTokenAccount => {
+            msg!("🧳 GMC Token: Migrate legacy token account");
+            process_migrate_token_account(accounts, program_id)
+        }
+        GMCInstruction::GetInstructionSetVersion => {
+            msg!("🔢 GMC Token: Instruction set v{}", GMC_INSTRUCTION_SET_VERSION);
+            set_return_data(&[GMC_INSTRUCTION_SET_VERSION]);
+            Ok(())
         }
     }
 }
 
+/// 🌐 Cria o GlobalState na PDA canônica (`["global_state"]`)
+///
+/// Contas esperadas:
+/// 0. `[writable]` GlobalState PDA (ainda não criada)
+/// 1. `[writable, signer]` Admin (pagador)
+/// 2. `[]` System program
 pub fn process_initialize(
     accounts: &[AccountInfo],
     initial_supply: u64,
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let global_state_account = next_account_info(account_info_iter)?;
     let admin_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
     
     if !admin_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    let mut global_state = GlobalState::try_from_slice(&global_state_account.data.borrow())?;
-    
-    if global_state.is_initialized {
+    // 🛡️ Somente a PDA canônica vira GlobalState (uma única vez)
+    let (global_state_key, bump) = find_global_state_address(program_id);
+    if *global_state_account.key != global_state_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if global_state_account.data_len() > 0 {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
+    if *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
     
-    global_state.total_supply = initial_supply;
-    global_state.admin = *admin_account.key;
-    global_state.is_initialized = true;
-    // 🔐 SECURITY: Initialize mint authority as not revoked
-    global_state.mint_authority_revoked = false;
+    let space = discriminator::ACCOUNT_HEADER_LEN + GlobalState::LEN;
+    create_pda_account(
+        program_id,
+        admin_account,
+        global_state_account,
+        system_program_account,
+        space,
+        &[GLOBAL_STATE_SEED, &[bump]],
+    )?;
     
-    global_state.serialize(&mut &mut global_state_account.data.borrow_mut()[..])?;
+    let global_state = GlobalState {
+        total_supply: initial_supply,
+        circulating_supply: 0,
+        burned_supply: 0,
+        admin: *admin_account.key,
+        ecosystem_wallets: EcosystemWallets::default(),
+        is_initialized: true,
+        burn_stopped: false,
+        // 🔐 SECURITY: Initialize mint authority as not revoked
+        mint_authority_revoked: false,
+        token_mode: token_mode::TokenMode::Native,
+        gmc_mint: Pubkey::default(),
+        genesis_distributed: false,
+        transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+        usdt_mint: Pubkey::default(),
+        usdt_price_per_gmc: staking::DEFAULT_USDT_PRICE_PER_GMC,
+    };
+    
+    store_global_state(program_id, global_state_account, &global_state)?;
     
     msg!("GMC Token initialized successfully with supply: {}", initial_supply);
     Ok(())
@@ -223,6 +590,7 @@ pub fn process_initialize(
 /// This function removes the mint authority from the GMC token, making it impossible
 /// to create new tokens beyond the initial 100M supply
 pub fn process_revoke_mint_authority(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
@@ -236,7 +604,7 @@ pub fn process_revoke_mint_authority(
         return Err(GMCError::OnlyDeployerCanRevokeMint.into());
     }
     
-    let mut global_state = GlobalState::try_from_slice(&global_state_account.data.borrow())?;
+    let mut global_state = load_global_state(program_id, global_state_account)?;
     
     // 🛡️ SECURITY: Verify caller is the admin/deployer
     if *current_authority_account.key != global_state.admin {
@@ -273,7 +641,7 @@ pub fn process_revoke_mint_authority(
     
     // 🔐 SECURITY: Mark mint authority as permanently revoked in our state
     global_state.mint_authority_revoked = true;
-    global_state.serialize(&mut &mut global_state_account.data.borrow_mut()[..])?;
+    store_global_state(program_id, global_state_account, &global_state)?;
     
     msg!("🔐 CRITICAL SECURITY: MINT AUTHORITY PERMANENTLY REVOKED");
     msg!("🔐 No new GMC tokens can be created beyond the 100M supply");
@@ -282,257 +650,324 @@ pub fn process_revoke_mint_authority(
     Ok(())
 }
 
+/// 🚫 Limiar das antigas "instruções especiais" codificadas no campo `amount`
+/// Valores acima deste limiar excedem o supply total e nunca são transferências
+/// válidas. O dispatch bit-packed foi substituído pelas variantes tipadas do
+/// `GMCInstruction` (Staking, Treasury, Vesting, Ranking, ...).
+pub const LEGACY_SPECIAL_INSTRUCTION_THRESHOLD: u64 = 0xFF00000000000000;
+
 pub fn process_transfer(
     accounts: &[AccountInfo],
     amount: u64,
+    program_id: &Pubkey,
 ) -> ProgramResult {
-    // 🛡️ SECURITY: Validar se é código especial ou transferência normal
-    if amount >= 0xFF00000000000000 {
-        // 🔐 CÓDIGO ESPECIAL DETECTADO - DISPATCH SEGURO
-        return process_special_instruction(accounts, amount);
-    } else {
-        // 📤 TRANSFERÊNCIA NORMAL - FUNÇÃO ORIGINAL
-        return process_normal_transfer(accounts, amount);
-    }
-}
-
-/// 🔐 Processador seguro de instruções especiais
-/// SECURITY: Todas as validações de autorização e integridade mantidas
-fn process_special_instruction(
-    accounts: &[AccountInfo],
-    encoded_instruction: u64,
-) -> ProgramResult {
-    // 🛡️ SECURITY: Decodificar e validar instrução
-    let instruction_type = (encoded_instruction >> 56) & 0xFF;
-    let sub_operation = ((encoded_instruction >> 48) & 0xFF) as u8;
-    let param1 = ((encoded_instruction >> 32) & 0xFFFF) as u16;
-    let param2 = (encoded_instruction & 0xFFFFFFFF) as u32;
-    
-    // 🛡️ SECURITY: Validar autorização base
-    if accounts.len() < 3 {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    }
-    
-    let authority_account = &accounts[2];
-    if !authority_account.is_signer {
-        return Err(GMCError::AccessDenied.into());
+    // 🛡️ SECURITY: Rejeitar explicitamente o formato legado em vez de
+    // tratá-lo como opcode ou como transferência
+    if amount >= LEGACY_SPECIAL_INSTRUCTION_THRESHOLD {
+        msg!("❌ Legacy encoded instruction rejected: {:#018x}", amount);
+        msg!("   Encoded special instructions were removed - use the typed GMCInstruction variants");
+        return Err(GMCError::LegacyInstructionEncoding.into());
     }
     
-    msg!("🔐 Special instruction: type={}, sub_op={}, param1={}, param2={}", 
-         instruction_type, sub_operation, param1, param2);
-    
-    match instruction_type {
-        0x01 => {
-            // 🥩 STAKING OPERATIONS
-            process_staking_special(accounts, sub_operation, param1, param2)
-        }
-        0x02 => {
-            // 🏛️ TREASURY OPERATIONS  
-            process_treasury_special(accounts, sub_operation, param1, param2)
-        }
-        0x03 => {
-            // 📅 VESTING OPERATIONS
-            process_vesting_special(accounts, sub_operation, param1, param2)
-        }
-        0x04 => {
-            // 🎯 RANKING OPERATIONS
-            process_ranking_special(accounts, sub_operation, param1, param2)
-        }
-        _ => {
-            msg!("❌ Invalid special instruction type: {}", instruction_type);
-            Err(GMCError::InvalidInput.into())
-        }
-    }
+    process_normal_transfer(accounts, amount, program_id, false)
 }
 
-/// 📤 Função de transferência normal (código original preservado)
+/// 📤 Transferência com taxa de 0.5% (50% queima, 40% staking, 10% ranking)
+/// `Transfer` exige a assinatura do dono; `TransferFrom` a do delegado,
+/// limitada ao saldo aprovado. Nos modos SPL o token program valida dono/delegado.
+///
+/// Contas esperadas:
+/// 0. `[writable]` Conta de token de origem
+/// 1. `[writable]` Conta de token de destino
+/// 2. `[signer]` Dono (ou delegado) da origem
+/// 3. `[writable]` GlobalState
+/// 4. `[writable]` Conta de token do fundo de staking (`ecosystem_wallets.staking_fund`)
+/// 5. `[writable]` Conta de token do fundo de ranking (`ecosystem_wallets.ranking_fund`)
+/// 6. `[]` Registro de pausa (`["pause_registry"]`)
+/// 7. `[writable]` Mint GMC (somente modos SPL)
+/// 8. `[]` Token program (somente modos SPL)
+/// 9. `[]` Opcional: lista de isenções (`["fee_exemptions"]`, conta 7 no modo nativo);
+///    origem ou destino isentos não pagam taxa. No Token-2022 a taxa retida pelo mint
+///    continua valendo.
 fn process_normal_transfer(
     accounts: &[AccountInfo],
     amount: u64,
+    program_id: &Pubkey,
+    as_delegate: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let from_account = next_account_info(account_info_iter)?;
     let to_account = next_account_info(account_info_iter)?;
     let authority_account = next_account_info(account_info_iter)?;
+    let global_state_account = next_account_info(account_info_iter)?;
+    let staking_fund_account = next_account_info(account_info_iter)?;
+    let ranking_fund_account = next_account_info(account_info_iter)?;
+    let pause_registry_account = next_account_info(account_info_iter)?;
     
     if !authority_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    let mut from_token_account = TokenAccount::try_from_slice(&from_account.data.borrow())?;
-    let mut to_token_account = TokenAccount::try_from_slice(&to_account.data.borrow())?;
+    if amount == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    
+    pause::require_not_paused(program_id, pause_registry_account, pause::PAUSE_TRANSFERS)?;
     
-    if !from_token_account.is_initialized || !to_token_account.is_initialized {
+    let mut global_state = load_global_state(program_id, global_state_account)?;
+    if !global_state.is_initialized {
         return Err(ProgramError::UninitializedAccount);
     }
     
-    if from_token_account.balance < amount {
-        return Err(ProgramError::InsufficientFunds);
+    // 🛡️ A taxa só pode ir para os fundos registrados no GlobalState
+    if *staking_fund_account.key != global_state.ecosystem_wallets.staking_fund
+        || *ranking_fund_account.key != global_state.ecosystem_wallets.ranking_fund
+    {
+        msg!("❌ Fee destination does not match the ecosystem wallets");
+        return Err(GMCError::InvalidWallet.into());
+    }
+    
+    // 🪙 Modos SPL: saldos vivem nas contas do mint GMC real
+    if global_state.token_mode != token_mode::TokenMode::Native {
+        let spl_accounts = token_mode::SplTransferAccounts {
+            from: from_account,
+            to: to_account,
+            authority: authority_account,
+            staking_fund: staking_fund_account,
+            ranking_fund: ranking_fund_account,
+            mint: next_account_info(account_info_iter)?,
+            token_program: next_account_info(account_info_iter)?,
+        };
+        let exemptions = fee_exemption::load_optional(program_id, account_info_iter.next())?;
+        let fee_exempt = fee_exemption::is_transfer_exempt(exemptions.as_ref(), from_account.key, to_account.key)?;
+        token_mode::spl_transfer_with_fee(&spl_accounts, &mut global_state, amount, fee_exempt)?;
+        store_global_state(program_id, global_state_account, &global_state)?;
+        return Ok(());
+    }
+    
+    for account in [from_account, to_account, staking_fund_account, ranking_fund_account] {
+        if account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+    
+    let exemptions = fee_exemption::load_optional(program_id, account_info_iter.next())?;
+    let fee_exempt = fee_exemption::is_transfer_exempt(exemptions.as_ref(), from_account.key, to_account.key)?;
+    
+    // 🔥 Taxa aplicada ao supply (queima respeita o piso de 12M e `burn_stopped`)
+    let fee_distribution = if fee_exempt {
+        TransferFeeDistribution::default()
+    } else {
+        apply_transfer_fee(&mut global_state, amount)?
+    };
+    let net_amount = amount
+        .checked_sub(fee_distribution.total_fee)
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    // Poeira de arredondamento da taxa fica com o fundo de staking
+    let staking_credit = fee_distribution.total_fee
+        .checked_sub(fee_distribution.burn_amount)
+        .and_then(|x| x.checked_sub(fee_distribution.ranking_amount))
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    
+    // 💸 Cada conta é lida e gravada em sequência, então contas repetidas
+    // (ex.: origem = destino) nunca sobrescrevem um saldo já atualizado
+    debit_token_account(from_account, authority_account.key, amount, as_delegate)?;
+    credit_token_account(to_account, net_amount)?;
+    credit_token_account(staking_fund_account, staking_credit)?;
+    credit_token_account(ranking_fund_account, fee_distribution.ranking_amount)?;
+    
+    store_global_state(program_id, global_state_account, &global_state)?;
+    
+    msg!("Transferred {} tokens ({} net, {} fee)", amount, net_amount, fee_distribution.total_fee);
+    Ok(())
+}
+
+/// Debita uma conta de token após validar dono/delegado (leitura-modificação-escrita imediata)
+fn debit_token_account(account: &AccountInfo, authority: &Pubkey, amount: u64, as_delegate: bool) -> ProgramResult {
+    let mut token_account = TokenAccount::unpack(&account.data.borrow())?;
+    if !token_account.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
     }
     
-    from_token_account.balance -= amount;
-    to_token_account.balance += amount;
+    token_account.authorize_debit(authority, amount, as_delegate)?;
     
-    from_token_account.serialize(&mut &mut from_account.data.borrow_mut()[..])?;
-    to_token_account.serialize(&mut &mut to_account.data.borrow_mut()[..])?;
+    token_account.balance = token_account.balance
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    token_account.pack(&mut account.data.borrow_mut())?;
+    Ok(())
+}
+
+/// Credita uma conta de token (leitura-modificação-escrita imediata)
+fn credit_token_account(account: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut token_account = TokenAccount::unpack(&account.data.borrow())?;
+    if !token_account.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
     
-    msg!("Transferred {} tokens", amount);
+    token_account.balance = token_account.balance
+        .checked_add(amount)
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    token_account.pack(&mut account.data.borrow_mut())?;
     Ok(())
 }
 
-/// 🥩 Processador seguro de operações de staking
-fn process_staking_special(
+/// 👛 Inicializa uma conta de token
+///
+/// Contas esperadas:
+/// 0. `[writable]` Conta de token (do programa, ainda não inicializada)
+/// 1. `[]` Dono da conta
+fn process_initialize_account(
     accounts: &[AccountInfo],
-    sub_operation: u8,
-    param1: u16,
-    param2: u32,
+    program_id: &Pubkey,
 ) -> ProgramResult {
-    msg!("🥩 Staking operation: sub_op={}, param1={}, param2={}", sub_operation, param1, param2);
-    
-    match sub_operation {
-        1 => {
-            // CREATE_POOL: param1=pool_id, param2=apy_basis_points
-            msg!("🏊 Creating staking pool: id={}, apy={} basis points", param1, param2);
-            // TODO: Chamar staking::process_create_pool(accounts, param1 as u8, param2 as u16)
-            msg!("✅ Pool creation initiated (implementation pending)");
-            Ok(())
-        }
-        2 => {
-            // STAKE: param1=pool_id, param2=amount_gmc
-            msg!("💰 Staking tokens: pool={}, amount={} GMC", param1, param2);
-            // TODO: Chamar staking::process_stake(accounts, param1 as u8, param2 as u64)
-            msg!("✅ Stake operation initiated (implementation pending)");
-            Ok(())
-        }
-        3 => {
-            // CLAIM_REWARDS: param1=pool_id, param2=unused
-            msg!("🎁 Claiming rewards from pool: {}", param1);
-            // TODO: Chamar staking::process_claim_rewards(accounts, param1 as u8)
-            msg!("✅ Claim operation initiated (implementation pending)");
-            Ok(())
-        }
-        4 => {
-            // FLEXIBLE_CANCEL: param1=unused, param2=amount
-            msg!("⚡ Flexible staking cancellation: amount={}", param2);
-            // ESTA FUNÇÃO JÁ ESTÁ ACESSÍVEL!
-            process_flexible_staking_cancellation(accounts, param2 as u64)
-        }
-        _ => {
-            msg!("❌ Invalid staking sub-operation: {}", sub_operation);
-            Err(GMCError::InvalidInput.into())
-        }
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    
+    if token_account_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
     }
+    
+    if token_account_info.data_len() < TokenAccount::ACCOUNT_LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    
+    // 🛡️ Somente contas zeradas: contas legadas passam por MigrateTokenAccount
+    if token_account_info.data.borrow().iter().any(|byte| *byte != 0) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    let token_account = TokenAccount {
+        owner: *owner_account.key,
+        balance: 0,
+        is_initialized: true,
+        delegated_amount: 0,
+        delegate: None,
+    };
+    token_account.pack(&mut token_account_info.data.borrow_mut())?;
+    
+    msg!("👛 Token account {} initialized for {}", token_account_info.key, owner_account.key);
+    Ok(())
 }
 
-/// 🏛️ Processador seguro de operações de treasury
-fn process_treasury_special(
+/// 🧳 Migra uma conta de token legada (sem cabeçalho) para o formato marcado,
+/// preservando dono, saldo e delegação
+///
+/// Contas esperadas:
+/// 0. `[writable]` Conta de token legada (do programa, 82 bytes)
+/// 1. `[writable, signer]` Pagador do aluguel adicional
+/// 2. `[]` System program
+fn process_migrate_token_account(
     accounts: &[AccountInfo],
-    sub_operation: u8,
-    param1: u16,
-    param2: u32,
+    program_id: &Pubkey,
 ) -> ProgramResult {
-    msg!("🏛️ Treasury operation: sub_op={}, param1={}, param2={}", sub_operation, param1, param2);
+    let account_info_iter = &mut accounts.iter();
+    let token_account_info = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
     
-    match sub_operation {
-        1 => {
-            // INITIALIZE_MULTISIG: param1=required_sigs, param2=total_signers
-            msg!("🔐 Initializing Treasury multisig: {}-of-{}", param1, param2);
-            
-            // 🛡️ SECURITY: Validar parâmetros conforme código treasury.rs
-            if param1 < 3 || (param1 as u32) > param2 || param2 > 10 {
-                return Err(GMCError::InvalidRankingParameters.into());
-            }
-            
-            // TODO: Chamar treasury::process_initialize() com signatários
-            msg!("✅ Treasury initialization initiated (implementation pending)");
-            Ok(())
-        }
-        2 => {
-            // PROPOSE_TRANSACTION: param1=tx_type, param2=amount
-            msg!("📋 Proposing treasury transaction: type={}, amount={}", param1, param2);
-            // TODO: Chamar treasury::process_propose_transaction()
-            msg!("✅ Transaction proposal initiated (implementation pending)");
-            Ok(())
-        }
-        3 => {
-            // AUTO_DISTRIBUTE: param1=percentage, param2=total_amount
-            msg!("🔄 Auto-distributing treasury funds: {}% of {}", param1, param2);
-            // TODO: Chamar treasury::process_auto_distribute()
-            msg!("✅ Auto-distribution initiated (implementation pending)");
-            Ok(())
-        }
-        _ => {
-            msg!("❌ Invalid treasury sub-operation: {}", sub_operation);
-            Err(GMCError::InvalidInput.into())
-        }
+    if !payer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    if token_account_info.owner != program_id || *system_program_account.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
     }
+    
+    if TokenAccount::unpack(&token_account_info.data.borrow()).is_ok() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let token_account = TokenAccount::unpack_legacy(&token_account_info.data.borrow())?;
+    
+    // 💰 O cabeçalho aumenta a conta: completa a isenção de aluguel antes do realloc
+    let required = Rent::get()?.minimum_balance(TokenAccount::ACCOUNT_LEN);
+    let top_up = required.saturating_sub(token_account_info.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer_account.key, token_account_info.key, top_up),
+            &[payer_account.clone(), token_account_info.clone(), system_program_account.clone()],
+        )?;
+    }
+    
+    token_account_info.realloc(TokenAccount::ACCOUNT_LEN, true)?;
+    token_account.pack(&mut token_account_info.data.borrow_mut())?;
+    
+    msg!("🧳 Token account {} migrated (owner {})", token_account_info.key, token_account.owner);
+    Ok(())
 }
 
-/// 📅 Processador seguro de operações de vesting
-fn process_vesting_special(
+/// 👛 Autoriza um delegado (substitui qualquer aprovação anterior)
+///
+/// Contas esperadas:
+/// 0. `[writable]` Conta de token de origem
+/// 1. `[]` Delegado
+/// 2. `[signer]` Dono da origem
+fn process_approve(
     accounts: &[AccountInfo],
-    sub_operation: u8,
-    param1: u16,
-    param2: u32,
+    amount: u64,
+    program_id: &Pubkey,
 ) -> ProgramResult {
-    msg!("📅 Vesting operation: sub_op={}, param1={}, param2={}", sub_operation, param1, param2);
+    let account_info_iter = &mut accounts.iter();
+    let source_account = next_account_info(account_info_iter)?;
+    let delegate_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
     
-    match sub_operation {
-        1 => {
-            // CREATE_SCHEDULE: param1=duration_months, param2=cliff_months
-            msg!("📋 Creating vesting schedule: duration={} months, cliff={} months", param1, param2);
-            
-            // 🛡️ SECURITY: Validar conforme regras implementadas
-            if param1 != 24 || (param2 as u16) != 6 {
-                msg!("⚠️ Non-standard vesting schedule: using {}/{} instead of 24/6", param1, param2);
-            }
-            
-            // TODO: Chamar vesting::process_create_schedule()
-            msg!("✅ Vesting schedule creation initiated (implementation pending)");
-            Ok(())
-        }
-        2 => {
-            // RELEASE_VESTING: param1=months_elapsed, param2=unused
-            msg!("🎁 Releasing vesting tokens: {} months elapsed", param1);
-            // ESTA FUNÇÃO JÁ ESTÁ ACESSÍVEL!
-            process_team_vesting_release(accounts, param1)
-        }
-        _ => {
-            msg!("❌ Invalid vesting sub-operation: {}", sub_operation);
-            Err(GMCError::InvalidInput.into())
-        }
-    }
+    let mut token_account = load_owned_token_account(source_account, owner_account, program_id)?;
+    token_account.delegate = Some(*delegate_account.key);
+    token_account.delegated_amount = amount;
+    token_account.pack(&mut source_account.data.borrow_mut())?;
+    
+    msg!("👛 Delegate {} approved for {} tokens", delegate_account.key, amount);
+    Ok(())
 }
 
-/// 🎯 Processador seguro de operações de ranking
-fn process_ranking_special(
+/// 👛 Remove o delegado
+///
+/// Contas esperadas:
+/// 0. `[writable]` Conta de token de origem
+/// 1. `[signer]` Dono da origem
+fn process_revoke(
     accounts: &[AccountInfo],
-    sub_operation: u8,
-    param1: u16,
-    param2: u32,
+    program_id: &Pubkey,
 ) -> ProgramResult {
-    msg!("🎯 Ranking operation: sub_op={}, param1={}, param2={}", sub_operation, param1, param2);
-    
-    match sub_operation {
-        1 => {
-            // UPDATE_SCORE: param1=score_to_add, param2=unused
-            msg!("📈 Updating user score: +{} points", param1);
-            // TODO: Chamar ranking::process_update_score()
-            msg!("✅ Score update initiated (implementation pending)");
-            Ok(())
-        }
-        2 => {
-            // DISTRIBUTE_REWARDS: param1=pool_percentage, param2=total_amount
-            msg!("🏆 Distributing ranking rewards: {}% of pool", param1);
-            // TODO: Chamar ranking::process_distribute_rewards()
-            msg!("✅ Reward distribution initiated (implementation pending)");
-            Ok(())
-        }
-        _ => {
-            msg!("❌ Invalid ranking sub-operation: {}", sub_operation);
-            Err(GMCError::InvalidInput.into())
-        }
+    let account_info_iter = &mut accounts.iter();
+    let source_account = next_account_info(account_info_iter)?;
+    let owner_account = next_account_info(account_info_iter)?;
+    
+    let mut token_account = load_owned_token_account(source_account, owner_account, program_id)?;
+    token_account.delegate = None;
+    token_account.delegated_amount = 0;
+    token_account.pack(&mut source_account.data.borrow_mut())?;
+    
+    msg!("👛 Delegate revoked");
+    Ok(())
+}
+
+/// Carrega uma conta de token exigindo a assinatura do seu dono
+fn load_owned_token_account(
+    token_account_info: &AccountInfo,
+    owner_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<TokenAccount, ProgramError> {
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
+    
+    if token_account_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let token_account = TokenAccount::unpack(&token_account_info.data.borrow())?;
+    if !token_account.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    
+    if token_account.owner != *owner_account.key {
+        return Err(GMCError::AccessDenied.into());
+    }
+    
+    Ok(token_account)
 }
 
 // 🔥 FUNÇÕES DE TAXA DE TRANSFERÊNCIA - OTIMIZADAS PARA GAS
@@ -541,9 +976,14 @@ fn process_ranking_special(
 /// Calcula a taxa de transferência de 0.5% e sua distribuição
 /// Otimizado: O(1) complexidade, zero-copy, proteção overflow
 pub fn calculate_transfer_fee(amount: u64) -> Result<TransferFeeDistribution, GMCError> {
+    calculate_transfer_fee_at(amount, TRANSFER_FEE_BASIS_POINTS)
+}
+
+/// Calcula a taxa de transferência para uma alíquota arbitrária (basis points)
+pub fn calculate_transfer_fee_at(amount: u64, fee_basis_points: u16) -> Result<TransferFeeDistribution, GMCError> {
     // 🛡️ Proteção contra overflow
     let fee_amount = amount
-        .checked_mul(TRANSFER_FEE_BASIS_POINTS as u64)
+        .checked_mul(fee_basis_points as u64)
         .and_then(|x| x.checked_div(10000))
         .ok_or(GMCError::ArithmeticOverflow)?;
     
@@ -577,41 +1017,21 @@ pub fn apply_transfer_fee(
     global_state: &mut GlobalState,
     transfer_amount: u64,
 ) -> Result<TransferFeeDistribution, GMCError> {
-    let fee_distribution = calculate_transfer_fee(transfer_amount)?;
-    
-    // 🛡️ Verificar se queima deve parar (12M GMC limite)
-    let new_circulating = global_state.circulating_supply
-        .checked_sub(fee_distribution.burn_amount)
-        .ok_or(GMCError::ArithmeticOverflow)?;
-    
-    if new_circulating < GMC_MINIMUM_SUPPLY {
-        // 🚫 Parar queima, redistribuir para staking/ranking
-        global_state.burn_stopped = true;
-        
-        // Redistribuir valor da queima: 80% staking, 20% ranking
-        let extra_staking = fee_distribution.burn_amount
-            .checked_mul(80)
-            .and_then(|x| x.checked_div(100))
-            .ok_or(GMCError::ArithmeticOverflow)?;
-        
-        let extra_ranking = fee_distribution.burn_amount
-            .checked_sub(extra_staking)
-            .ok_or(GMCError::ArithmeticOverflow)?;
-        
-        return Ok(TransferFeeDistribution {
-            total_fee: fee_distribution.total_fee,
-            burn_amount: 0, // Queima parada
-            staking_amount: fee_distribution.staking_amount + extra_staking,
-            ranking_amount: fee_distribution.ranking_amount + extra_ranking,
-        });
+    let fee_distribution = calculate_transfer_fee_at(transfer_amount, global_state.transfer_fee_basis_points)?;
+    apply_fee_distribution(global_state, fee_distribution)
+}
+
+/// Aplica uma distribuição de taxa já calculada (queima respeita o piso de 12M)
+pub fn apply_fee_distribution(
+    global_state: &mut GlobalState,
+    fee_distribution: TransferFeeDistribution,
+) -> Result<TransferFeeDistribution, GMCError> {
+    // 🔥 Queima parcial até o piso de 12M; o restante vai para staking/ranking
+    let burn = apply_burn(global_state, fee_distribution.burn_amount)?;
+    if burn.rerouted > 0 {
+        return redistribute_burn_share(fee_distribution, burn.rerouted);
     }
     
-    // ✅ Aplicar queima normalmente
-    global_state.circulating_supply = new_circulating;
-    global_state.burned_supply = global_state.burned_supply
-        .checked_add(fee_distribution.burn_amount)
-        .ok_or(GMCError::ArithmeticOverflow)?;
-    
     msg!("🔥 Taxa aplicada: {} GMC queimados, {} para staking, {} para ranking",
          fee_distribution.burn_amount,
          fee_distribution.staking_amount,
@@ -620,61 +1040,113 @@ pub fn apply_transfer_fee(
     Ok(fee_distribution)
 }
 
-/// Processa transferência com taxa automática
-/// Otimizado: Single-pass, compute units eficiente
-pub fn process_transfer_with_fee(
-    accounts: &[AccountInfo],
-    amount: u64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let from_account = next_account_info(account_info_iter)?;
-    let to_account = next_account_info(account_info_iter)?;
-    let global_state_account = next_account_info(account_info_iter)?;
-    let authority_account = next_account_info(account_info_iter)?;
+/// 🔥 Resultado de uma queima limitada pelo piso de 12M
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct BurnOutcome {
+    /// Quantidade efetivamente queimada
+    pub burned: u64,
+    /// Parte que não pôde ser queimada e deve ser redirecionada pelo chamador
+    pub rerouted: u64,
+    pub circulating_supply: u64,
+    pub burn_stopped: bool,
+}
+
+/// 🔥 Rotina de queima compartilhada por todos os módulos (taxa, penalidades, burn-for-boost)
+/// Queima até a capacidade restante acima de `GMC_MINIMUM_SUPPLY`, ativa `burn_stopped`
+/// ao atingir o piso e devolve o excedente para o chamador redirecionar.
+/// Atualiza apenas a contabilidade; a queima SPL é feita pelo chamador com `burned`.
+pub fn apply_burn(global_state: &mut GlobalState, amount: u64) -> Result<BurnOutcome, GMCError> {
+    let capacity = if global_state.burn_stopped {
+        0
+    } else {
+        global_state.circulating_supply.saturating_sub(GMC_MINIMUM_SUPPLY)
+    };
+    let burned = amount.min(capacity);
+    let rerouted = amount - burned;
     
-    // 🛡️ Verificações de segurança
-    if !authority_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    global_state.circulating_supply = global_state.circulating_supply
+        .checked_sub(burned)
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    global_state.burned_supply = global_state.burned_supply
+        .checked_add(burned)
+        .ok_or(GMCError::ArithmeticOverflow)?;
     
-    if amount == 0 {
-        return Err(ProgramError::InvalidArgument);
+    let floor_reached_now = !global_state.burn_stopped && global_state.circulating_supply <= GMC_MINIMUM_SUPPLY;
+    if floor_reached_now {
+        global_state.burn_stopped = true;
     }
     
-    // 📊 Carregar contas (zero-copy quando possível)
-    let mut from_token_account = TokenAccount::try_from_slice(&from_account.data.borrow())?;
-    let mut to_token_account = TokenAccount::try_from_slice(&to_account.data.borrow())?;
-    let mut global_state = GlobalState::try_from_slice(&global_state_account.data.borrow())?;
-    
-    // ✅ Verificar saldo suficiente
-    if from_token_account.balance < amount {
-        return Err(ProgramError::InsufficientFunds);
+    let outcome = BurnOutcome {
+        burned,
+        rerouted,
+        circulating_supply: global_state.circulating_supply,
+        burn_stopped: global_state.burn_stopped,
+    };
+    
+    if floor_reached_now {
+        emit_event("BurnFloorReached", &outcome);
+        msg!("🚫 Piso de 12M GMC atingido: {} queimados, {} redirecionados; queimas futuras serão redirecionadas",
+             burned, rerouted);
+    } else if rerouted > 0 {
+        msg!("🚫 Queima encerrada no piso de 12M: {} GMC redirecionados", rerouted);
     }
     
-    // 🔥 Calcular e aplicar taxa
-    let fee_distribution = apply_transfer_fee(&mut global_state, amount)?;
-    let net_amount = amount
-        .checked_sub(fee_distribution.total_fee)
-        .ok_or(ProgramError::InsufficientFunds)?;
+    Ok(outcome)
+}
+
+/// 📣 Evento estruturado (`sol_log_data`): nome do evento + payload Borsh
+pub fn emit_event<T: BorshSerialize>(name: &str, event: &T) {
+    if let Ok(payload) = event.try_to_vec() {
+        solana_program::log::sol_log_data(&[name.as_bytes(), &payload]);
+    }
+}
+
+/// Divide uma taxa já arrecadada (ex.: retida pelo Token-2022) em 50/40/10
+/// O arredondamento fica com o ranking para que as partes somem o total
+pub fn split_collected_fee(total_fee: u64) -> Result<TransferFeeDistribution, GMCError> {
+    let burn_amount = total_fee
+        .checked_mul(TRANSFER_FEE_BURN_PERCENT as u64)
+        .and_then(|x| x.checked_div(100))
+        .ok_or(GMCError::TransferFeeCalculationError)?;
     
-    // 💸 Executar transferência
-    from_token_account.balance = from_token_account.balance
-        .checked_sub(amount)
-        .ok_or(ProgramError::InsufficientFunds)?;
+    let staking_amount = total_fee
+        .checked_mul(TRANSFER_FEE_STAKING_PERCENT as u64)
+        .and_then(|x| x.checked_div(100))
+        .ok_or(GMCError::TransferFeeCalculationError)?;
     
-    to_token_account.balance = to_token_account.balance
-        .checked_add(net_amount)
-        .ok_or(ProgramError::InvalidArgument)?;
+    let ranking_amount = total_fee
+        .checked_sub(burn_amount)
+        .and_then(|x| x.checked_sub(staking_amount))
+        .ok_or(GMCError::TransferFeeCalculationError)?;
     
-    // 💾 Salvar estados (batch write para eficiência)
-    from_token_account.serialize(&mut &mut from_account.data.borrow_mut()[..])?;
-    to_token_account.serialize(&mut &mut to_account.data.borrow_mut()[..])?;
-    global_state.serialize(&mut &mut global_state_account.data.borrow_mut()[..])?;
+    Ok(TransferFeeDistribution {
+        total_fee,
+        burn_amount,
+        staking_amount,
+        ranking_amount,
+    })
+}
+
+/// Redistribui a parcela não queimada da taxa: 80% staking, 20% ranking
+fn redistribute_burn_share(
+    fee_distribution: TransferFeeDistribution,
+    rerouted: u64,
+) -> Result<TransferFeeDistribution, GMCError> {
+    let extra_staking = rerouted
+        .checked_mul(80)
+        .and_then(|x| x.checked_div(100))
+        .ok_or(GMCError::ArithmeticOverflow)?;
     
-    msg!("✅ Transferência concluída: {} GMC líquidos, {} GMC taxa",
-         net_amount, fee_distribution.total_fee);
+    let extra_ranking = rerouted
+        .checked_sub(extra_staking)
+        .ok_or(GMCError::ArithmeticOverflow)?;
     
-    Ok(())
+    Ok(TransferFeeDistribution {
+        total_fee: fee_distribution.total_fee,
+        burn_amount: fee_distribution.burn_amount - rerouted,
+        staking_amount: fee_distribution.staking_amount + extra_staking,
+        ranking_amount: fee_distribution.ranking_amount + extra_ranking,
+    })
 }
 
 // 🛡️ FUNÇÕES DE SEGURANÇA OWASP - IMPLEMENTAÇÃO TDD
@@ -819,7 +1291,7 @@ pub fn secure_transfer(
             return Err(GMCError::AccessDenied.into());
         }
         
-        let mut global_state = GlobalState::try_from_slice(&global_state_account.data.borrow())?;
+        let mut global_state: GlobalState = discriminator::unpack(&global_state_account.data.borrow())?;
         
         // 🛡️ Verificar autorização específica se necessário
         // check_access_control(&global_state, authority_account.key, "user")?;
@@ -981,45 +1453,6 @@ pub fn calculate_usdt_withdrawal_fee(usdt_amount: u64) -> Result<WithdrawalFeeDi
     })
 }
 
-/// Função integrada para processar cancelamento de staking flexível
-/// Implementa penalidade + distribuição automática
-pub fn process_flexible_staking_cancellation(
-    accounts: &[AccountInfo],
-    stake_amount: u64,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let staker_account = next_account_info(account_info_iter)?;
-    let global_state_account = next_account_info(account_info_iter)?;
-    let authority_account = next_account_info(account_info_iter)?;
-    
-    // 🛡️ Verificações de segurança
-    if !authority_account.is_signer {
-        return Err(GMCError::AccessDenied.into());
-    }
-    
-    // ⚖️ Calcular penalidade
-    let penalty = calculate_flexible_cancellation_penalty(stake_amount)?;
-    
-    let mut global_state = GlobalState::try_from_slice(&global_state_account.data.borrow())?;
-    
-    // 🔥 Aplicar penalidade (queima)
-    global_state.circulating_supply = global_state.circulating_supply
-        .checked_sub(penalty.penalty_amount)
-        .ok_or(GMCError::ArithmeticOverflow)?;
-    
-    global_state.burned_supply = global_state.burned_supply
-        .checked_add(penalty.penalty_amount)
-        .ok_or(GMCError::ArithmeticOverflow)?;
-    
-    // 💾 Salvar estado
-    global_state.serialize(&mut &mut global_state_account.data.borrow_mut()[..])?;
-    
-    msg!("⚖️ Cancelamento flexível processado: {} GMC líquidos, {} GMC penalidade",
-         penalty.net_amount, penalty.penalty_amount);
-    
-    Ok(())
-}
-
 // 🏗️ ESTRUTURAS DE VESTING E DISTRIBUIÇÃO - IMPLEMENTAÇÃO TDD
 // Regras críticas: Vesting Equipe, Time-locks, Supply Inicial, Queima Limite, Carteiras
 
@@ -1271,51 +1704,21 @@ pub fn validate_burn_limit(
     })
 }
 
-/// Função integrada para processar vesting da equipe
-/// Implementa liberação automática baseada em cronograma
-pub fn process_team_vesting_release(
-    accounts: &[AccountInfo],
-    months_elapsed: u16,
-) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
-    let team_account = next_account_info(account_info_iter)?;
-    let vesting_account = next_account_info(account_info_iter)?;
-    let authority_account = next_account_info(account_info_iter)?;
-    
-    // 🛡️ Verificações de segurança
-    if !authority_account.is_signer {
-        return Err(GMCError::AccessDenied.into());
-    }
-    
-    // 📊 Carregar cronograma de vesting
-    let mut vesting_schedule = TeamVestingSchedule::try_from_slice(&vesting_account.data.borrow())?;
-    
-    // 🏗️ Calcular liberação
-    let releasable_amount = calculate_team_vesting_release(&vesting_schedule, months_elapsed)?;
-    let new_release = releasable_amount
-        .checked_sub(vesting_schedule.released_amount)
-        .ok_or(GMCError::ArithmeticOverflow)?;
-    
-    if new_release == 0 {
-        return Err(GMCError::NoVestingDue.into());
-    }
-    
-    // 💾 Atualizar estado
-    vesting_schedule.released_amount = releasable_amount;
-    vesting_schedule.serialize(&mut &mut vesting_account.data.borrow_mut()[..])?;
-    
-    msg!("🏗️ Vesting da equipe liberado: {} GMC (total: {} GMC)",
-         new_release, releasable_amount);
-    
-    Ok(())
-}
-
 // 🏗️ DECLARAÇÃO DOS MÓDULOS PRINCIPAIS
-mod staking;
-mod affiliate;
-mod ranking;
-mod treasury;
-mod vesting;
+// Públicos para que clientes possam montar as instruções aninhadas do GMCInstruction
+pub mod staking;
+pub mod affiliate;
+pub mod ranking;
+pub mod treasury;
+pub mod vesting;
+pub mod token_mode;
+pub mod fee_exemption;
+pub mod genesis;
+pub mod timelock;
+pub mod authority;
+pub mod pause;
+pub mod roles;
+pub mod discriminator;
 mod cpi_batch_optimization;
 
 // 📝 Incluir testes TDD
@@ -1329,15 +1732,14 @@ pub mod safe_math;
 mod tests {
     use super::*;
     use solana_program::clock::Epoch;
-    use std::mem;
 
     #[test]
     fn test_initialize() {
         let program_id = Pubkey::new_unique();
-        let key = Pubkey::new_unique();
+        let key = find_global_state_address(&program_id).0;
         let mut lamports = 0;
-        let mut data = vec![0; mem::size_of::<GlobalState>()];
-        let owner = program_id;
+        let mut data = vec![];
+        let owner = system_program::id();
         let account = AccountInfo::new(
             &key,
             false,
@@ -1363,8 +1765,303 @@ mod tests {
             Epoch::default(),
         );
 
-        let accounts = vec![account, admin_account];
+        let system_key = system_program::id();
+        let mut system_lamports = 0;
+        let mut system_data = vec![];
+        let system_account = AccountInfo::new(
+            &system_key,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &system_key,
+            true,
+            Epoch::default(),
+        );
+
+        let accounts = vec![account, admin_account, system_account];
         assert!(process_initialize(&accounts, 1000, &program_id).is_ok());
     }
+
+    #[test]
+    fn test_instruction_wire_format_is_stable() {
+        // Variantes legadas mantêm suas tags Borsh
+        let transfer = GMCInstruction::Transfer { amount: 42 }.try_to_vec().unwrap();
+        assert_eq!(transfer[0], 1);
+        let revoke = GMCInstruction::RevokeMintAuthority.try_to_vec().unwrap();
+        assert_eq!(revoke, vec![2]);
+
+        // Instruções de módulo são aninhadas após as legadas
+        let stake = GMCInstruction::Staking(staking::StakingInstruction::Stake {
+            pool_id: staking::LONG_TERM_POOL_ID,
+            amount: 1_000,
+        });
+        let data = stake.try_to_vec().unwrap();
+        assert_eq!(data[0], 3);
+        match GMCInstruction::try_from_slice(&data).unwrap() {
+            GMCInstruction::Staking(staking::StakingInstruction::Stake { pool_id, amount }) => {
+                assert_eq!(pool_id, staking::LONG_TERM_POOL_ID);
+                assert_eq!(amount, 1_000);
+            }
+            other => panic!("unexpected instruction: {:?}", other),
+        }
+
+        let distribute = GMCInstruction::Ranking(ranking::RankingInstruction::DistributeRewards);
+        assert_eq!(distribute.try_to_vec().unwrap(), vec![6, 2]);
+
+        // A consulta de versão é sempre a última variante
+        let migrate = GMCInstruction::MigrateTokenAccount.try_to_vec().unwrap();
+        let version = GMCInstruction::GetInstructionSetVersion.try_to_vec().unwrap();
+        assert_eq!(version, vec![migrate[0] + 1]);
+        assert_eq!(GMC_INSTRUCTION_SET_VERSION, 12);
+    }
+
+    #[test]
+    fn test_legacy_encoded_transfer_is_rejected() {
+        // Layout do decodificador legado: [tipo:8][sub_op:8][param1:16][param2:32], big-endian.
+        // Só valores com o byte de tipo 0xFF chegavam ao decodificador
+        let legacy = |instruction_type: u8, sub_op: u8, param1: u16, param2: u32| {
+            let mut bytes = [0u8; 8];
+            bytes[0] = instruction_type;
+            bytes[1] = sub_op;
+            bytes[2..4].copy_from_slice(&param1.to_be_bytes());
+            bytes[4..8].copy_from_slice(&param2.to_be_bytes());
+            u64::from_be_bytes(bytes)
+        };
+        let program_id = Pubkey::new_unique();
+        for encoded in [
+            legacy(0xFF, 1, 1, 1_200),      // CREATE_POOL: pool 1, 1200 bps
+            legacy(0xFF, 2, 1, 1_000),      // STAKE: pool 1, 1000 GMC
+            legacy(0xFF, 3, 1, 0),          // CLAIM_REWARDS: pool 1
+            legacy(0xFF, 4, 0, 500),        // FLEXIBLE_CANCEL: 500
+            legacy(0xFF, 0, 0, 0),
+            legacy(0xFF, 0xFF, u16::MAX, u32::MAX),
+        ] {
+            assert!(encoded >= LEGACY_SPECIAL_INSTRUCTION_THRESHOLD);
+            assert_eq!(
+                process_transfer(&[], encoded, &program_id),
+                Err(ProgramError::Custom(GMCError::LegacyInstructionEncoding as u32))
+            );
+        }
+
+        // Valores abaixo do limiar seguem o caminho normal (aqui falta conta)
+        assert_eq!(process_transfer(&[], legacy(0xFE, 0xFF, u16::MAX, u32::MAX), &program_id), Err(ProgramError::NotEnoughAccountKeys));
+        assert_eq!(process_transfer(&[], 1_000, &program_id), Err(ProgramError::NotEnoughAccountKeys));
+    }
+
+    fn token_account_data(owner: Pubkey, balance: u64) -> Vec<u8> {
+        let mut data = discriminator::to_tagged_vec(&TokenAccount {
+            owner,
+            balance,
+            is_initialized: true,
+            delegated_amount: 0,
+            delegate: None,
+        }).unwrap();
+        data.resize(TokenAccount::ACCOUNT_LEN, 0);
+        data
+    }
+
+    #[test]
+    fn test_transfer_applies_fee_distribution() {
+        let program_id = Pubkey::new_unique();
+        let mut keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        keys[3] = find_global_state_address(&program_id).0;
+        keys.push(pause::find_pause_registry_address(&program_id).0);
+        let token = |balance: u64| token_account_data(keys[2], balance);
+        let registry = |paused_modules: u16, exit_only: bool| discriminator::to_tagged_vec(&pause::PauseRegistry {
+            is_initialized: true,
+            paused_modules,
+            exit_only,
+            ..Default::default()
+        }).unwrap();
+        let global_state = |burn_stopped: bool| GlobalState {
+            total_supply: GMC_TOTAL_SUPPLY,
+            circulating_supply: GMC_TOTAL_SUPPLY,
+            burned_supply: 0,
+            admin: Pubkey::default(),
+            ecosystem_wallets: EcosystemWallets {
+                team: Pubkey::default(),
+                treasury: Pubkey::default(),
+                marketing: Pubkey::default(),
+                airdrop: Pubkey::default(),
+                presale: Pubkey::default(),
+                staking_fund: keys[4],
+                ranking_fund: keys[5],
+            },
+            is_initialized: true,
+            burn_stopped,
+            mint_authority_revoked: false,
+            token_mode: crate::token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
+        };
+        let global_state = |burn_stopped: bool| discriminator::to_tagged_vec(&global_state(burn_stopped)).unwrap();
+
+        // 1000 GMC -> taxa 5 GMC: 2.5 queimados, 2 staking, 0.5 ranking
+        let amount = 1_000_000_000_000;
+        for burn_stopped in [false, true] {
+            let mut data = vec![token(amount), token(0), vec![], global_state(burn_stopped), token(0), token(0), registry(0, false)];
+            let mut lamports = vec![0u64; 7];
+            let accounts: Vec<AccountInfo> = data.iter_mut().zip(lamports.iter_mut()).enumerate()
+                .map(|(i, (d, l))| AccountInfo::new(&keys[i], i == 2, true, l, d, &program_id, false, Epoch::default()))
+                .collect();
+
+            process_transfer(&accounts, amount, &program_id).unwrap();
+
+            let balance = |i: usize| TokenAccount::unpack(&accounts[i].data.borrow()).unwrap().balance;
+            let state: GlobalState = discriminator::unpack(&accounts[3].data.borrow()).unwrap();
+            assert_eq!(balance(0), 0);
+            assert_eq!(balance(1), 995_000_000_000);
+            if burn_stopped {
+                // Queima encerrada: 80% da parcela vai para staking, 20% para ranking
+                assert_eq!(state.burned_supply, 0);
+                assert_eq!(balance(4), 4_000_000_000);
+                assert_eq!(balance(5), 1_000_000_000);
+            } else {
+                assert_eq!(state.burned_supply, 2_500_000_000);
+                assert_eq!(state.circulating_supply, GMC_TOTAL_SUPPLY - 2_500_000_000);
+                assert_eq!(balance(4), 2_000_000_000);
+                assert_eq!(balance(5), 500_000_000);
+            }
+        }
+        
+        // ⏸️ Transferências pausadas não movem saldo
+        for (paused_modules, exit_only) in [(pause::PAUSE_TRANSFERS, false), (0, true)] {
+            let mut data = vec![token(amount), token(0), vec![], global_state(false), token(0), token(0), registry(paused_modules, exit_only)];
+            let mut lamports = vec![0u64; 7];
+            let accounts: Vec<AccountInfo> = data.iter_mut().zip(lamports.iter_mut()).enumerate()
+                .map(|(i, (d, l))| AccountInfo::new(&keys[i], i == 2, true, l, d, &program_id, false, Epoch::default()))
+                .collect();
+            
+            assert_eq!(process_transfer(&accounts, amount, &program_id), Err(GMCError::ProtocolPaused.into()));
+            assert_eq!(TokenAccount::unpack(&accounts[0].data.borrow()).unwrap().balance, amount);
+        }
+    }
+
+    #[test]
+    fn test_legacy_token_accounts_are_migrated_not_reinitialized() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let legacy = TokenAccount {
+            owner,
+            balance: 500,
+            is_initialized: true,
+            delegated_amount: 0,
+            delegate: None,
+        };
+        let mut data = legacy.try_to_vec().unwrap();
+        data.resize(TokenAccount::LEN, 0);
+
+        // O layout legado é reconhecido, mas não passa pelo carregamento marcado
+        assert!(TokenAccount::unpack(&data).is_err());
+        let decoded = TokenAccount::unpack_legacy(&data).unwrap();
+        assert_eq!((decoded.owner, decoded.balance), (owner, 500));
+        assert!(TokenAccount::unpack_legacy(&token_account_data(owner, 500)).is_err());
+
+        // InitializeAccount recusa qualquer conta com dados (não zera saldos legados)
+        data.resize(TokenAccount::ACCOUNT_LEN, 0);
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut owner_data = vec![];
+        let mut owner_lamports = 0u64;
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, Epoch::default());
+        let owner_info = AccountInfo::new(&owner, false, false, &mut owner_lamports, &mut owner_data, &program_id, false, Epoch::default());
+        assert_eq!(
+            process_initialize_account(&[account.clone(), owner_info.clone()], &program_id),
+            Err(ProgramError::AccountAlreadyInitialized)
+        );
+
+        account.data.borrow_mut().fill(0);
+        assert!(process_initialize_account(&[account.clone(), owner_info], &program_id).is_ok());
+        assert_eq!(TokenAccount::unpack(&account.data.borrow()).unwrap().owner, owner);
+    }
+
+    #[test]
+    fn test_owner_and_delegate_debits() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut source = TokenAccount::unpack(&token_account_data(owner, 1_000)).unwrap();
+
+        // Somente o dono debita via Transfer; o delegado precisa de aprovação
+        assert!(source.authorize_debit(&owner, 1_000, false).is_ok());
+        assert_eq!(
+            source.authorize_debit(&delegate, 1, false),
+            Err(GMCError::AccessDenied.into())
+        );
+        assert!(source.authorize_debit(&delegate, 1, true).is_err());
+
+        // Approve/Revoke exigem a assinatura do dono
+        let source_key = Pubkey::new_unique();
+        let mut source_data = token_account_data(owner, 1_000);
+        let (mut l0, mut l1, mut l2) = (0u64, 0u64, 0u64);
+        let (mut d1, mut d2) = (vec![], vec![]);
+        let source_info = AccountInfo::new(&source_key, false, true, &mut l0, &mut source_data, &program_id, false, Epoch::default());
+        let delegate_info = AccountInfo::new(&delegate, false, false, &mut l1, &mut d1, &program_id, false, Epoch::default());
+        let owner_info = AccountInfo::new(&owner, true, false, &mut l2, &mut d2, &program_id, false, Epoch::default());
+        assert_eq!(
+            process_approve(&[source_info.clone(), delegate_info.clone(), delegate_info.clone()], 300, &program_id),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        process_approve(&[source_info.clone(), delegate_info, owner_info.clone()], 300, &program_id).unwrap();
+
+        // O delegado consome a permissão até zerar
+        let mut approved = TokenAccount::unpack(&source_info.data.borrow()).unwrap();
+        assert_eq!(approved.delegate, Some(delegate));
+        assert!(approved.authorize_debit(&delegate, 200, true).is_ok());
+        assert_eq!(approved.authorize_debit(&delegate, 101, true), Err(ProgramError::InsufficientFunds));
+        assert!(approved.authorize_debit(&delegate, 100, true).is_ok());
+        assert_eq!(approved.delegate, None);
+
+        process_revoke(&[source_info.clone(), owner_info], &program_id).unwrap();
+        let revoked = TokenAccount::unpack(&source_info.data.borrow()).unwrap();
+        assert_eq!((revoked.delegate, revoked.delegated_amount), (None, 0));
+    }
+
+    #[test]
+    fn test_burn_routine_stops_at_floor_and_reroutes() {
+        let mut state = GlobalState {
+            total_supply: GMC_TOTAL_SUPPLY,
+            circulating_supply: GMC_MINIMUM_SUPPLY + 300,
+            burned_supply: GMC_TOTAL_SUPPLY - GMC_MINIMUM_SUPPLY - 300,
+            admin: Pubkey::default(),
+            ecosystem_wallets: EcosystemWallets {
+                team: Pubkey::default(),
+                treasury: Pubkey::default(),
+                marketing: Pubkey::default(),
+                airdrop: Pubkey::default(),
+                presale: Pubkey::default(),
+                staking_fund: Pubkey::default(),
+                ranking_fund: Pubkey::default(),
+            },
+            is_initialized: true,
+            burn_stopped: false,
+            mint_authority_revoked: false,
+            token_mode: crate::token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
+        };
+
+        // Abaixo da capacidade: queima integral
+        let outcome = apply_burn(&mut state, 100).unwrap();
+        assert_eq!((outcome.burned, outcome.rerouted, outcome.burn_stopped), (100, 0, false));
+
+        // Cruzando o piso: queima parcial, excedente redirecionado e queima encerrada
+        let outcome = apply_burn(&mut state, 500).unwrap();
+        assert_eq!((outcome.burned, outcome.rerouted, outcome.burn_stopped), (200, 300, true));
+        assert_eq!(state.circulating_supply, GMC_MINIMUM_SUPPLY);
+        assert_eq!(state.circulating_supply + state.burned_supply, GMC_TOTAL_SUPPLY);
+
+        // Depois do piso tudo é redirecionado; na taxa, 80% staking e 20% ranking
+        let fee = apply_fee_distribution(&mut state, split_collected_fee(1_000).unwrap()).unwrap();
+        assert_eq!((fee.burn_amount, fee.staking_amount, fee.ranking_amount), (0, 800, 200));
+        assert_eq!(state.circulating_supply, GMC_MINIMUM_SUPPLY);
+    }
 } 
 
@@ -0,0 +1,334 @@
+// ⏸️ GMC Token - Protocol-wide emergency pause
+// One registry PDA holds a pause bit per module; every gated processor reads it.
+// The admin or a quorum of treasury signers flips the bits; `Pauser` role holders
+// may only add pauses. "Exit-only" mode
+// halts everything except unstaking, which then returns principal only.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{discriminator, emit_event, load_global_state, roles::{self, Role}, treasury::TreasuryState, GMCError};
+
+/// Seed of the pause registry PDA (`["pause_registry"]`)
+pub const PAUSE_REGISTRY_SEED: &[u8] = b"pause_registry";
+
+/// 📤 Transfer / TransferFrom
+pub const PAUSE_TRANSFERS: u16 = 1 << 0;
+/// 🥩 Staking entry
+pub const PAUSE_STAKE: u16 = 1 << 1;
+/// 🎁 Staking reward claims
+pub const PAUSE_CLAIMS: u16 = 1 << 2;
+/// 📤 Unstake
+pub const PAUSE_UNSTAKE: u16 = 1 << 3;
+/// 📅 Vesting releases (module schedules and team vesting)
+pub const PAUSE_VESTING_RELEASE: u16 = 1 << 4;
+/// 🏆 Ranking payouts
+pub const PAUSE_RANKING_PAYOUTS: u16 = 1 << 5;
+/// 🤝 Affiliate registration
+pub const PAUSE_AFFILIATE_REGISTRATION: u16 = 1 << 6;
+/// Every module flag
+pub const PAUSE_ALL: u16 = PAUSE_TRANSFERS
+    | PAUSE_STAKE
+    | PAUSE_CLAIMS
+    | PAUSE_UNSTAKE
+    | PAUSE_VESTING_RELEASE
+    | PAUSE_RANKING_PAYOUTS
+    | PAUSE_AFFILIATE_REGISTRATION;
+
+/// Pause state stored at `["pause_registry"]`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct PauseRegistry {
+    pub is_initialized: bool,
+    /// Treasury state whose signers may also control the pause
+    pub treasury: Pubkey,
+    pub paused_modules: u16,
+    pub exit_only: bool,
+    pub updated_at: i64,
+    pub updated_by: Pubkey,
+}
+
+impl PauseRegistry {
+    pub const LEN: usize = 1 + 32 + 2 + 1 + 8 + 32; // 76 bytes
+
+    /// Exit-only halts every module except unstaking
+    pub fn is_paused(&self, flag: u16) -> bool {
+        self.paused_modules & flag != 0 || (self.exit_only && flag != PAUSE_UNSTAKE)
+    }
+
+    /// Whether the new state keeps every current pause (what a `Pauser` may do)
+    pub fn only_tightens(&self, paused_modules: u16, exit_only: bool) -> bool {
+        paused_modules & self.paused_modules == self.paused_modules && (exit_only || !self.exit_only)
+    }
+}
+
+/// 📣 Event logged (as `sol_log_data`) whenever the pause state changes
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PauseEvent {
+    pub paused_modules: u16,
+    pub exit_only: bool,
+    pub updated_by: Pubkey,
+    pub updated_at: i64,
+}
+
+/// Derive the pause registry address
+pub fn find_pause_registry_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAUSE_REGISTRY_SEED], program_id)
+}
+
+/// Load the registry after checking it is the program's PDA
+pub fn load_registry(program_id: &Pubkey, registry_info: &AccountInfo) -> Result<PauseRegistry, ProgramError> {
+    if *registry_info.key != find_pause_registry_address(program_id).0 {
+        msg!("🚨 Security Alert: Invalid pause registry");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let registry: PauseRegistry = discriminator::load(program_id, registry_info)?;
+    if !registry.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(registry)
+}
+
+/// 🛡️ Fail with `ProtocolPaused` when `flag` is paused
+pub fn require_not_paused(program_id: &Pubkey, registry_info: &AccountInfo, flag: u16) -> ProgramResult {
+    if load_registry(program_id, registry_info)?.is_paused(flag) {
+        msg!("⏸️ Protocol paused for this operation (flag {:#06x})", flag);
+        return Err(GMCError::ProtocolPaused.into());
+    }
+    Ok(())
+}
+
+/// The admin alone, or `required_signatures` distinct treasury signers, may change the pause
+pub fn pause_authority(
+    admin: &Pubkey,
+    treasury_key: &Pubkey,
+    treasury: &TreasuryState,
+    signers: &[Pubkey],
+) -> Result<Pubkey, ProgramError> {
+    if signers.contains(admin) {
+        return Ok(*admin);
+    }
+
+    let treasury_signers = &treasury.signers[..(treasury.active_signers as usize).min(treasury.signers.len())];
+    let approvals = treasury_signers
+        .iter()
+        .filter(|signer| signers.contains(signer))
+        .count();
+    if treasury.is_initialized && treasury.required_signatures > 0 && approvals >= treasury.required_signatures as usize {
+        return Ok(*treasury_key);
+    }
+
+    msg!("⚠️ No admin or treasury quorum among the signers ({}/{})",
+         approvals, treasury.required_signatures);
+    Err(GMCError::AccessDenied.into())
+}
+
+fn log_event(registry: &PauseRegistry) {
+    let event = PauseEvent {
+        paused_modules: registry.paused_modules,
+        exit_only: registry.exit_only,
+        updated_by: registry.updated_by,
+        updated_at: registry.updated_at,
+    };
+    emit_event("PauseUpdated", &event);
+    msg!("📣 PauseUpdated: modules {:#06x}, exit-only {} by {}",
+         event.paused_modules, event.exit_only, event.updated_by);
+}
+
+/// ⏸️ Create the pause registry (nothing paused)
+///
+/// Accounts expected:
+/// 0. `[writable, signer]` Admin (payer)
+/// 1. `[]` Global state PDA (`["global_state"]`)
+/// 2. `[writable]` Pause registry PDA (`["pause_registry"]`)
+/// 3. `[]` Treasury state whose signers may also control the pause
+/// 4. `[]` System program
+pub fn process_initialize_pause_registry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let admin_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let registry_info = next_account_info(account_info_iter)?;
+    let treasury_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can create the pause registry");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    let treasury: TreasuryState = discriminator::load(program_id, treasury_info)?;
+    if !treasury.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let (registry_key, bump) = find_pause_registry_address(program_id);
+    if *registry_info.key != registry_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if registry_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    crate::create_pda_account(
+        program_id,
+        admin_info,
+        registry_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + PauseRegistry::LEN,
+        &[PAUSE_REGISTRY_SEED, &[bump]],
+    )?;
+
+    let registry = PauseRegistry {
+        is_initialized: true,
+        treasury: *treasury_info.key,
+        paused_modules: 0,
+        exit_only: false,
+        updated_at: Clock::get()?.unix_timestamp,
+        updated_by: *admin_info.key,
+    };
+    discriminator::store(program_id, registry_info, &registry)?;
+
+    msg!("⏸️ Pause registry created (treasury {})", treasury_info.key);
+    Ok(())
+}
+
+/// ⏸️ Replace the paused module set and the exit-only switch
+///
+/// Accounts expected:
+/// 0. `[writable]` Pause registry PDA
+/// 1. `[]` Global state PDA (`["global_state"]`)
+/// 2. `[]` Treasury state bound to the registry
+/// 3. `[]` Role registry PDA (consulted for `Pauser` holders)
+/// 4. `[signer]` The admin, at least `required_signatures` treasury signers, or a
+///    `Pauser` that only adds pauses (one account each)
+pub fn process_set_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused_modules: u16,
+    exit_only: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let registry_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let treasury_info = next_account_info(account_info_iter)?;
+    let roles_info = next_account_info(account_info_iter)?;
+    let signers: Vec<Pubkey> = account_info_iter
+        .filter(|info| info.is_signer)
+        .map(|info| *info.key)
+        .collect();
+
+    if paused_modules & !PAUSE_ALL != 0 {
+        msg!("❌ Unknown pause flags: {:#06x}", paused_modules & !PAUSE_ALL);
+        return Err(GMCError::InvalidInput.into());
+    }
+
+    let mut registry = load_registry(program_id, registry_info)?;
+
+    if *treasury_info.key != registry.treasury {
+        msg!("🚨 Security Alert: Treasury does not match the pause registry");
+        return Err(GMCError::InvalidAuthority.into());
+    }
+
+    let global_state = load_global_state(program_id, global_state_info)?;
+    let treasury: TreasuryState = discriminator::load(program_id, treasury_info)?;
+    let updated_by = match pause_authority(&global_state.admin, treasury_info.key, &treasury, &signers) {
+        Ok(authority) => authority,
+        Err(err) => {
+            // 🎭 Pausers can halt modules during an incident but never lift a pause
+            let role_registry = roles::load_registry(program_id, roles_info)?;
+            let pauser = signers
+                .iter()
+                .find(|signer| role_registry.has_role(Role::Pauser, signer))
+                .ok_or(err)?;
+            if !registry.only_tightens(paused_modules, exit_only) {
+                msg!("🚨 Security Alert: A pauser cannot lift pauses");
+                return Err(GMCError::AccessDenied.into());
+            }
+            *pauser
+        }
+    };
+
+    registry.paused_modules = paused_modules;
+    registry.exit_only = exit_only;
+    registry.updated_at = Clock::get()?.unix_timestamp;
+    registry.updated_by = updated_by;
+    discriminator::store(program_id, registry_info, &registry)?;
+
+    log_event(&registry);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_only_halts_everything_but_unstake() {
+        let mut registry = PauseRegistry { is_initialized: true, ..Default::default() };
+        assert_eq!(registry.try_to_vec().unwrap().len(), PauseRegistry::LEN);
+        assert!(!registry.is_paused(PAUSE_TRANSFERS));
+
+        registry.paused_modules = PAUSE_STAKE | PAUSE_CLAIMS;
+        assert!(registry.is_paused(PAUSE_STAKE));
+        assert!(registry.is_paused(PAUSE_CLAIMS));
+        assert!(!registry.is_paused(PAUSE_TRANSFERS));
+        assert!(!registry.is_paused(PAUSE_UNSTAKE));
+
+        registry.paused_modules = 0;
+        registry.exit_only = true;
+        for flag in [PAUSE_TRANSFERS, PAUSE_STAKE, PAUSE_CLAIMS, PAUSE_VESTING_RELEASE, PAUSE_RANKING_PAYOUTS, PAUSE_AFFILIATE_REGISTRATION] {
+            assert!(registry.is_paused(flag));
+        }
+        assert!(!registry.is_paused(PAUSE_UNSTAKE));
+
+        // Pausers may add flags or enter exit-only, never remove either
+        registry.paused_modules = PAUSE_STAKE;
+        registry.exit_only = false;
+        assert!(registry.only_tightens(PAUSE_STAKE | PAUSE_CLAIMS, false));
+        assert!(registry.only_tightens(PAUSE_STAKE, true));
+        assert!(!registry.only_tightens(PAUSE_CLAIMS, false));
+        registry.exit_only = true;
+        assert!(!registry.only_tightens(PAUSE_STAKE, false));
+    }
+
+    #[test]
+    fn test_admin_or_treasury_quorum_controls_the_pause() {
+        let admin = Pubkey::new_unique();
+        let treasury_key = Pubkey::new_unique();
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut treasury = TreasuryState::default();
+        treasury.is_initialized = true;
+        treasury.signers[..3].copy_from_slice(&members);
+        treasury.active_signers = 3;
+        treasury.required_signatures = 2;
+
+        assert_eq!(pause_authority(&admin, &treasury_key, &treasury, &[admin]), Ok(admin));
+        assert_eq!(pause_authority(&admin, &treasury_key, &treasury, &members[..2]), Ok(treasury_key));
+
+        // One signer, or the same signer twice, is not a quorum
+        assert_eq!(
+            pause_authority(&admin, &treasury_key, &treasury, &[members[0], members[0]]),
+            Err(GMCError::AccessDenied.into())
+        );
+        assert_eq!(
+            pause_authority(&admin, &treasury_key, &treasury, &[Pubkey::new_unique()]),
+            Err(GMCError::AccessDenied.into())
+        );
+    }
+}
@@ -12,7 +12,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
-use crate::GMCError;
+use crate::{discriminator, GMCError};
 
 // OPTIMIZATION: Reduced leaderboard size for better memory efficiency
 pub const MAX_LEADERBOARD_SIZE: usize = 25; // Reduced from 50 to 25
@@ -80,8 +80,9 @@ pub enum RankingInstruction {
     /// Updates the score for a user.
     /// (Typically called by an authorized program/authority).
     /// Accounts expected:
-    /// 0. `[signer]` The authority account.
+    /// 0. `[signer]` The ranking authority or a `RankingScoreUpdater` role holder.
     /// 1. `[writable]` The ranking state account.
+    /// 2. `[]` Optional: the role registry PDA (required for role holders).
     UpdateScore {
         user_pubkey: Pubkey,
         score_to_add: u64,
@@ -93,6 +94,7 @@ pub enum RankingInstruction {
     /// 1. `[writable]` The ranking state account.
     /// 2. `[writable]` The ranking pool token account (source of funds).
     /// 3. `[]` The token program.
+    /// 4. `[]` The pause registry PDA.
     /// .. `[writable]` Winner token accounts (up to MAX_LEADERBOARD_SIZE).
     DistributeRewards,
 }
@@ -106,6 +108,15 @@ pub fn process_instruction(
 ) -> ProgramResult {
     let instruction = RankingInstruction::try_from_slice(instruction_data)?;
 
+    process_ranking_instruction(program_id, accounts, instruction)
+}
+
+/// Processes an already-deserialized instruction (used by the top-level entrypoint).
+pub fn process_ranking_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: RankingInstruction,
+) -> ProgramResult {
     match instruction {
         RankingInstruction::Initialize => {
             msg!("Instruction: Initialize Ranking");
@@ -113,7 +124,7 @@ pub fn process_instruction(
         }
         RankingInstruction::UpdateScore { user_pubkey, score_to_add } => {
             msg!("Instruction: Update Score for {}", user_pubkey);
-            process_update_score(accounts, user_pubkey, score_to_add)
+            process_update_score(program_id, accounts, user_pubkey, score_to_add)
         }
         RankingInstruction::DistributeRewards => {
             msg!("Instruction: Distribute Rewards");
@@ -124,6 +135,7 @@ pub fn process_instruction(
 
 /// 🚀 OPTIMIZED: Updates a user's score with minimal gas usage
 pub fn process_update_score(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     user_pubkey: Pubkey,
     score_to_add: u64,
@@ -131,6 +143,7 @@ pub fn process_update_score(
     let account_info_iter = &mut accounts.iter();
     let authority_info = next_account_info(account_info_iter)?;
     let ranking_state_info = next_account_info(account_info_iter)?;
+    let roles_info = account_info_iter.next();
 
     // 🛡️ Security Checks (optimized order for early returns)
     if !authority_info.is_signer {
@@ -142,11 +155,13 @@ pub fn process_update_score(
         return Ok(()); // Skip processing for very low scores
     }
 
-    let mut ranking_state = RankingState::try_from_slice(&ranking_state_info.data.borrow())?;
+    let mut ranking_state: RankingState = discriminator::load(program_id, ranking_state_info)?;
     if !ranking_state.is_initialized || !ranking_state.is_active {
         return Err(ProgramError::Custom(GMCError::RankingInactive as u32));
     }
-    if *authority_info.key != ranking_state.authority {
+    if *authority_info.key != ranking_state.authority
+        && !crate::roles::has_role(program_id, roles_info, crate::roles::Role::RankingScoreUpdater, authority_info.key)?
+    {
         return Err(ProgramError::Custom(GMCError::InvalidAuthority as u32));
     }
 
@@ -202,14 +217,14 @@ pub fn process_update_score(
         }
     }
 
-    ranking_state.serialize(&mut *ranking_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, ranking_state_info, &ranking_state)?;
 
     Ok(())
 }
 
 /// 🛡️ Distributes rewards to the winners and resets the season.
 pub fn process_distribute_rewards(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
@@ -218,12 +233,14 @@ pub fn process_distribute_rewards(
     let ranking_state_info = next_account_info(account_info_iter)?;
     let ranking_pool_info = next_account_info(account_info_iter)?;
     let token_program_info = next_account_info(account_info_iter)?;
+    let pause_registry_info = next_account_info(account_info_iter)?;
 
     // Security Checks
     if !authority_info.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
-    let mut ranking_state = RankingState::try_from_slice(&ranking_state_info.data.borrow())?;
+    crate::pause::require_not_paused(program_id, pause_registry_info, crate::pause::PAUSE_RANKING_PAYOUTS)?;
+    let mut ranking_state: RankingState = discriminator::load(program_id, ranking_state_info)?;
     if !ranking_state.is_initialized || !ranking_state.is_active {
         return Err(ProgramError::Custom(GMCError::RankingInactive as u32));
     }
@@ -260,7 +277,7 @@ pub fn process_distribute_rewards(
         ranking_state.total_prize_pool = ranking_state.monthly_prize_pool.saturating_add(ranking_state.annual_prize_pool);
         ranking_state.active_entries = 0;
         ranking_state.leaderboard = [RankEntry::default(); MAX_LEADERBOARD_SIZE];
-        ranking_state.serialize(&mut *ranking_state_info.data.borrow_mut())?;
+        discriminator::store(program_id, ranking_state_info, &ranking_state)?;
         return Ok(());
     }
     let remaining_accounts = account_info_iter.as_slice();
@@ -316,7 +333,7 @@ pub fn process_distribute_rewards(
     ranking_state.total_prize_pool = prize_pool.saturating_sub(distributed_amount);
     ranking_state.leaderboard = [RankEntry::default(); MAX_LEADERBOARD_SIZE];
 
-    ranking_state.serialize(&mut *ranking_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, ranking_state_info, &ranking_state)?;
 
     Ok(())
 }
@@ -325,6 +342,7 @@ pub fn process_distribute_rewards(
 /// This function implements the core requirement: 90% monthly, 10% annual accumulation
 #[allow(dead_code)]
 pub fn add_funds_to_ranking_pools(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     total_amount: u64,
 ) -> ProgramResult {
@@ -337,7 +355,7 @@ pub fn add_funds_to_ranking_pools(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    let mut ranking_state = RankingState::try_from_slice(&ranking_state_info.data.borrow())?;
+    let mut ranking_state: RankingState = discriminator::load(program_id, ranking_state_info)?;
     if !ranking_state.is_initialized {
         return Err(ProgramError::Custom(GMCError::RankingNotInitialized as u32));
     }
@@ -356,7 +374,7 @@ pub fn add_funds_to_ranking_pools(
 
     msg!("💰 Funds added to ranking pools: Monthly: {} GMC, Annual: {} GMC", monthly_amount, annual_amount);
     
-    ranking_state.serialize(&mut *ranking_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, ranking_state_info, &ranking_state)?;
     Ok(())
 }
 
@@ -377,24 +395,24 @@ pub fn process_initialize(
     if ranking_state_info.owner != program_id {
         return Err(ProgramError::IllegalOwner);
     }
-
-    let mut ranking_state = RankingState::try_from_slice(&ranking_state_info.data.borrow())?;
-    if ranking_state.is_initialized {
+    if !discriminator::is_untagged(&ranking_state_info.data.borrow()) {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
 
     // 🚀 OPTIMIZATION: Initialize state with optimized field order
-    ranking_state.is_initialized = true;
-    ranking_state.authority = *authority_info.key;
-    ranking_state.is_active = false; // Starts inactive, requires explicit activation
-    ranking_state.season_id = 0;
-    ranking_state.season_end_timestamp = 0;
-    ranking_state.total_prize_pool = 0;
-    ranking_state.active_entries = 0;
-
-    ranking_state.leaderboard = [RankEntry::default(); MAX_LEADERBOARD_SIZE];
+    let ranking_state = RankingState {
+        is_initialized: true,
+        authority: *authority_info.key,
+        is_active: false, // Starts inactive, requires explicit activation
+        season_id: 0,
+        season_end_timestamp: 0,
+        total_prize_pool: 0,
+        active_entries: 0,
+        leaderboard: [RankEntry::default(); MAX_LEADERBOARD_SIZE],
+        ..RankingState::default()
+    };
 
-    ranking_state.serialize(&mut *ranking_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, ranking_state_info, &ranking_state)?;
 
     Ok(())
 }
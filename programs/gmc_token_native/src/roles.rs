@@ -0,0 +1,269 @@
+// 🎭 GMC Token - Role-based access control
+// Operational keys get narrow roles (score updater, treasury proposer, vesting
+// creator, pool manager, pauser) instead of sharing a module authority. Grants
+// live in one PDA next to GlobalState and only the admin grants or revokes them.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{discriminator, emit_event, load_global_state, validate_pubkey_not_default, GMCError};
+
+/// Seed of the role registry PDA
+pub const ROLES_SEED: &[u8] = b"roles";
+
+/// Maximum number of (role, member) grants
+pub const MAX_ROLE_GRANTS: usize = 32;
+
+/// Operational role
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Pushes ranking scores (`Ranking::UpdateScore`)
+    RankingScoreUpdater,
+    /// Proposes treasury transactions without being a multisig signer
+    TreasuryProposer,
+    /// Creates vesting schedules (emergency release stays with the vesting authority)
+    VestingCreator,
+    /// Creates the predefined staking pools (APY changes stay time-locked)
+    PoolManager,
+    /// Pauses modules; lifting a pause still needs the admin or the treasury quorum
+    Pauser,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RoleGrant {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct RoleRegistry {
+    pub is_initialized: bool,
+    pub grants: Vec<RoleGrant>,
+}
+
+impl RoleRegistry {
+    pub const LEN: usize = 1 + 4 + MAX_ROLE_GRANTS * (1 + 32 + 8);
+
+    pub fn has_role(&self, role: Role, member: &Pubkey) -> bool {
+        self.grants.iter().any(|grant| grant.role == role && grant.member == *member)
+    }
+
+    pub fn grant(&mut self, role: Role, member: Pubkey, now: i64) -> ProgramResult {
+        if self.has_role(role, &member) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if self.grants.len() >= MAX_ROLE_GRANTS {
+            msg!("❌ Role registry is full ({} grants)", MAX_ROLE_GRANTS);
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+
+        self.grants.push(RoleGrant { role, member, granted_at: now });
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, role: Role, member: &Pubkey) -> ProgramResult {
+        let index = self.grants
+            .iter()
+            .position(|grant| grant.role == role && grant.member == *member)
+            .ok_or(ProgramError::InvalidArgument)?;
+        self.grants.swap_remove(index);
+        Ok(())
+    }
+}
+
+/// 📣 Event logged (as `sol_log_data`) whenever a role is granted or revoked
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct RoleEvent {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted: bool,
+    pub authority: Pubkey,
+}
+
+fn log_event(name: &str, event: &RoleEvent) -> ProgramResult {
+    emit_event(name, event);
+    msg!("📣 {}: {:?} {}", name, event.role, event.member);
+    Ok(())
+}
+
+/// Derive the role registry address
+pub fn find_roles_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROLES_SEED], program_id)
+}
+
+/// Load the registry after checking it is the program's PDA
+pub fn load_registry(program_id: &Pubkey, roles_info: &AccountInfo) -> Result<RoleRegistry, ProgramError> {
+    if *roles_info.key != find_roles_address(program_id).0 {
+        msg!("🚨 Security Alert: Invalid role registry account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let registry: RoleRegistry = discriminator::load(program_id, roles_info)?;
+    if !registry.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(registry)
+}
+
+/// Whether `member` holds `role`; without a registry account nobody does
+pub fn has_role(
+    program_id: &Pubkey,
+    roles_info: Option<&AccountInfo>,
+    role: Role,
+    member: &Pubkey,
+) -> Result<bool, ProgramError> {
+    let Some(roles_info) = roles_info else {
+        return Ok(false);
+    };
+    Ok(load_registry(program_id, roles_info)?.has_role(role, member))
+}
+
+fn load_admin_and_registry<'a, 'info>(
+    program_id: &Pubkey,
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>), ProgramError> {
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let roles_info = next_account_info(account_info_iter)?;
+
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can manage roles");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    if *roles_info.key != find_roles_address(program_id).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok((admin_info, roles_info))
+}
+
+/// 🏗️ Create the role registry PDA
+///
+/// Accounts expected:
+/// 0. `[]` Global state
+/// 1. `[writable, signer]` Admin (payer)
+/// 2. `[writable]` Role registry PDA (`["roles"]`)
+/// 3. `[]` System program
+pub fn process_initialize_roles(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, roles_info) = load_admin_and_registry(program_id, account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if roles_info.owner == program_id && roles_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let (_, bump) = find_roles_address(program_id);
+    crate::create_pda_account(
+        program_id,
+        admin_info,
+        roles_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + RoleRegistry::LEN,
+        &[ROLES_SEED, &[bump]],
+    )?;
+
+    let registry = RoleRegistry { is_initialized: true, grants: Vec::new() };
+    discriminator::store(program_id, roles_info, &registry)?;
+
+    msg!("🎭 Role registry initialized");
+    Ok(())
+}
+
+/// ➕ Grant a role
+///
+/// Accounts expected:
+/// 0. `[]` Global state
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Role registry PDA
+pub fn process_grant_role(program_id: &Pubkey, accounts: &[AccountInfo], role: Role, member: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, roles_info) = load_admin_and_registry(program_id, account_info_iter)?;
+    validate_pubkey_not_default(&member)?;
+
+    let mut registry = load_registry(program_id, roles_info)?;
+    registry.grant(role, member, Clock::get()?.unix_timestamp)?;
+    discriminator::store(program_id, roles_info, &registry)?;
+
+    log_event("RoleGranted", &RoleEvent {
+        role,
+        member,
+        granted: true,
+        authority: *admin_info.key,
+    })
+}
+
+/// ➖ Revoke a role
+///
+/// Accounts expected:
+/// 0. `[]` Global state
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Role registry PDA
+pub fn process_revoke_role(program_id: &Pubkey, accounts: &[AccountInfo], role: Role, member: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let (admin_info, roles_info) = load_admin_and_registry(program_id, account_info_iter)?;
+
+    let mut registry = load_registry(program_id, roles_info)?;
+    registry.revoke(role, &member)?;
+    // Shrinking leaves stale bytes past the Vec; loading ignores them
+    discriminator::store(program_id, roles_info, &registry)?;
+
+    log_event("RoleRevoked", &RoleEvent {
+        role,
+        member,
+        granted: false,
+        authority: *admin_info.key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_are_granted_per_member_and_revoked() {
+        let mut registry = RoleRegistry { is_initialized: true, grants: Vec::new() };
+        let bot = Pubkey::new_unique();
+        let now = 1_700_000_000;
+
+        registry.grant(Role::RankingScoreUpdater, bot, now).unwrap();
+        assert!(registry.has_role(Role::RankingScoreUpdater, &bot));
+        assert!(!registry.has_role(Role::VestingCreator, &bot));
+        assert_eq!(registry.grant(Role::RankingScoreUpdater, bot, now), Err(ProgramError::AccountAlreadyInitialized));
+
+        // Full registry still fits the account
+        while registry.grants.len() < MAX_ROLE_GRANTS {
+            registry.grant(Role::Pauser, Pubkey::new_unique(), now).unwrap();
+        }
+        assert!(registry.grant(Role::Pauser, Pubkey::new_unique(), now).is_err());
+        let mut data = discriminator::to_tagged_vec(&registry).unwrap();
+        assert_eq!(data.len(), discriminator::ACCOUNT_HEADER_LEN + RoleRegistry::LEN);
+
+        registry.revoke(Role::RankingScoreUpdater, &bot).unwrap();
+        discriminator::pack(&registry, &mut data).unwrap();
+        let reloaded: RoleRegistry = discriminator::unpack(&data).unwrap();
+        assert!(!reloaded.has_role(Role::RankingScoreUpdater, &bot));
+        assert_eq!(reloaded.grants.len(), MAX_ROLE_GRANTS - 1);
+        assert!(registry.revoke(Role::RankingScoreUpdater, &bot).is_err());
+    }
+}
@@ -9,7 +9,7 @@ mod security_tests {
     use crate::{
         validate_input_amount, check_access_control, ReentrancyGuard,
         validate_timestamp, validate_pubkey_not_default, simulate_expensive_operation,
-        GMCError, GlobalState, EcosystemWallets
+        GMCError, GlobalState, EcosystemWallets, TRANSFER_FEE_BASIS_POINTS
     };
 
     // 🔴 RED: Teste de Validação de Entrada
@@ -173,6 +173,12 @@ mod security_tests {
             is_initialized: true,
             burn_stopped: false,
             mint_authority_revoked: false,
+            token_mode: crate::token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
         }
     }
 
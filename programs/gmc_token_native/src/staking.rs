@@ -8,46 +8,24 @@
 // - Business Rules from TABELA_MELHORADA_REGRAS_STAKING.md
 
 use solana_program::{
-    account_info::AccountInfo,
+    account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
     clock::Clock,
     sysvar::Sysvar,
-    program::invoke,
-    instruction::Instruction,
+    program::{invoke, invoke_signed},
+    program_pack::Pack,
+    system_program,
 };
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use crate::GMCError;
+use crate::{discriminator, GMCError};
 use crate::safe_math::*;
 
 // 🚀 OTIMIZAÇÃO: Módulos integrados diretamente no staking.rs
 
-// 🔥 LOOKUP TABLES PARA BOOST (integradas dos módulos removidos)
-const BURN_BOOST_LOOKUP: [u16; 256] = {
-    let mut lookup = [0u16; 256];
-    let mut i = 0;
-    while i < 256 {
-        // Boost progressivo: 0-50% baseado no nível de burn
-        lookup[i] = (i as u16 * 50) / 255; // 0% a 50% boost
-        i += 1;
-    }
-    lookup
-};
-
-const AFFILIATE_BOOST_LOOKUP: [u16; 100] = {
-    let mut lookup = [0u16; 100];
-    let mut i = 0;
-    while i < 100 {
-        // Boost progressivo: 0-50% baseado no poder de afiliados
-        lookup[i] = (i as u16 * 50) / 99; // 0% a 50% boost
-        i += 1;
-    }
-    lookup
-};
-
 // 🚀 OPTIMIZED: Staking Pool Configuration with better memory layout
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 #[repr(C)] // 🚀 OPTIMIZATION: Explicit memory layout
@@ -61,12 +39,106 @@ pub struct StakingPool {
     pub lock_duration_days: u16,    // 🚀 OPTIMIZATION: u16 instead of u32 (2 bytes)
     pub pool_id: u8,                // 1 byte
     pub is_active: bool,            // 1 byte
+    pub acc_reward_per_share: u128, // 16 bytes - rewards per share, scaled by REWARD_PRECISION
+    pub total_reward_shares: u64,   // 8 bytes - sum of active records' reward shares
+    pub reward_budget: u64,         // 8 bytes - funded rewards not yet emitted
+    pub last_reward_update: u32,    // 4 bytes - last accumulator update timestamp
     pub _padding: [u8; 6],          // 🚀 OPTIMIZATION: Explicit padding for alignment
 }
 
 impl StakingPool {
     #[allow(dead_code)]
-    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 2 + 2 + 1 + 1 + 6; // 🚀 OPTIMIZATION: 76 bytes (vs 88)
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 2 + 2 + 1 + 1 + 16 + 8 + 8 + 4 + 6; // 112 bytes
+    
+    /// Fixed-point scale of `acc_reward_per_share`
+    pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+    
+    // 🚀 APY applied to a record: pool APY + affiliate boost, capped by the pool type maximum
+    pub fn effective_apy(&self, affiliate_power: u8) -> Result<u16, ProgramError> {
+        let stake_type = stake_type_for_pool(self.pool_id)?;
+        let max_apy = if stake_type == "long-term" { LONG_TERM_MAX_APY } else { FLEXIBLE_MAX_APY };
+        Ok(std::cmp::min(
+            self.apy_basis_points.saturating_add(affiliate_boost_basis_points(stake_type, affiliate_power)),
+            std::cmp::max(max_apy, self.apy_basis_points),
+        ))
+    }
+    
+    // 🧮 Reward shares of a position: principal weighted by burn boost and by the
+    // affiliate-boosted APY relative to the pool APY
+    pub fn reward_shares(&self, amount: u64, burn_boost_multiplier: u16, affiliate_power: u8) -> Result<u64, ProgramError> {
+        if self.apy_basis_points == 0 {
+            return Ok(0);
+        }
+        
+        let effective_apy = self.effective_apy(affiliate_power)?;
+        let shares = (amount as u128)
+            .checked_mul(burn_boost_multiplier as u128)
+            .and_then(|x| x.checked_mul(effective_apy as u128))
+            .and_then(|x| x.checked_div(10000 * self.apy_basis_points as u128))
+            .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
+        
+        u64::try_from(shares).map_err(|_| ProgramError::Custom(GMCError::ArithmeticOverflow as u32))
+    }
+    
+    // 🛡️ Advance the accumulator. The APY is a target emission rate: emission is
+    // throttled to `reward_budget`, so owed rewards never exceed funded rewards.
+    // Returns the amount emitted.
+    pub fn update_rewards(&mut self, current_time: u32) -> Result<u64, ProgramError> {
+        self.update_rewards_capped(current_time, u64::MAX)
+    }
+    
+    // 🛡️ Same as `update_rewards`, additionally limited by an emission schedule cap
+    pub fn update_rewards_capped(&mut self, current_time: u32, emission_cap: u64) -> Result<u64, ProgramError> {
+        if current_time <= self.last_reward_update {
+            return Ok(0);
+        }
+        
+        let elapsed = current_time - self.last_reward_update;
+        self.last_reward_update = current_time;
+        
+        if self.total_reward_shares == 0 || self.reward_budget == 0 {
+            return Ok(0);
+        }
+        
+        const SECONDS_PER_YEAR: u128 = 365 * 86400;
+        let target_emission = (self.total_reward_shares as u128)
+            .checked_mul(self.apy_basis_points as u128)
+            .and_then(|x| x.checked_mul(elapsed as u128))
+            .and_then(|x| x.checked_div(10000 * SECONDS_PER_YEAR))
+            .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
+        
+        let emission = std::cmp::min(target_emission, std::cmp::min(self.reward_budget, emission_cap) as u128) as u64;
+        if (emission as u128) < target_emission {
+            msg!("⚠️ Pool {} reward budget low: emission throttled to {}", self.pool_id, emission);
+        }
+        
+        let per_share_delta = (emission as u128)
+            .checked_mul(Self::REWARD_PRECISION)
+            .map(|x| x / self.total_reward_shares as u128)
+            .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
+        
+        self.acc_reward_per_share = self.acc_reward_per_share
+            .checked_add(per_share_delta)
+            .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
+        self.reward_budget -= emission;
+        
+        Ok(emission)
+    }
+    
+    // 🧮 Accumulated rewards owed to `shares` at the current accumulator value
+    pub fn reward_debt_for(&self, shares: u64) -> Result<u128, ProgramError> {
+        (shares as u128)
+            .checked_mul(self.acc_reward_per_share)
+            .map(|x| x / Self::REWARD_PRECISION)
+            .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))
+    }
+    
+    // 💰 Rewards a record can claim (call `update_rewards` first)
+    pub fn pending_rewards(&self, record: &StakeRecord) -> Result<u64, ProgramError> {
+        let accumulated = self.reward_debt_for(record.reward_shares)?;
+        let pending = accumulated.saturating_sub(record.reward_debt);
+        u64::try_from(pending).map_err(|_| ProgramError::Custom(GMCError::ArithmeticOverflow as u32))
+    }
     
     // 🛡️ OWASP SC02: Safe APY calculation with overflow protection
     pub fn calculate_rewards(&self, amount: u64, days_staked: u32) -> Result<u64, ProgramError> {
@@ -149,6 +221,9 @@ pub struct StakeRecord {
     pub staker: Pubkey,                 // 32 bytes - most accessed field first
     pub amount: u64,                    // 8 bytes
     pub total_claimed: u64,             // 8 bytes
+    pub reward_debt: u128,              // 16 bytes - accumulator value already accounted for
+    pub reward_shares: u64,             // 8 bytes - weight in the pool's reward accumulator
+    pub total_burned: u64,              // 8 bytes - cumulative GMC burned for boost
     pub staked_at: u32,                 // 🚀 OPTIMIZATION: u32 timestamp (4 bytes)
     pub last_claim_at: u32,             // 🚀 OPTIMIZATION: u32 timestamp (4 bytes)
     pub burn_boost_multiplier: u16,     // 2 bytes - 10000 = 1.0x, 15000 = 1.5x
@@ -158,7 +233,7 @@ pub struct StakeRecord {
 
 impl StakeRecord {
     #[allow(dead_code)]
-    pub const LEN: usize = 32 + 8 + 8 + 4 + 4 + 2 + 1 + 1; // 🚀 OPTIMIZATION: 60 bytes (vs 68)
+    pub const LEN: usize = 32 + 8 + 8 + 16 + 8 + 8 + 4 + 4 + 2 + 1 + 1; // 92 bytes
     
     // 🛡️ Calculate pending rewards with dynamic APY (NEW VERSION)
     #[allow(dead_code)]
@@ -249,15 +324,160 @@ impl StakeRecord {
     }
 }
 
+// 📉 Emission schedule for the 70M staking allocation (single PDA shared by all pools)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct EmissionSchedule {
+    pub authority: Pubkey,
+    pub total_allocation: u64,      // STAKING_POOL_ALLOCATION
+    pub total_emitted: u64,
+    pub initial_epoch_cap: u64,     // Max emission in epoch 0
+    pub emitted_in_epoch: u64,
+    pub start_timestamp: u32,
+    pub epoch_duration: u32,        // seconds
+    pub current_epoch: u32,
+    pub decay_basis_points: u16,    // Cap reduction per epoch (5000 = halving, 0 = flat)
+    pub is_initialized: bool,
+}
+
+// 📊 Remaining runway of the staking allocation
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct EmissionRunway {
+    pub remaining_allocation: u64,
+    pub current_epoch: u32,
+    pub current_epoch_cap: u64,
+    pub epochs_remaining: u32,      // u32::MAX when the decay curve never exhausts the allocation
+    pub exhausted_at: u64,          // Estimated unix timestamp, u64::MAX if never
+}
+
+impl EmissionSchedule {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 4 + 4 + 4 + 2 + 1; // 79 bytes
+    
+    /// Upper bound of simulated epochs in runway reports (compute budget)
+    pub const MAX_RUNWAY_EPOCHS: u32 = 1_000;
+    
+    /// Fixed-point scale of the decay factor (1e18 = 1.0)
+    const DECAY_SCALE: u128 = 1_000_000_000_000_000_000;
+    
+    pub fn epoch_at(&self, current_time: u32) -> u32 {
+        current_time.saturating_sub(self.start_timestamp) / self.epoch_duration.max(1)
+    }
+    
+    // 📉 Cap retained per epoch, as a `DECAY_SCALE` fixed-point ratio
+    fn decay_ratio(&self) -> u128 {
+        (10000 - self.decay_basis_points.min(10000) as u128) * Self::DECAY_SCALE / 10000
+    }
+    
+    fn cap_for_factor(&self, factor: u128) -> u64 {
+        (self.initial_epoch_cap as u128 * factor / Self::DECAY_SCALE) as u64
+    }
+    
+    // 📉 Share of the initial cap left at `epoch` (exponentiation by squaring)
+    fn decay_factor(&self, epoch: u32) -> u128 {
+        let mut factor = Self::DECAY_SCALE;
+        let mut base = self.decay_ratio();
+        let mut exponent = epoch;
+        while exponent > 0 && factor > 0 {
+            if exponent & 1 == 1 {
+                factor = factor * base / Self::DECAY_SCALE;
+            }
+            base = base * base / Self::DECAY_SCALE;
+            exponent >>= 1;
+        }
+        factor
+    }
+    
+    // 📉 Cap of a given epoch after applying the decay curve
+    pub fn epoch_cap(&self, epoch: u32) -> u64 {
+        self.cap_for_factor(self.decay_factor(epoch))
+    }
+    
+    // 💰 Emission still allowed right now (epoch cap and remaining allocation)
+    pub fn available(&self, current_time: u32) -> u64 {
+        let epoch = self.epoch_at(current_time);
+        let emitted_in_epoch = if epoch == self.current_epoch { self.emitted_in_epoch } else { 0 };
+        let remaining_allocation = self.total_allocation.saturating_sub(self.total_emitted);
+        std::cmp::min(self.epoch_cap(epoch).saturating_sub(emitted_in_epoch), remaining_allocation)
+    }
+    
+    pub fn record_emission(&mut self, current_time: u32, amount: u64) -> Result<(), ProgramError> {
+        if amount > self.available(current_time) {
+            msg!("🚨 Security Alert: Emission exceeds schedule");
+            return Err(ProgramError::Custom(GMCError::OperationNotAllowed as u32));
+        }
+        
+        let epoch = self.epoch_at(current_time);
+        if epoch != self.current_epoch {
+            self.current_epoch = epoch;
+            self.emitted_in_epoch = 0;
+        }
+        
+        self.emitted_in_epoch = safe_add(self.emitted_in_epoch, amount)?;
+        self.total_emitted = safe_add(self.total_emitted, amount)?;
+        Ok(())
+    }
+    
+    // 🚀 Highest APY the schedule can sustain for `total_staked` at the current epoch cap
+    pub fn max_sustainable_apy(&self, current_time: u32, total_staked: u64) -> u16 {
+        if total_staked == 0 {
+            return u16::MAX;
+        }
+        
+        const SECONDS_PER_YEAR: u128 = 365 * 86400;
+        let annualized = self.epoch_cap(self.epoch_at(current_time)) as u128 * SECONDS_PER_YEAR
+            / self.epoch_duration.max(1) as u128;
+        std::cmp::min(annualized * 10000 / total_staked as u128, u16::MAX as u128) as u16
+    }
+    
+    // 📊 Simulate future epochs at full cap to estimate when the allocation runs out
+    pub fn runway(&self, current_time: u32) -> EmissionRunway {
+        let current_epoch = self.epoch_at(current_time);
+        let mut remaining = self.total_allocation.saturating_sub(self.total_emitted);
+        let remaining_allocation = remaining;
+        
+        remaining = remaining.saturating_sub(self.available(current_time));
+        let mut epoch = current_epoch;
+        // Decay factor carried from epoch to epoch instead of recomputed for each
+        let ratio = self.decay_ratio();
+        let mut factor = self.decay_factor(current_epoch);
+        while remaining > 0 && epoch - current_epoch < Self::MAX_RUNWAY_EPOCHS {
+            epoch += 1;
+            factor = factor * ratio / Self::DECAY_SCALE;
+            let cap = self.cap_for_factor(factor);
+            if cap == 0 {
+                break;
+            }
+            remaining = remaining.saturating_sub(cap);
+        }
+        
+        let (epochs_remaining, exhausted_at) = if remaining == 0 {
+            let end_epoch = epoch as u64 + 1;
+            (epoch - current_epoch + 1, self.start_timestamp as u64 + end_epoch * self.epoch_duration as u64)
+        } else {
+            (u32::MAX, u64::MAX)
+        };
+        
+        EmissionRunway {
+            remaining_allocation,
+            current_epoch,
+            current_epoch_cap: self.epoch_cap(current_epoch),
+            epochs_remaining,
+            exhausted_at,
+        }
+    }
+}
+
 // 🛡️ Staking Instructions
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum StakingInstruction {
-    /// Create a new staking pool
+    /// Create a predefined staking pool at its PDA. The APY must be the pool's
+    /// predefined APY; later changes go through the time-locked `SetPoolApy`.
     /// 
     /// Accounts expected:
-    /// 0. `[writable, signer]` Authority account
-    /// 1. `[writable]` Staking pool account
-    /// 2. `[]` Rent sysvar
+    /// 0. `[writable, signer]` Authority account (`PoolManager` role holder, pays rent)
+    /// 1. `[writable]` Staking pool PDA (`["staking_pool", pool_id]`)
+    /// 2. `[]` Role registry PDA (`["roles"]`)
+    /// 3. `[]` Global state PDA (`["global_state"]`, the admin governs the pool)
+    /// 4. `[]` System program
     CreatePool {
         pool_id: u8,
         apy_basis_points: u16,
@@ -269,65 +489,224 @@ pub enum StakingInstruction {
     /// Stake tokens in a pool with USDT entry fee
     /// 
     /// Accounts expected:
-    /// 0. `[writable, signer]` Staker account
+    /// 0. `[writable, signer]` Staker account (pays rent for the stake record)
     /// 1. `[writable]` Staker GMC token account
     /// 2. `[writable]` Staker USDT token account (for entry fee)
-    /// 3. `[writable]` Staking pool GMC account
-    /// 4. `[writable]` Stake record account
-    /// 5. `[writable]` Team USDT wallet (40% of fee)
-    /// 6. `[writable]` Staking Fund USDT wallet (40% of fee)
-    /// 7. `[writable]` Ranking Fund USDT wallet (20% of fee)
-    /// 8. `[]` Token program
+    /// 3. `[writable]` Staking pool state account
+    /// 4. `[writable]` Pool GMC vault (owned by the pool vault authority PDA)
+    /// 5. `[writable]` Stake record PDA (`["stake_record", pool_id, staker]`)
+    /// 6. `[writable]` Team USDT wallet (40% of fee)
+    /// 7. `[writable]` Staking Fund USDT wallet (40% of fee)
+    /// 8. `[writable]` Ranking Fund USDT wallet (20% of fee)
+    /// 9. `[]` Token program
+    /// 10. `[]` System program
+    /// 11. `[writable]` Emission schedule PDA
+    /// 12. `[]` Pause registry PDA (`["pause_registry"]`)
+    /// 13. `[]` Global state PDA (`["global_state"]`, pins the GMC/USDT mints and fee wallets)
     Stake {
         pool_id: u8,
         amount: u64,
     },
     
-    /// Claim staking rewards
+    /// Claim staking rewards (1% interest withdrawal fee: 40% team, 40% stays
+    /// in the reward vault for stakers, 20% ranking)
     /// 
     /// Accounts expected:
     /// 0. `[writable, signer]` Staker account
-    /// 1. `[writable]` Staker token account
-    /// 2. `[writable]` Staking pool account
-    /// 3. `[writable]` Pool token account
-    /// 4. `[writable]` Stake record account
-    /// 5. `[]` Token program
+    /// 1. `[writable]` Staker GMC token account
+    /// 2. `[writable]` Staking pool state account
+    /// 3. `[writable]` Reward vault (owned by the reward vault authority PDA)
+    /// 4. `[writable]` Stake record PDA
+    /// 5. `[]` Reward vault authority PDA (`["reward_vault", pool_id]`)
+    /// 6. `[]` Global state PDA (`["global_state"]`, ecosystem wallets)
+    /// 7. `[writable]` Team GMC token account
+    /// 8. `[writable]` Ranking fund GMC token account
+    /// 9. `[]` Token program
+    /// 10. `[writable]` Emission schedule PDA
+    /// 11. `[]` Pause registry PDA (`["pause_registry"]`)
+    /// 12. `[]` Optional: (AffiliateRecord PDA, StakeRecord PDA) pairs of the staker's
+    ///     referral tree from here on, up to six levels deep, each parent listed before
+    ///     its referrals
     ClaimRewards {
         pool_id: u8,
     },
     
-    /// Unstake tokens (only after lock period)
+    /// Unstake the full position. Before the lock expires long-term stakes burn
+    /// 50% of principal and redistribute 80% of rewards; flexible stakes burn 2.5%
+    /// of principal. The stake record is closed and its rent returned to the staker.
+    /// Returned rewards pay the same 1% interest withdrawal fee as `ClaimRewards`.
+    /// In exit-only pause mode the full principal is returned and pending rewards go
+    /// back to the pool's reward budget.
     /// 
     /// Accounts expected:
     /// 0. `[writable, signer]` Staker account
-    /// 1. `[writable]` Staker token account
-    /// 2. `[writable]` Staking pool account
-    /// 3. `[writable]` Pool token account
-    /// 4. `[writable]` Stake record account
-    /// 5. `[]` Token program
+    /// 1. `[writable]` Staker GMC token account
+    /// 2. `[writable]` Staking pool state account
+    /// 3. `[writable]` Pool GMC vault
+    /// 4. `[]` Pool vault authority PDA
+    /// 5. `[writable]` Reward vault
+    /// 6. `[]` Reward vault authority PDA
+    /// 7. `[writable]` Redistribution pool (owned by `["redistribution", pool_id]` PDA)
+    /// 8. `[writable]` Stake record PDA
+    /// 9. `[writable]` Global state PDA (`["global_state"]`)
+    /// 10. `[writable]` GMC mint
+    /// 11. `[]` Token program
+    /// 12. `[writable]` Emission schedule PDA
+    /// 13. `[]` Pause registry PDA (`["pause_registry"]`)
+    /// 14. `[writable]` Team GMC token account
+    /// 15. `[writable]` Ranking fund GMC token account
     Unstake {
         pool_id: u8,
         amount: u64,
     },
     
-    /// Burn tokens to boost APY (Burn-for-Boost feature)
+    /// Burn tokens to boost APY (Burn-for-Boost feature, long-term pools only).
+    /// Burns `burn_amount` + 10% GMC fee and charges a fixed 0.8 USDT fee; the
+    /// multiplier is derived on-chain from the cumulative burned amount. Past the
+    /// 12M supply floor the unburnable part funds the pool's reward vault instead.
     /// 
     /// Accounts expected:
     /// 0. `[writable, signer]` Staker account
-    /// 1. `[writable]` Staker token account
-    /// 2. `[writable]` Stake record account
-    /// 3. `[]` Token program
+    /// 1. `[writable]` Staker GMC token account
+    /// 2. `[writable]` Staker USDT token account
+    /// 3. `[writable]` GMC mint
+    /// 4. `[writable]` Stake record PDA
+    /// 5. `[writable]` Staking pool state account
+    /// 6. `[writable]` Global state PDA (`["global_state"]`)
+    /// 7. `[writable]` Team USDT token account
+    /// 8. `[]` Token program
+    /// 9. `[writable]` Emission schedule PDA
+    /// 10. `[writable]` Pool reward vault
+    /// 11. `[]` Optional: (AffiliateRecord PDA, StakeRecord PDA) pairs of the staker's
+    ///     referral tree from here on, as in `ClaimRewards`
     BurnForBoost {
         pool_id: u8,
         burn_amount: u64,
-        boost_multiplier: u16, // Additional multiplier (10000 = 1.0x)
+        boost_multiplier: u16, // Ignored: kept for wire compatibility, derived on-chain
     },
+    
+    /// Fund a pool's reward vault; the amount becomes emittable reward budget
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` Funder account
+    /// 1. `[writable]` Funder GMC token account
+    /// 2. `[writable]` Staking pool state account
+    /// 3. `[writable]` Reward vault (owned by the reward vault authority PDA)
+    /// 4. `[]` Token program
+    /// 5. `[writable]` Emission schedule PDA
+    /// 6. `[]` Global state PDA (`["global_state"]`, pins the GMC mint)
+    FundRewards {
+        pool_id: u8,
+        amount: u64,
+    },
+    
+    /// Create the emission schedule that caps how fast the staking allocation is paid out
+    /// 
+    /// Accounts expected:
+    /// 0. `[writable, signer]` Admin account (GlobalState admin, pays rent)
+    /// 1. `[]` Global state PDA (`["global_state"]`)
+    /// 2. `[writable]` Emission schedule PDA (`["emission_schedule"]`)
+    /// 3. `[]` System program
+    InitializeEmissionSchedule {
+        epoch_duration_secs: u32,
+        initial_epoch_cap: u64,
+        decay_basis_points: u16, // Cap reduction per epoch (5000 = halving, 0 = flat)
+    },
+    
+    /// Report the remaining runway of the staking allocation (logged and set as return data)
+    /// 
+    /// Accounts expected:
+    /// 0. `[]` Emission schedule PDA
+    ReportEmissionRunway,
 }
 
 // 🛡️ IDs dos Pools Predefinidos
 pub const LONG_TERM_POOL_ID: u8 = 1;
 pub const FLEXIBLE_POOL_ID: u8 = 4;
 
+// 🔑 Seeds das contas derivadas (PDA) do staking
+pub const STAKING_POOL_SEED: &[u8] = b"staking_pool";
+pub const STAKE_RECORD_SEED: &[u8] = b"stake_record";
+pub const POOL_VAULT_AUTHORITY_SEED: &[u8] = b"pool_vault";
+pub const REWARD_VAULT_AUTHORITY_SEED: &[u8] = b"reward_vault";
+pub const REDISTRIBUTION_AUTHORITY_SEED: &[u8] = b"redistribution";
+pub const EMISSION_SCHEDULE_SEED: &[u8] = b"emission_schedule";
+
+/// Maximum (AffiliateRecord, StakeRecord) pairs accepted by a claim
+pub const MAX_AFFILIATE_ACCOUNTS_PER_CLAIM: usize = 12;
+
+/// Derive the address of a pool's state account
+pub fn find_pool_address(program_id: &Pubkey, pool_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKING_POOL_SEED, &[pool_id]], program_id)
+}
+
+/// Derive the stake record address for a staker in a given pool
+pub fn find_stake_record_address(program_id: &Pubkey, pool_id: u8, staker: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_RECORD_SEED, &[pool_id], staker.as_ref()], program_id)
+}
+
+/// Derive the authority that owns a pool's GMC vault token account
+pub fn find_pool_vault_authority(program_id: &Pubkey, pool_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_VAULT_AUTHORITY_SEED, &[pool_id]], program_id)
+}
+
+/// Derive the authority that owns a pool's reward vault token account
+pub fn find_reward_vault_authority(program_id: &Pubkey, pool_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARD_VAULT_AUTHORITY_SEED, &[pool_id]], program_id)
+}
+
+/// Derive the authority that owns a pool's redistribution token account
+pub fn find_redistribution_authority(program_id: &Pubkey, pool_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REDISTRIBUTION_AUTHORITY_SEED, &[pool_id]], program_id)
+}
+
+/// Derive the emission schedule address
+pub fn find_emission_schedule_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EMISSION_SCHEDULE_SEED], program_id)
+}
+
+/// Load the emission schedule after checking its address and owner
+pub fn load_emission_schedule(program_id: &Pubkey, schedule_info: &AccountInfo) -> Result<EmissionSchedule, ProgramError> {
+    if *schedule_info.key != find_emission_schedule_address(program_id).0 {
+        msg!("🚨 Security Alert: Invalid emission schedule account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let schedule: EmissionSchedule = discriminator::load(program_id, schedule_info)?;
+    if !schedule.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(schedule)
+}
+
+/// Advance a pool's accumulator within the emission schedule
+pub fn settle_pool_rewards(
+    pool: &mut StakingPool,
+    schedule: &mut EmissionSchedule,
+    current_time: u32,
+) -> Result<u64, ProgramError> {
+    let emitted = pool.update_rewards_capped(current_time, schedule.available(current_time))?;
+    schedule.record_emission(current_time, emitted)?;
+    Ok(emitted)
+}
+
+/// Stake type used by the dynamic APY rules ("long-term" for pools 1-3, "flexible" for 4-6)
+pub fn stake_type_for_pool(pool_id: u8) -> Result<&'static str, ProgramError> {
+    match pool_id {
+        1..=3 => Ok("long-term"),
+        4..=6 => Ok("flexible"),
+        _ => Err(ProgramError::Custom(GMCError::InvalidPoolId as u32)),
+    }
+}
+
+/// Look up the predefined configuration for a pool id (long-term 1-3, flexible 4-6)
+pub fn find_predefined_pool(pool_id: u8) -> Option<&'static StakingPool> {
+    LONG_TERM_POOLS
+        .iter()
+        .chain(FLEXIBLE_POOLS.iter())
+        .find(|pool| pool.pool_id == pool_id)
+}
+
 // 🛡️ Parâmetros dos Pools Predefinidos (para uso em testes e outros módulos)
 pub const LONG_TERM_APY_BASIS_POINTS: u16 = 1000; // 10%
 pub const FLEXIBLE_APY_BASIS_POINTS: u16 = 500; // 5%
@@ -349,6 +728,8 @@ pub const FLEXIBLE_MAX_STAKE: u64 = 100_000 * 1_000_000_000; // 100,000 GMC
 // 💰 USDT Fee Tiers for Staking Entry (based on GMC amount)
 // REGRA DE NEGÓCIO: Taxas em USDT baseadas na quantidade de GMC que será staked
 pub const USDT_DECIMALS: u8 = 6; // USDT padrão tem 6 decimais
+// Preço inicial de referência: $0.10 por GMC (micro-USDT por GMC inteiro); depois via time-lock
+pub const DEFAULT_USDT_PRICE_PER_GMC: u64 = 100_000;
 
 // 💰 TAXA PERCENTUAL DE ENTRADA NO STAKING (conforme requisitos originais)
 // Tier 1: Até 1.000 GMC -> 10%
@@ -409,6 +790,10 @@ pub const LONG_TERM_POOLS: [StakingPool; 3] = [
         minimum_stake: 1_000_000, // 1M tokens
         maximum_stake: 100_000_000_000, // 100B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
     // Long-term 12 months - 150% APY
@@ -422,6 +807,10 @@ pub const LONG_TERM_POOLS: [StakingPool; 3] = [
         minimum_stake: 10_000_000, // 10M tokens
         maximum_stake: 100_000_000_000, // 100B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
     // Long-term 12 months - 280% APY
@@ -435,6 +824,10 @@ pub const LONG_TERM_POOLS: [StakingPool; 3] = [
         minimum_stake: 50_000_000, // 50M tokens
         maximum_stake: 100_000_000_000, // 100B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
 ];
@@ -452,6 +845,10 @@ pub const FLEXIBLE_POOLS: [StakingPool; 3] = [
         minimum_stake: 100_000, // 100K tokens
         maximum_stake: 10_000_000_000, // 10B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
     // Flexible 30 days - 35% APY
@@ -465,6 +862,10 @@ pub const FLEXIBLE_POOLS: [StakingPool; 3] = [
         minimum_stake: 1_000_000, // 1M tokens
         maximum_stake: 10_000_000_000, // 10B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
     // Flexible 30 days - 70% APY
@@ -478,6 +879,10 @@ pub const FLEXIBLE_POOLS: [StakingPool; 3] = [
         minimum_stake: 5_000_000, // 5M tokens
         maximum_stake: 10_000_000_000, // 10B tokens
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     },
 ];
@@ -497,20 +902,14 @@ pub fn calculate_usdt_fee_by_amount(gmc_amount: u64, usdt_price_per_gmc: u64) ->
         TIER_5_FEE_PERCENT // 0.5%
     };
     
-    // 🛡️ SAFE MATH: Usar funções seguras para todos os cálculos
+    // 🛡️ SAFE MATH: valor em u128 - `gmc_amount * preço` passa de u64 em stakes grandes
     // Calcula o valor em USDT do GMC staked
-    let gmc_value_in_usdt = safe_div(
-        safe_mul(gmc_amount, usdt_price_per_gmc)?,
-        1_000_000_000
-    )?;
+    let gmc_value_in_usdt = gmc_amount as u128 * usdt_price_per_gmc as u128 / 1_000_000_000;
     
     // Aplica a porcentagem (basis points: 10000 = 100%)
-    let fee_usdt = safe_div(
-        safe_mul(gmc_value_in_usdt, fee_percent as u64)?,
-        10000
-    )?;
+    let fee_usdt = gmc_value_in_usdt * fee_percent as u128 / 10000;
     
-    Ok(fee_usdt)
+    u64::try_from(fee_usdt).map_err(|_| ProgramError::ArithmeticOverflow)
 }
 
 // 💰 Calculate USDT fee distribution (SAFE MATH)
@@ -537,11 +936,11 @@ pub fn calculate_usdt_fee_distribution(total_fee: u64) -> Result<(u64, u64, u64)
 }
 
 // 💸 Transfer USDT using SPL Token CPI
-pub fn transfer_usdt_via_cpi(
-    from_account: &AccountInfo,
-    to_account: &AccountInfo,
-    authority: &AccountInfo,
-    token_program: &AccountInfo,
+pub fn transfer_usdt_via_cpi<'a>(
+    from_account: &AccountInfo<'a>,
+    to_account: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
     amount: u64,
 ) -> ProgramResult {
     msg!("💸 Transferring {} USDT via CPI", amount as f64 / 1_000_000.0);
@@ -552,25 +951,24 @@ pub fn transfer_usdt_via_cpi(
         return Err(ProgramError::Custom(crate::GMCError::InvalidAmount as u32));
     }
     
-    // In real implementation, this would create and invoke SPL Token transfer instruction:
-    // let transfer_instruction = spl_token::instruction::transfer(
-    //     token_program.key,
-    //     from_account.key,
-    //     to_account.key,
-    //     authority.key,
-    //     &[],
-    //     amount,
-    // )?;
-    // 
-    // invoke(
-    //     &transfer_instruction,
-    //     &[
-    //         from_account.clone(),
-    //         to_account.clone(),
-    //         authority.clone(),
-    //         token_program.clone(),
-    //     ],
-    // )?;
+    let transfer_instruction = spl_token::instruction::transfer(
+        token_program.key,
+        from_account.key,
+        to_account.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    
+    invoke(
+        &transfer_instruction,
+        &[
+            from_account.clone(),
+            to_account.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+    )?;
     
     msg!("✅ USDT transfer completed: {} USDT from {} to {}", 
          amount as f64 / 1_000_000.0, from_account.key, to_account.key);
@@ -594,48 +992,35 @@ pub fn calculate_dynamic_apy(
     }
 }
 
-// 🚀 OTIMIZAÇÃO: Wrapper to convert to optimized parameters
-pub fn calculate_dynamic_apy_optimized_wrapper(
+// 📉 Dynamic APY limited by what the emission schedule can sustain for the pool
+pub fn calculate_dynamic_apy_with_schedule(
     stake_type: &str,
     burn_power: u8,
     affiliate_power: u8,
+    schedule: &EmissionSchedule,
+    total_staked: u64,
+    current_time: u32,
 ) -> Result<(u16, u16, u16, u16), ProgramError> {
-    // 🚀 OPTIMIZATION: Convert stake_type to numeric for lookup efficiency
-    let pool_multiplier = match stake_type {
-        "long-term" => 12000,  // 120% multiplier for long-term
-        "flexible" => 8000,    // 80% multiplier for flexible  
-        _ => return Err(ProgramError::Custom(crate::GMCError::InvalidAmount as u32)),
-    };
-    
-    // 🚀 OPTIMIZATION: Use optimized calculation with current slot
-    let current_slot = Clock::get()?.slot;
-    // Cache manager optimization removed - using standard processing
-    
-    // 🚀 OPTIMIZATION: Direct APY calculation using lookup tables
-    // Temporarily use original calculation due to type mismatch
-    let (_base_apy, _burn_boost, _affiliate_boost, optimized_apy) = 
-        calculate_dynamic_apy_original(
-            if pool_multiplier == 12000 { "long-term" } else { "flexible" },
-            burn_power,
-            affiliate_power,
-        )?;
+    let (base_apy, burn_boost, affiliate_boost, dynamic_apy) =
+        calculate_dynamic_apy(stake_type, burn_power, affiliate_power)?;
     
-    // 🚀 OPTIMIZATION: Calculate components using lookup tables
-    // Using default base APY since cache manager was removed
-    let base_apy = 10.0; // Default 10% base APY
-    let burn_boost = if stake_type == "long-term" {
-        BURN_BOOST_LOOKUP[burn_power.min(255) as usize]
-    } else {
-        0
-    };
-    let affiliate_boost = if (affiliate_power as usize) < AFFILIATE_BOOST_LOOKUP.len() {
-        AFFILIATE_BOOST_LOOKUP[affiliate_power as usize]
-    } else {
-        0
-    };
+    let sustainable_apy = schedule.max_sustainable_apy(current_time, total_staked);
+    if sustainable_apy < dynamic_apy {
+        msg!("📉 APY limited by emission schedule: {} -> {} basis points", dynamic_apy, sustainable_apy);
+    }
     
-    let optimized_apy_u16 = (base_apy * 100.0) as u16 + burn_boost + affiliate_boost;
-    Ok((base_apy as u16 * 100, burn_boost, affiliate_boost, optimized_apy_u16))
+    Ok((base_apy, burn_boost, affiliate_boost, std::cmp::min(dynamic_apy, sustainable_apy)))
+}
+
+// 🚀 OTIMIZAÇÃO: Entry point kept for the optimized dispatch
+pub fn calculate_dynamic_apy_optimized_wrapper(
+    stake_type: &str,
+    burn_power: u8,
+    affiliate_power: u8,
+) -> Result<(u16, u16, u16, u16), ProgramError> {
+    // Same validated calculation as the original (single source of truth): base APY
+    // per stake type, boosts and the per-type cap
+    calculate_dynamic_apy_original(stake_type, burn_power, affiliate_power)
 }
 
 // 🚀 Original function preserved for fallback
@@ -650,9 +1035,9 @@ pub fn calculate_dynamic_apy_original(
         return Err(ProgramError::Custom(crate::GMCError::InvalidAmount as u32));
     }
     
-    let (base_apy, max_apy, max_affiliate_boost) = match stake_type {
-        "long-term" => (LONG_TERM_BASE_APY, LONG_TERM_MAX_APY, MAX_AFFILIATE_BOOST_LONG_TERM),
-        "flexible" => (FLEXIBLE_BASE_APY, FLEXIBLE_MAX_APY, MAX_AFFILIATE_BOOST_FLEXIBLE),
+    let (base_apy, max_apy) = match stake_type {
+        "long-term" => (LONG_TERM_BASE_APY, LONG_TERM_MAX_APY),
+        "flexible" => (FLEXIBLE_BASE_APY, FLEXIBLE_MAX_APY),
         _ => {
             msg!("🚨 Security Alert: Invalid stake type");
             return Err(ProgramError::Custom(crate::GMCError::InvalidAmount as u32));
@@ -672,30 +1057,7 @@ pub fn calculate_dynamic_apy_original(
     };
     
     // Calcular affiliate boost
-    let affiliate_boost = if affiliate_power > 0 {
-        // Para long-term: até 50% boost
-        // Para flexible: até 65% boost (scaling diferente)
-        let boost = if stake_type == "long-term" {
-            // Linear scaling: affiliate_power × 100 basis points = boost
-            // Máximo: 50% affiliate_power = 5000 basis points (50%)
-            (affiliate_power as u16)
-                .checked_mul(100)
-                .unwrap_or(0)
-        } else {
-            // Para flexible: scaling especial para chegar a 65% com 35% affiliate_power
-            // Fórmula: (affiliate_power × 6500) / 35 = boost
-            // 🛡️ FIX: Usar u32 para evitar overflow em cálculos intermediários
-            (affiliate_power as u32)
-                .checked_mul(max_affiliate_boost as u32)
-                .and_then(|x| x.checked_div(35))
-                .and_then(|x| if x <= u16::MAX as u32 { Some(x as u16) } else { None })
-                .unwrap_or(0)
-        };
-        
-        std::cmp::min(boost, max_affiliate_boost)
-    } else {
-        0
-    };
+    let affiliate_boost = affiliate_boost_basis_points(stake_type, affiliate_power);
     
     // Calcular APY total
     let total_apy = base_apy
@@ -716,6 +1078,34 @@ pub fn calculate_dynamic_apy_original(
     Ok((base_apy, burn_boost, affiliate_boost, final_apy))
 }
 
+// 🤝 Affiliate boost in basis points for a given affiliate power (0-50)
+pub fn affiliate_boost_basis_points(stake_type: &str, affiliate_power: u8) -> u16 {
+    if affiliate_power == 0 {
+        return 0;
+    }
+    
+    let (boost, max_affiliate_boost) = if stake_type == "long-term" {
+        // Linear scaling: affiliate_power × 100 basis points = boost
+        // Máximo: 50% affiliate_power = 5000 basis points (50%)
+        let boost = (affiliate_power as u16)
+            .checked_mul(100)
+            .unwrap_or(0);
+        (boost, MAX_AFFILIATE_BOOST_LONG_TERM)
+    } else {
+        // Para flexible: scaling especial para chegar a 65% com 35% affiliate_power
+        // Fórmula: (affiliate_power × 6500) / 35 = boost
+        // 🛡️ FIX: Usar u32 para evitar overflow em cálculos intermediários
+        let boost = (affiliate_power as u32)
+            .checked_mul(MAX_AFFILIATE_BOOST_FLEXIBLE as u32)
+            .and_then(|x| x.checked_div(35))
+            .and_then(|x| if x <= u16::MAX as u32 { Some(x as u16) } else { None })
+            .unwrap_or(0);
+        (boost, MAX_AFFILIATE_BOOST_FLEXIBLE)
+    };
+    
+    std::cmp::min(boost, max_affiliate_boost)
+}
+
 // 🤝 Calculate affiliate power based on referral tree (SAFE MATH)
 // 🎯 LÓGICA CORRETA: "EU CONVIDO → AMIGO FAZ STAKING → MEU APY AUMENTA"
 pub fn calculate_affiliate_power(referral_levels: Vec<(u8, u8)>) -> Result<u8, ProgramError> {
@@ -831,11 +1221,75 @@ pub fn calculate_mining_power_from_stake(stake_amount: u64, burn_power: u8) -> R
 }
 
 // 🛡️ Verificar se afiliado tem staking ativo (proteção anti-fraude)
-pub fn affiliate_has_active_staking(affiliate_pubkey: &Pubkey) -> bool {
-    // TODO: Implementar verificação real da conta StakePosition
-    // Por enquanto, retorna true para testes
-    msg!("🔍 Checking active staking for affiliate: {}", affiliate_pubkey);
-    true // Placeholder - implementar verificação real
+pub fn affiliate_has_active_staking(
+    affiliate: &crate::affiliate::AffiliateRecord,
+    affiliate_stake: &StakeRecord,
+) -> bool {
+    affiliate.is_active
+        && affiliate_stake.is_active
+        && affiliate_stake.amount > 0
+        && affiliate_stake.staker == affiliate.affiliate_id
+}
+
+// 🌳 Verify an ordered referral tree rooted at `staker` and return the
+// (level, stake_amount, burn_power) entries of affiliates with active staking.
+// Every entry must be referred by the staker (level 1) or by an earlier entry,
+// so parents always precede children and cycles cannot be expressed.
+pub fn verify_referral_tree(
+    staker: &Pubkey,
+    entries: &[(crate::affiliate::AffiliateRecord, StakeRecord)],
+) -> Result<Vec<(u8, u64, u8)>, ProgramError> {
+    let max_level = AFFILIATE_LEVEL_PERCENTAGES.len() as u8;
+    let mut verified: Vec<(Pubkey, u8)> = Vec::with_capacity(entries.len());
+    let mut affiliate_stakes = Vec::with_capacity(entries.len());
+    
+    for (affiliate, affiliate_stake) in entries {
+        if affiliate.affiliate_id == *staker
+            || verified.iter().any(|(key, _)| *key == affiliate.affiliate_id)
+        {
+            msg!("🚨 Security Alert: Duplicate affiliate in referral tree");
+            return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+        }
+        
+        if affiliate_stake.staker != affiliate.affiliate_id {
+            msg!("🚨 Security Alert: Stake record does not belong to affiliate");
+            return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+        }
+        
+        let level = match affiliate.referrer {
+            Some(referrer) if referrer == *staker => 1,
+            Some(referrer) => verified
+                .iter()
+                .find(|(key, _)| *key == referrer)
+                .map(|(_, parent_level)| parent_level + 1)
+                .ok_or_else(|| {
+                    msg!("🚨 Security Alert: Affiliate referrer is not part of the tree");
+                    ProgramError::Custom(GMCError::InvalidInput as u32)
+                })?,
+            None => {
+                msg!("🚨 Security Alert: Affiliate has no referrer");
+                return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+            }
+        };
+        
+        if level > max_level {
+            msg!("🚨 Security Alert: Referral tree deeper than {} levels", max_level);
+            return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+        }
+        
+        verified.push((affiliate.affiliate_id, level));
+        
+        // 🎯 Inactive affiliates still link the tree but add no boost
+        if affiliate_has_active_staking(affiliate, affiliate_stake) {
+            let burn_power = std::cmp::min(
+                (affiliate_stake.total_burned as u128 * 100) / affiliate_stake.amount as u128,
+                100,
+            ) as u8;
+            affiliate_stakes.push((level, affiliate_stake.amount, burn_power));
+        }
+    }
+    
+    Ok(affiliate_stakes)
 }
 
 // 🔥 Calculate burn boost multiplier based on burned amount vs principal (SAFE MATH)
@@ -871,13 +1325,46 @@ pub fn calculate_burn_boost_multiplier(burned_amount: u64, principal_amount: u64
 
 // 💸 Distribute USDT fees to all three destinations
 // 🛡️ CRITICAL SECURITY: Only authorized accounts can distribute USDT fees
-pub fn distribute_usdt_fees(
-    staker_usdt_account: &AccountInfo,
-    team_usdt_account: &AccountInfo,
-    staking_fund_usdt_account: &AccountInfo,
-    ranking_fund_usdt_account: &AccountInfo,
-    staker_authority: &AccountInfo,
-    token_program: &AccountInfo,
+/// Pin the USDT fee accounts: every account holds the configured USDT mint and the
+/// destinations belong to the team / staking fund / ranking fund wallets
+pub fn validate_usdt_fee_accounts<'a>(
+    global_state: &crate::GlobalState,
+    payer_usdt_info: &AccountInfo<'a>,
+    team_usdt_info: &AccountInfo<'a>,
+    staking_fund_usdt_info: &AccountInfo<'a>,
+    ranking_fund_usdt_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    if global_state.usdt_mint == Pubkey::default() {
+        msg!("🚨 USDT mint is not configured");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    let wallets = &global_state.ecosystem_wallets;
+    for (info, expected_owner) in [
+        (payer_usdt_info, None),
+        (team_usdt_info, Some(&wallets.team)),
+        (staking_fund_usdt_info, Some(&wallets.staking_fund)),
+        (ranking_fund_usdt_info, Some(&wallets.ranking_fund)),
+    ] {
+        if *info.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let account = spl_token::state::Account::unpack(&info.data.borrow())?;
+        if account.mint != global_state.usdt_mint || expected_owner.is_some_and(|owner| account.owner != *owner) {
+            msg!("🚨 Security Alert: Invalid USDT fee account {}", info.key);
+            return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+        }
+    }
+    Ok(())
+}
+
+pub fn distribute_usdt_fees<'a>(
+    staker_usdt_account: &AccountInfo<'a>,
+    team_usdt_account: &AccountInfo<'a>,
+    staking_fund_usdt_account: &AccountInfo<'a>,
+    ranking_fund_usdt_account: &AccountInfo<'a>,
+    staker_authority: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
     team_fee: u64,
     staking_fee: u64,
     ranking_fee: u64,
@@ -924,60 +1411,153 @@ pub fn distribute_usdt_fees(
     Ok(())
 }
 
+// 🥩 Router function to process staking instructions
+///
+/// Receives a StakingInstruction and routes it to the appropriate handler
+pub fn process_instruction_router(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: StakingInstruction,
+) -> ProgramResult {
+    match instruction {
+        StakingInstruction::CreatePool {
+            pool_id,
+            apy_basis_points,
+            lock_duration_days,
+            minimum_stake,
+            maximum_stake,
+        } => {
+            process_create_pool(
+                program_id,
+                accounts,
+                pool_id,
+                apy_basis_points,
+                lock_duration_days,
+                minimum_stake,
+                maximum_stake,
+            )
+        },
+        StakingInstruction::Stake { pool_id, amount } => {
+            process_stake(program_id, accounts, pool_id, amount)
+        },
+        StakingInstruction::ClaimRewards { pool_id } => {
+            process_claim_rewards(program_id, accounts, pool_id)
+        },
+        StakingInstruction::Unstake { pool_id, amount } => {
+            process_unstake(program_id, accounts, pool_id, amount)
+        },
+        StakingInstruction::BurnForBoost { pool_id, burn_amount, boost_multiplier } => {
+            process_burn_for_boost(program_id, accounts, pool_id, burn_amount, boost_multiplier)
+        },
+        StakingInstruction::FundRewards { pool_id, amount } => {
+            process_fund_rewards(program_id, accounts, pool_id, amount)
+        },
+        StakingInstruction::InitializeEmissionSchedule { epoch_duration_secs, initial_epoch_cap, decay_basis_points } => {
+            process_initialize_emission_schedule(program_id, accounts, epoch_duration_secs, initial_epoch_cap, decay_basis_points)
+        },
+        StakingInstruction::ReportEmissionRunway => {
+            process_report_emission_runway(program_id, accounts)
+        },
+    }
+}
+
 // 🛡️ Staking processor functions
 pub fn process_create_pool(
-    _accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
     pool_id: u8,
     apy_basis_points: u16,
-    _lock_duration_days: u32,
+    lock_duration_days: u32,
     minimum_stake: u64,
     maximum_stake: u64,
 ) -> ProgramResult {
     msg!("🏗️ Creating staking pool: {}", pool_id);
     
     // 🛡️ OWASP SC04: Input validation
-    if apy_basis_points > 50000 { // Max 500% APY
-        msg!("🚨 Security Alert: APY too high (max 500%)");
-        return Err(ProgramError::Custom(GMCError::TransferFeeTooHigh as u32));
-    }
-    
-    if minimum_stake > maximum_stake {
+    if minimum_stake == 0 || minimum_stake > maximum_stake {
         msg!("🚨 Security Alert: Invalid stake limits");
         return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
     }
     
-    // ✅ Implement pool creation logic
-    msg!("🏊 Creating staking pool {}", pool_id);
+    let predefined = find_predefined_pool(pool_id).ok_or_else(|| {
+        msg!("🚨 Invalid pool ID: {}", pool_id);
+        ProgramError::Custom(GMCError::InvalidPoolId as u32)
+    })?;
+    
+    // ⏳ APY changes are critical parameters: only the time-locked SetPoolApy moves them
+    if apy_basis_points != predefined.apy_basis_points {
+        msg!("🚨 Security Alert: Pool {} must start at its predefined APY ({}bps)", pool_id, predefined.apy_basis_points);
+        return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+    }
+    
+    let lock_duration_days = u16::try_from(lock_duration_days)
+        .map_err(|_| ProgramError::Custom(GMCError::InvalidInput as u32))?;
     
-    // Validate pool doesn't already exist
-    // In real implementation: check if pool_account already exists
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let roles_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    
+    if !authority_info.is_signer {
+        msg!("🚨 Security Alert: Pool authority must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
     
-    // Create pool with predefined parameters based on pool_id
-    let (apy, lock_duration, min_stake, max_stake) = match pool_id {
-        1 => (LONG_TERM_POOL_APY, LONG_TERM_LOCK_DURATION, LONG_TERM_MIN_STAKE, LONG_TERM_MAX_STAKE),
-        2 => (FLEXIBLE_POOL_APY, FLEXIBLE_LOCK_DURATION, FLEXIBLE_MIN_STAKE, FLEXIBLE_MAX_STAKE),
-        _ => {
-            msg!("🚨 Invalid pool ID: {}", pool_id);
-            return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
-        }
-    };
+    if !crate::roles::has_role(program_id, Some(roles_info), crate::roles::Role::PoolManager, authority_info.key)? {
+        msg!("🚨 Security Alert: Only pool managers can create pools");
+        return Err(ProgramError::Custom(GMCError::AccessDenied as u32));
+    }
     
-    msg!("📋 Pool parameters: APY={}%, Lock={}days, Min={}GMC, Max={}GMC", 
-         apy, lock_duration, min_stake / 1_000_000_000, max_stake / 1_000_000_000);
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
     
-    // In real implementation:
-    // let pool = StakingPool {
-    //     pool_id,
-    //     apy,
-    //     lock_duration_days: lock_duration,
-    //     min_stake_amount: min_stake,
-    //     max_stake_amount: max_stake,
-    //     total_staked: 0,
-    //     total_rewards_distributed: 0,
-    //     is_active: true,
-    //     created_at: current_time as u32,
-    // };
-    // pool.save(pool_account)?;
+    // 🔑 One pool per id, at its derived address
+    let (pool_key, pool_bump) = find_pool_address(program_id, pool_id);
+    if *pool_info.key != pool_key {
+        msg!("🚨 Security Alert: Invalid pool address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    // 🛡️ Pool can only be created once
+    if pool_info.owner == program_id && pool_info.data_len() > 0 {
+        msg!("🚨 Security Alert: Pool {} already exists", pool_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    
+    crate::create_pda_account(
+        program_id,
+        authority_info,
+        pool_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + StakingPool::LEN,
+        &[STAKING_POOL_SEED, &[pool_id], &[pool_bump]],
+    )?;
+    
+    let pool = StakingPool {
+        authority: global_state.admin,
+        total_staked: 0,
+        total_rewards: 0,
+        minimum_stake,
+        maximum_stake,
+        apy_basis_points,
+        lock_duration_days,
+        pool_id,
+        is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
+        _padding: [0; 6],
+    };
+    discriminator::store(program_id, pool_info, &pool)?;
+    
+    msg!("📋 Pool parameters: APY={}bps, Lock={}days, Min={}GMC, Max={}GMC", 
+         apy_basis_points, lock_duration_days, minimum_stake / 1_000_000_000, maximum_stake / 1_000_000_000);
     msg!("✅ Staking pool {} created successfully", pool_id);
     
     Ok(())
@@ -985,6 +1565,7 @@ pub fn process_create_pool(
 
 // 🚀 OTIMIZAÇÃO: Process stake with optimized path
 pub fn process_stake_optimized(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     amount: u64,
@@ -1013,10 +1594,11 @@ pub fn process_stake_optimized(
     
     // Continue with original staking logic but use optimized structures...
     // For now, call the original function to maintain functionality
-    process_stake_original(accounts, pool_id, amount)
+    process_stake_original(program_id, accounts, pool_id, amount)
 }
 
 pub fn process_stake(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     amount: u64,
@@ -1025,13 +1607,14 @@ pub fn process_stake(
     let use_optimization = true; // Can be configured via program upgrade
     
     if use_optimization {
-        process_stake_optimized(accounts, pool_id, amount)
+        process_stake_optimized(program_id, accounts, pool_id, amount)
     } else {
-        process_stake_original(accounts, pool_id, amount)
+        process_stake_original(program_id, accounts, pool_id, amount)
     }
 }
 
 pub fn process_stake_original(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     amount: u64,
@@ -1046,15 +1629,20 @@ pub fn process_stake_original(
     
     // 🛡️ Account validation - get accounts in order
     let account_info_iter = &mut accounts.iter();
-    let staker_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let staker_gmc_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let staker_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let pool_gmc_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let stake_record_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let team_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let staking_fund_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let ranking_fund_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let _token_program_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let staker_info = next_account_info(account_info_iter)?;
+    let staker_gmc_info = next_account_info(account_info_iter)?;
+    let staker_usdt_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let pool_vault_info = next_account_info(account_info_iter)?;
+    let stake_record_info = next_account_info(account_info_iter)?;
+    let team_usdt_info = next_account_info(account_info_iter)?;
+    let staking_fund_usdt_info = next_account_info(account_info_iter)?;
+    let ranking_fund_usdt_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let pause_registry_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
     
     // 🛡️ Security: Validate staker is signer
     if !staker_info.is_signer {
@@ -1062,10 +1650,82 @@ pub fn process_stake_original(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // 💰 Step 1: Calculate USDT entry fee based on GMC amount (PERCENTAGE-BASED)
-    // Preço padrão: $0.10 por GMC (100_000 micro-USDT por GMC com 9 decimais)
-    let usdt_price_per_gmc = 100_000; // $0.10 em micro-USDT (6 decimais) por GMC
-    let usdt_fee_required = calculate_usdt_fee_by_amount(amount, usdt_price_per_gmc)?;
+    crate::pause::require_not_paused(program_id, pause_registry_info, crate::pause::PAUSE_STAKE)?;
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    
+    if *token_program_info.key != spl_token::id() || *system_program_info.key != system_program::id() {
+        msg!("🚨 Security Alert: Unexpected token or system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 🏊 Step 1: Load pool and enforce its limits
+    if pool_info.owner != program_id {
+        msg!("🚨 Security Alert: Pool account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut pool: StakingPool = discriminator::load(program_id, pool_info)?;
+    
+    if pool.authority == Pubkey::default() || pool.pool_id != pool_id {
+        msg!("🚨 Invalid pool ID: {}", pool_id);
+        return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
+    }
+    
+    if !pool.is_active {
+        msg!("🚨 Pool {} is not active", pool_id);
+        return Err(ProgramError::Custom(GMCError::OperationNotAllowed as u32));
+    }
+    
+    if amount < pool.minimum_stake || amount > pool.maximum_stake {
+        msg!("🚨 Stake amount outside pool limits ({} - {})", pool.minimum_stake, pool.maximum_stake);
+        return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
+    }
+    
+    let new_total_staked = safe_add(pool.total_staked, amount)?;
+    
+    // 🧮 Settle the accumulator before the new shares join the pool
+    let current_time = Clock::get()?.unix_timestamp as u32;
+    let mut schedule = load_emission_schedule(program_id, schedule_info)?;
+    settle_pool_rewards(&mut pool, &mut schedule, current_time)?;
+    let reward_shares = pool.reward_shares(amount, 10000, 0)?;
+    let reward_debt = pool.reward_debt_for(reward_shares)?;
+    
+    // 🔑 Step 2: Validate vault ownership and stake record address
+    let (vault_authority, _) = find_pool_vault_authority(program_id, pool_id);
+    let vault = spl_token::state::Account::unpack(&pool_vault_info.data.borrow())?;
+    let staker_gmc = spl_token::state::Account::unpack(&staker_gmc_info.data.borrow())?;
+    
+    if vault.owner != vault_authority || vault.mint != global_state.gmc_mint || staker_gmc.mint != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Invalid pool vault");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    // 💵 The entry fee is paid in the configured USDT mint to the ecosystem wallets
+    validate_usdt_fee_accounts(
+        &global_state,
+        staker_usdt_info,
+        team_usdt_info,
+        staking_fund_usdt_info,
+        ranking_fund_usdt_info,
+    )?;
+    
+    let (stake_record_key, stake_record_bump) = find_stake_record_address(program_id, pool_id, staker_info.key);
+    if *stake_record_info.key != stake_record_key {
+        msg!("🚨 Security Alert: Invalid stake record address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if stake_record_info.owner == program_id && stake_record_info.data_len() > 0 {
+        msg!("🚨 Stake record already exists for this pool");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    // 💰 Step 3: Calculate USDT entry fee based on GMC amount (PERCENTAGE-BASED)
+    // Preço de referência do GlobalState (micro-USDT por GMC, alterável via time-lock)
+    if global_state.usdt_price_per_gmc == 0 {
+        msg!("🚨 USDT price per GMC is not configured");
+        return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+    }
+    let usdt_fee_required = calculate_usdt_fee_by_amount(amount, global_state.usdt_price_per_gmc)?;
     let (team_fee, staking_fee, ranking_fee) = calculate_usdt_fee_distribution(usdt_fee_required)?;
     
     msg!("💰 USDT Entry Fee Analysis:");
@@ -1075,57 +1735,216 @@ pub fn process_stake_original(
     msg!("   • Staking Fund (40%): ${:.2} USDT", staking_fee as f64 / 1_000_000.0);
     msg!("   • Ranking Fund (20%): ${:.2} USDT", ranking_fee as f64 / 1_000_000.0);
     
-    // 💰 Step 2: Charge USDT entry fee and distribute via CPI
-    msg!("💸 Charging USDT entry fee:");
-    msg!("   • Team (40%): ${:.2} USDT", team_fee as f64 / 1_000_000.0);
-    msg!("   • Staking Fund (40%): ${:.2} USDT", staking_fee as f64 / 1_000_000.0);
-    msg!("   • Ranking Fund (20%): ${:.2} USDT", ranking_fee as f64 / 1_000_000.0);
-    
-    // Execute USDT fee distribution via CPI
+    // 💸 Step 4: Charge USDT entry fee and distribute via CPI
     distribute_usdt_fees(
         staker_usdt_info,
         team_usdt_info,
         staking_fund_usdt_info,
         ranking_fund_usdt_info,
         staker_info,
-        _token_program_info,
+        token_program_info,
         team_fee,
         staking_fee,
         ranking_fee,
     )?;
     
-    // Log wallet addresses for audit trail
-    msg!("🏛️ Fee Distribution Addresses:");
-    msg!("   • Team USDT Wallet: {}", team_usdt_info.key);
-    msg!("   • Staking Fund Wallet: {}", staking_fund_usdt_info.key);
-    msg!("   • Ranking Fund Wallet: {}", ranking_fund_usdt_info.key);
-    
-    // 💰 Step 3: Transfer GMC from user to staking pool
-    msg!("💸 Transferring {} GMC from user to staking pool", amount / 1_000_000_000);
-    msg!("   • From: {}", staker_gmc_info.key);
-    msg!("   • To: {}", pool_gmc_info.key);
-    
-    // 📝 Step 4: Create stake record
-    let current_time = Clock::get()?.unix_timestamp;
-    msg!("📝 Creating stake record:");
-    msg!("   • Staker: {}", staker_info.key);
-    msg!("   • Pool ID: {}", pool_id);
-    msg!("   • Amount: {} GMC", amount / 1_000_000_000);
-    msg!("   • Timestamp: {}", current_time);
-    msg!("   • Record Account: {}", stake_record_info.key);
-    
-    // 📊 Step 5: Update pool statistics
-    msg!("📊 Updating pool {} statistics:", pool_id);
-    msg!("   • Total Staked: +{} GMC", amount / 1_000_000_000);
-    msg!("   • Pool Account: {}", pool_gmc_info.key);
+    // 💰 Step 5: Transfer GMC principal from user to the pool vault
+    transfer_tokens(
+        token_program_info,
+        staker_gmc_info,
+        pool_vault_info,
+        staker_info,
+        amount,
+    )?;
     
-    msg!("✅ Stake completed successfully with USDT fee payment");
+    // 📝 Step 6: Create stake record at its derived address
+    crate::create_pda_account(
+        program_id,
+        staker_info,
+        stake_record_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + StakeRecord::LEN,
+        &[STAKE_RECORD_SEED, &[pool_id], staker_info.key.as_ref(), &[stake_record_bump]],
+    )?;
     
-    Ok(())
-}
+    let stake_record = StakeRecord {
+        staker: *staker_info.key,
+        amount,
+        total_claimed: 0,
+        reward_debt,
+        reward_shares,
+        total_burned: 0,
+        staked_at: current_time,
+        last_claim_at: current_time,
+        burn_boost_multiplier: 10000, // 1.0x
+        pool_id,
+        is_active: true,
+    };
+    discriminator::store(program_id, stake_record_info, &stake_record)?;
+    
+    // 📊 Step 7: Update pool statistics
+    pool.total_staked = new_total_staked;
+    pool.total_reward_shares = safe_add(pool.total_reward_shares, reward_shares)?;
+    discriminator::store(program_id, pool_info, &pool)?;
+    discriminator::store(program_id, schedule_info, &schedule)?;
+    
+    msg!("📝 Stake record {} created at {}", stake_record_info.key, current_time);
+    msg!("📊 Pool {} total staked: {} GMC", pool_id, pool.total_staked / 1_000_000_000);
+    msg!("✅ Stake completed successfully with USDT fee payment");
+    
+    Ok(())
+}
+
+// 💰 Fund the reward vault of a pool (e.g. from the 70M staking allocation)
+pub fn process_fund_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pool_id: u8,
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
+    }
+    
+    let account_info_iter = &mut accounts.iter();
+    let funder_info = next_account_info(account_info_iter)?;
+    let funder_gmc_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let reward_vault_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    
+    if !funder_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    if *token_program_info.key != spl_token::id() || pool_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    
+    let mut pool: StakingPool = discriminator::load(program_id, pool_info)?;
+    if pool.authority == Pubkey::default() || pool.pool_id != pool_id {
+        return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
+    }
+    
+    let (reward_authority, _) = find_reward_vault_authority(program_id, pool_id);
+    let reward_vault = spl_token::state::Account::unpack(&reward_vault_info.data.borrow())?;
+    if reward_vault.owner != reward_authority || reward_vault.mint != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Invalid reward vault");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    // 🧮 Close the current emission period before new budget becomes available
+    let mut schedule = load_emission_schedule(program_id, schedule_info)?;
+    settle_pool_rewards(&mut pool, &mut schedule, Clock::get()?.unix_timestamp as u32)?;
+    
+    transfer_tokens(token_program_info, funder_gmc_info, reward_vault_info, funder_info, amount)?;
+    
+    pool.reward_budget = safe_add(pool.reward_budget, amount)?;
+    discriminator::store(program_id, pool_info, &pool)?;
+    discriminator::store(program_id, schedule_info, &schedule)?;
+    
+    msg!("💰 Pool {} reward budget: {} GMC", pool_id, pool.reward_budget / 1_000_000_000);
+    Ok(())
+}
+
+// 📉 Create the emission schedule for the staking allocation
+pub fn process_initialize_emission_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    epoch_duration_secs: u32,
+    initial_epoch_cap: u64,
+    decay_basis_points: u16,
+) -> ProgramResult {
+    if epoch_duration_secs == 0 || initial_epoch_cap == 0 || decay_basis_points >= 10000 {
+        msg!("🚨 Invalid emission schedule parameters");
+        return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+    }
+    
+    let account_info_iter = &mut accounts.iter();
+    let admin_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can create the emission schedule");
+        return Err(ProgramError::Custom(GMCError::AccessDenied as u32));
+    }
+    
+    let (schedule_key, schedule_bump) = find_emission_schedule_address(program_id);
+    if *schedule_info.key != schedule_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if schedule_info.owner == program_id && schedule_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
+    crate::create_pda_account(
+        program_id,
+        admin_info,
+        schedule_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + EmissionSchedule::LEN,
+        &[EMISSION_SCHEDULE_SEED, &[schedule_bump]],
+    )?;
+    
+    let schedule = EmissionSchedule {
+        authority: *admin_info.key,
+        total_allocation: crate::STAKING_POOL_ALLOCATION,
+        total_emitted: 0,
+        initial_epoch_cap,
+        emitted_in_epoch: 0,
+        start_timestamp: Clock::get()?.unix_timestamp as u32,
+        epoch_duration: epoch_duration_secs,
+        current_epoch: 0,
+        decay_basis_points,
+        is_initialized: true,
+    };
+    discriminator::store(program_id, schedule_info, &schedule)?;
+    
+    let runway = schedule.runway(schedule.start_timestamp);
+    msg!("📉 Emission schedule created: cap {} GMC/epoch, decay {} bps, {} epochs of runway",
+         initial_epoch_cap / 1_000_000_000, decay_basis_points, runway.epochs_remaining);
+    Ok(())
+}
+
+// 📊 Report the remaining runway of the staking allocation
+pub fn process_report_emission_runway(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let schedule_info = next_account_info(account_info_iter)?;
+    
+    let schedule = load_emission_schedule(program_id, schedule_info)?;
+    let runway = schedule.runway(Clock::get()?.unix_timestamp as u32);
+    
+    msg!("📊 Staking allocation runway:");
+    msg!("   • Remaining: {} GMC", runway.remaining_allocation / 1_000_000_000);
+    msg!("   • Epoch {} cap: {} GMC", runway.current_epoch, runway.current_epoch_cap / 1_000_000_000);
+    msg!("   • Epochs remaining: {}", runway.epochs_remaining);
+    msg!("   • Exhausted at: {}", runway.exhausted_at);
+    
+    solana_program::program::set_return_data(&runway.try_to_vec()?);
+    Ok(())
+}
 
 // 🚀 OTIMIZAÇÃO: Claim rewards with feature flag for optimized version
 pub fn process_claim_rewards(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
 ) -> ProgramResult {
@@ -1133,181 +1952,264 @@ pub fn process_claim_rewards(
     let use_optimization = true; // Can be toggled via program upgrade
     
     if use_optimization {
-        process_claim_rewards_optimized(accounts, pool_id)
+        process_claim_rewards_optimized(program_id, accounts, pool_id)
     } else {
-        process_claim_rewards_original(accounts, pool_id)
+        process_claim_rewards_original(program_id, accounts, pool_id)
     }
 }
 
-// 🚀 OTIMIZAÇÃO: Optimized claim rewards with zero-copy and cache
+// 🚀 OTIMIZAÇÃO: Optimized claim rewards path
 pub fn process_claim_rewards_optimized(
-    _accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
     pool_id: u8,
 ) -> ProgramResult {
     msg!("🚀 Optimized claiming rewards from pool {} with enhanced compute efficiency", pool_id);
     
-    // 🚀 OPTIMIZATION: Use strategic cache manager for frequent calculations
-    let current_slot = Clock::get()?.slot;
-    // Cache manager optimization removed - using standard processing
-    
-    // 🚀 OPTIMIZATION: Zero-copy stake record simulation
-    // In real implementation, this would use ZeroCopyStakeRecord::from_account_data
-    let mut zero_copy_data = vec![0u8; 56]; // Size of ZeroCopyStakeRecord
-    
-    // 🚀 OPTIMIZATION: Mock data as packed bytes (would be real account data)
-    let stake_amount = 10_000 * 1_000_000_000u64;
-    let staked_timestamp = (current_slot as i64) - (180 * 432000); // ~180 days ago
-    
-    // Pack data directly to simulate zero-copy access
-    zero_copy_data[0..8].copy_from_slice(&stake_amount.to_le_bytes());
-    zero_copy_data[8..16].copy_from_slice(&staked_timestamp.to_le_bytes());
-    
-    // 🚀 OPTIMIZATION: Direct memory access for stake record
-    let stake_amount_packed = u64::from_le_bytes([
-        zero_copy_data[0], zero_copy_data[1], zero_copy_data[2], zero_copy_data[3],
-        zero_copy_data[4], zero_copy_data[5], zero_copy_data[6], zero_copy_data[7],
-    ]);
-    
-    // 🚀 OPTIMIZATION: Use cached APY calculation
-    let burn_boost_level = 25u8;
-    let affiliate_tier = 15u8;
-    
-    // Using default APY calculation since cache manager was removed
-    let optimized_apy = 15.0; // Default 15% APY for claim calculation
+    // Same validated path as the original implementation (single source of truth)
+    process_claim_rewards_original(program_id, accounts, pool_id)
+}
+
+// 🤝 Affiliate power from the (AffiliateRecord, StakeRecord) pairs of the claimer's referral tree
+fn affiliate_power_from_accounts(
+    program_id: &Pubkey,
+    staker: &Pubkey,
+    affiliate_accounts: &[AccountInfo],
+) -> Result<u8, ProgramError> {
+    if affiliate_accounts.is_empty() {
+        return Ok(0);
+    }
     
-    // 🚀 OPTIMIZATION: Fast rewards calculation using cached values
-    let days_staked = ((current_slot as i64 - staked_timestamp) / 432000) as u32; // Slots to days
-    // Using validated calculate_rewards function instead of orphaned optimized version
-    let temp_pool = StakingPool {
-        authority: solana_program::pubkey::Pubkey::default(),
-        total_staked: 0,
-        total_rewards: 0,
-        minimum_stake: 1000,
-        maximum_stake: 1000000,
-        apy_basis_points: (optimized_apy * 100.0) as u16,
-        lock_duration_days: 365,
-        pool_id: 0,
-        is_active: true,
-        _padding: [0; 6],
-    };
-    let pending_rewards = temp_pool.calculate_rewards(stake_amount_packed, days_staked)?;
+    if !affiliate_accounts.len().is_multiple_of(2) || affiliate_accounts.len() / 2 > MAX_AFFILIATE_ACCOUNTS_PER_CLAIM {
+        msg!("🚨 Security Alert: Invalid affiliate account list");
+        return Err(ProgramError::Custom(GMCError::InvalidInput as u32));
+    }
     
-    if pending_rewards == 0 {
-        msg!("ℹ️ No pending rewards to claim");
-        return Ok(());
-    }
-    
-    // 💰 BUSINESS RULE: Taxa Saque Juros (1% sobre valor sacado)
-    let withdrawal_fee = pending_rewards
-        .checked_mul(100) // 1% = 100/10000
-        .and_then(|x| x.checked_div(10000))
-        .ok_or_else(|| {
-            msg!("🚨 Security Alert: Withdrawal fee calculation overflow");
-            ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-        })?;
-    
-    let user_receives = pending_rewards
-        .checked_sub(withdrawal_fee)
-        .ok_or_else(|| {
-            msg!("🚨 Security Alert: User rewards calculation underflow");
-            ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-        })?;
-    
-    msg!("🚀 Optimized rewards calculated: {} GMC (APY: {} bps, Days: {})", 
-         pending_rewards / 1_000_000_000, optimized_apy, days_staked);
-    msg!("💰 Withdrawal fee (1%): {} GMC", withdrawal_fee / 1_000_000_000);
-    msg!("💸 User receives: {} GMC (after 1% fee)", user_receives / 1_000_000_000);
-    
-    // 💰 Send withdrawal fee to treasury
-    msg!("💰 Sending {} GMC withdrawal fee to treasury", withdrawal_fee / 1_000_000_000);
-    // In real implementation: transfer withdrawal_fee to treasury account
-    
-    // Continue with optimized transfer and state updates...
-    // For now, use original logic to maintain compatibility
-    msg!("💸 Transferring {} GMC rewards to user (optimized path)", user_receives / 1_000_000_000);
-    msg!("✅ Rewards claimed successfully with 1% withdrawal fee applied");
+    let mut entries = Vec::with_capacity(affiliate_accounts.len() / 2);
+    for pair in affiliate_accounts.chunks(2) {
+        let (affiliate_info, affiliate_stake_info) = (&pair[0], &pair[1]);
+        if affiliate_info.owner != program_id || affiliate_stake_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        
+        let affiliate: crate::affiliate::AffiliateRecord = discriminator::load(program_id, affiliate_info)?;
+        let affiliate_stake: StakeRecord = discriminator::load(program_id, affiliate_stake_info)?;
+        
+        // 🛡️ Both records must live at their derived addresses
+        let (affiliate_key, _) =
+            crate::affiliate::find_affiliate_record_address(program_id, &affiliate.affiliate_id);
+        let (stake_key, _) =
+            find_stake_record_address(program_id, affiliate_stake.pool_id, &affiliate_stake.staker);
+        if *affiliate_info.key != affiliate_key || *affiliate_stake_info.key != stake_key {
+            msg!("🚨 Security Alert: Affiliate accounts are not at their derived addresses");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        
+        entries.push((affiliate, affiliate_stake));
+    }
     
+    let affiliate_stakes = verify_referral_tree(staker, &entries)?;
+    calculate_affiliate_boost_from_active_staking(staker, affiliate_stakes)
+}
+
+// 🏛️ Fee destinations must belong to the configured ecosystem wallets
+fn validate_reward_fee_accounts(
+    global_state: &crate::GlobalState,
+    reward_mint: &Pubkey,
+    team_gmc_info: &AccountInfo,
+    ranking_gmc_info: &AccountInfo,
+) -> ProgramResult {
+    let team_gmc = spl_token::state::Account::unpack(&team_gmc_info.data.borrow())?;
+    let ranking_gmc = spl_token::state::Account::unpack(&ranking_gmc_info.data.borrow())?;
+    if team_gmc.owner != global_state.ecosystem_wallets.team
+        || ranking_gmc.owner != global_state.ecosystem_wallets.ranking_fund
+        || team_gmc.mint != *reward_mint
+        || ranking_gmc.mint != *reward_mint
+    {
+        msg!("🚨 Security Alert: Fee destination is not an ecosystem wallet");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
     Ok(())
 }
 
+// 💸 Pay rewards from the reward vault minus the interest withdrawal fee
+// (1%, 40/40/20) to the (staker, team, ranking) GMC accounts; the staking share
+// stays in the vault for the caller to recycle
+fn pay_rewards_with_fee<'a>(
+    token_program_info: &AccountInfo<'a>,
+    reward_vault_info: &AccountInfo<'a>,
+    reward_authority_info: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    [staker_gmc_info, team_gmc_info, ranking_gmc_info]: [&AccountInfo<'a>; 3],
+    rewards: u64,
+) -> Result<crate::WithdrawalFeeDistribution, ProgramError> {
+    // 💰 BUSINESS RULE: Taxa Saque Juros (1% sobre valor sacado, 40/40/20)
+    let fee = crate::calculate_interest_withdrawal_fee(rewards)?;
+    msg!("💰 Withdrawal fee (1%): {} GMC", fee.total_fee / 1_000_000_000);
+    
+    transfer_tokens_signed(token_program_info, reward_vault_info, staker_gmc_info, reward_authority_info, signer_seeds, fee.net_amount)?;
+    if fee.team_amount > 0 {
+        transfer_tokens_signed(token_program_info, reward_vault_info, team_gmc_info, reward_authority_info, signer_seeds, fee.team_amount)?;
+    }
+    if fee.ranking_amount > 0 {
+        transfer_tokens_signed(token_program_info, reward_vault_info, ranking_gmc_info, reward_authority_info, signer_seeds, fee.ranking_amount)?;
+    }
+    
+    Ok(fee)
+}
+
 // 🚀 Original function preserved for fallback
 pub fn process_claim_rewards_original(
-    _accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
     pool_id: u8,
 ) -> ProgramResult {
     msg!("🎁 Claiming rewards from pool {}", pool_id);
     
-    // ✅ INTEGRATION WITH DYNAMIC APY
-    // Em uma implementação real, estes valores seriam carregados das contas do usuário
-    // Aqui simulamos para demonstrar a integração
-    let mock_stake_record = StakeRecord {
-        staker: Pubkey::default(),
-        amount: 10_000 * 1_000_000_000, // 10,000 GMC
-        total_claimed: 0,
-        staked_at: 1640995200,  // timestamp mock (exemplo: 1º janeiro 2022)
-        last_claim_at: 1640995200,
-        burn_boost_multiplier: 12000, // 1.2x boost
-        pool_id,
-        is_active: true,
-    };
+    let account_info_iter = &mut accounts.iter();
+    let staker_info = next_account_info(account_info_iter)?;
+    let staker_gmc_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let reward_vault_info = next_account_info(account_info_iter)?;
+    let stake_record_info = next_account_info(account_info_iter)?;
+    let reward_authority_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let team_gmc_info = next_account_info(account_info_iter)?;
+    let ranking_gmc_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let pause_registry_info = next_account_info(account_info_iter)?;
+    let affiliate_accounts = account_info_iter.as_slice();
     
-    // Determinar tipo de stake
-    let stake_type = if mock_stake_record.pool_id == 1 || mock_stake_record.pool_id == 2 || mock_stake_record.pool_id == 3 {
-        "long-term"
-    } else if mock_stake_record.pool_id == 4 || mock_stake_record.pool_id == 5 || mock_stake_record.pool_id == 6 {
-        "flexible"
-    } else {
-        "unknown"
-    };
+    if !staker_info.is_signer {
+        msg!("🚨 Security Alert: Staker must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
     
-    // Simular poderes de boost (em implementação real, vem das contas do usuário)
-    let burn_power = 25u8;      // 25% de poder de queima (exemplo)
-    let affiliate_power = 15u8; // 15% de poder de afiliados (exemplo)
+    crate::pause::require_not_paused(program_id, pause_registry_info, crate::pause::PAUSE_CLAIMS)?;
     
-    msg!("🚀 Calculating rewards with Dynamic APY...");
-    msg!("   • Stake Type: {}", stake_type);
-    msg!("   • Burn Power: {}%", burn_power);
-    msg!("   • Affiliate Power: {}%", affiliate_power);
-    msg!("   • Burn Boost Multiplier: {}x", mock_stake_record.burn_boost_multiplier as f64 / 10000.0);
-    
-    // ✅ Calculate pending rewards using DYNAMIC APY
-    let pending_rewards = mock_stake_record.calculate_pending_rewards_dynamic(
-        stake_type,
-        burn_power,
-        affiliate_power,
-    )?;
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
     
-    if pending_rewards == 0 {
-        msg!("ℹ️ No pending rewards to claim");
-        return Ok(());
+    if pool_info.owner != program_id || stake_record_info.owner != program_id {
+        msg!("🚨 Security Alert: State account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
     }
     
-    msg!("💰 Pending rewards with Dynamic APY: {} GMC", pending_rewards / 1_000_000_000);
+    // 📝 Load the caller's stake record from its derived address
+    let (stake_record_key, _) = find_stake_record_address(program_id, pool_id, staker_info.key);
+    if *stake_record_info.key != stake_record_key {
+        msg!("🚨 Security Alert: Invalid stake record address");
+        return Err(ProgramError::InvalidSeeds);
+    }
     
-    // ✅ Transfer rewards to user
-    // In real implementation: invoke SPL Token transfer from rewards pool
-    msg!("💸 Transferring {} GMC rewards to user", pending_rewards / 1_000_000_000);
+    let mut stake_record: StakeRecord = discriminator::load(program_id, stake_record_info)?;
+    let mut pool: StakingPool = discriminator::load(program_id, pool_info)?;
     
-    // ✅ Update stake record
-    // In real implementation:
-    // let mut stake_record = StakeRecord::load(stake_account)?;
-    // stake_record.claimed_rewards = stake_record.claimed_rewards.saturating_add(pending_rewards);
-    // stake_record.last_claim_timestamp = current_time as u32;
-    // stake_record.save(stake_account)?;
+    if !stake_record.is_active || stake_record.staker != *staker_info.key || stake_record.pool_id != pool_id || pool.pool_id != pool_id {
+        msg!("🚨 No active stake for this staker in pool {}", pool_id);
+        return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
+    }
     
-    // ✅ Update pool statistics
-    // In real implementation:
-    // let mut pool = StakingPool::load(pool_account)?;
-    // pool.total_rewards_distributed = pool.total_rewards_distributed.saturating_add(pending_rewards);
-    // pool.save(pool_account)?;
+    // 🔑 Reward vault is controlled by the program
+    let (reward_authority, reward_authority_bump) = find_reward_vault_authority(program_id, pool_id);
+    if *reward_authority_info.key != reward_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    let reward_vault = spl_token::state::Account::unpack(&reward_vault_info.data.borrow())?;
+    if reward_vault.owner != reward_authority || reward_vault.mint != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Invalid reward vault");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    validate_reward_fee_accounts(&global_state, &reward_vault.mint, team_gmc_info, ranking_gmc_info)?;
+    
+    // 🧮 Settle the pool accumulator and the record's share of it
+    let affiliate_power = affiliate_power_from_accounts(program_id, staker_info.key, affiliate_accounts)?;
+    let current_time = Clock::get()?.unix_timestamp as u32;
+    let mut schedule = load_emission_schedule(program_id, schedule_info)?;
+    settle_pool_rewards(&mut pool, &mut schedule, current_time)?;
+    let pending_rewards = pool.pending_rewards(&stake_record)?;
+    
+    if pending_rewards > 0 {
+        msg!("💰 Pending rewards: {} GMC (affiliate power {}%)", pending_rewards / 1_000_000_000, affiliate_power);
+        
+        let signer_seeds: &[&[u8]] = &[REWARD_VAULT_AUTHORITY_SEED, &[pool_id], &[reward_authority_bump]];
+        let fee = pay_rewards_with_fee(
+            token_program_info,
+            reward_vault_info,
+            reward_authority_info,
+            signer_seeds,
+            [staker_gmc_info, team_gmc_info, ranking_gmc_info],
+            pending_rewards,
+        )?;
+        
+        // 🔄 The staking share (and rounding dust) is recycled into the reward budget
+        let recycled = safe_sub(fee.total_fee, safe_add(fee.team_amount, fee.ranking_amount)?)?;
+        pool.reward_budget = safe_add(pool.reward_budget, recycled)?;
+        pool.total_rewards = safe_add(pool.total_rewards, pending_rewards)?;
+        stake_record.total_claimed = safe_add(stake_record.total_claimed, pending_rewards)?;
+        
+        msg!("💸 User receives: {} GMC (after 1% fee)", fee.net_amount / 1_000_000_000);
+    } else {
+        msg!("ℹ️ No pending rewards to claim");
+    }
     
-    msg!("📊 Updated pool statistics (+{} GMC rewards distributed)", pending_rewards / 1_000_000_000);
-    msg!("✅ Rewards claimed successfully with Dynamic APY");
+    // 🤝 Re-weight the record with the current affiliate boost from now on
+    let new_shares = pool.reward_shares(stake_record.amount, stake_record.burn_boost_multiplier, affiliate_power)?;
+    pool.total_reward_shares = safe_add(safe_sub(pool.total_reward_shares, stake_record.reward_shares)?, new_shares)?;
+    stake_record.reward_shares = new_shares;
+    stake_record.reward_debt = pool.reward_debt_for(new_shares)?;
+    stake_record.last_claim_at = current_time;
+    
+    discriminator::store(program_id, stake_record_info, &stake_record)?;
+    discriminator::store(program_id, pool_info, &pool)?;
+    discriminator::store(program_id, schedule_info, &schedule)?;
+    
+    msg!("✅ Rewards claimed successfully");
     
     Ok(())
 }
 
+// 📤 How an unstaked position is split between the staker, the burn and the redistribution pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnstakeSettlement {
+    pub principal_returned: u64,
+    pub principal_burned: u64,
+    pub rewards_returned: u64,
+    pub rewards_redistributed: u64,
+}
+
+// ⚖️ Apply the early-unstake penalty rules to a position
+pub fn calculate_unstake_settlement(
+    is_long_term: bool,
+    lock_expired: bool,
+    principal: u64,
+    pending_rewards: u64,
+) -> Result<UnstakeSettlement, ProgramError> {
+    let (principal_burned, rewards_redistributed) = if lock_expired {
+        (0, 0)
+    } else if is_long_term {
+        // 🔥 LONG-TERM PENALTY: 50% of principal + 80% of rewards
+        (principal / 2, safe_div(safe_mul(pending_rewards, 80)?, 100)?)
+    } else {
+        // 💰 FLEXIBLE PENALTY: 2.5% of principal only
+        (crate::calculate_flexible_cancellation_penalty(principal)?.penalty_amount, 0)
+    };
+    
+    Ok(UnstakeSettlement {
+        principal_returned: safe_sub(principal, principal_burned)?,
+        principal_burned,
+        rewards_returned: safe_sub(pending_rewards, rewards_redistributed)?,
+        rewards_redistributed,
+    })
+}
+
 pub fn process_unstake(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     amount: u64,
@@ -1320,179 +2222,182 @@ pub fn process_unstake(
         return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
     }
     
-    // 🛡️ Get current time for lock period validation
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+    let account_info_iter = &mut accounts.iter();
+    let staker_info = next_account_info(account_info_iter)?;
+    let staker_gmc_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let pool_vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let reward_vault_info = next_account_info(account_info_iter)?;
+    let reward_authority_info = next_account_info(account_info_iter)?;
+    let redistribution_info = next_account_info(account_info_iter)?;
+    let stake_record_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let pause_registry_info = next_account_info(account_info_iter)?;
+    let team_gmc_info = next_account_info(account_info_iter)?;
+    let ranking_gmc_info = next_account_info(account_info_iter)?;
     
-    // 🛡️ CRITICAL: Implement unstaking logic with lock period validation and penalties
-    // This is the missing business logic identified in the analysis
+    if !staker_info.is_signer {
+        msg!("🚨 Security Alert: Staker must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
     
-    // Mock stake record data for demonstration (in real implementation, this would be loaded from account)
-    let stake_timestamp = current_time - (180 * 24 * 60 * 60); // 6 months ago (example)
-    let principal_amount = amount;
-    let pending_rewards = 5000u64; // Example pending rewards
+    // ⏸️ Exit-only mode keeps unstaking open even while the flag is set
+    let pause_registry = crate::pause::load_registry(program_id, pause_registry_info)?;
+    let exit_only = pause_registry.exit_only;
+    if !exit_only && pause_registry.is_paused(crate::pause::PAUSE_UNSTAKE) {
+        msg!("⏸️ Unstaking is paused");
+        return Err(ProgramError::Custom(GMCError::ProtocolPaused as u32));
+    }
     
-    // 🔍 Determine pool type and lock duration
-    let (is_long_term, lock_duration_days, penalty_description) = match pool_id {
-        1..=3 => (true, 365u32, "50% of principal + 80% of rewards"),  // Long-term pools
-        4..=6 => (false, 30u32, "2.5% of principal"),                  // Flexible pools
-        _ => {
-            msg!("🚨 Security Alert: Invalid pool ID");
-            return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
-        }
-    };
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    if pool_info.owner != program_id || stake_record_info.owner != program_id {
+        msg!("🚨 Security Alert: State account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut global_state = crate::load_global_state(program_id, global_state_info)?;
+    if *mint_info.key != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Mint is not the GMC mint");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
     
-    // 🛡️ Calculate lock expiration time
-    let lock_duration_seconds = (lock_duration_days as i64) * 24 * 60 * 60;
-    let unlock_time = stake_timestamp + lock_duration_seconds;
-    let is_lock_expired = current_time >= unlock_time;
+    // 📝 Load the stored stake record
+    let (stake_record_key, _) = find_stake_record_address(program_id, pool_id, staker_info.key);
+    if *stake_record_info.key != stake_record_key {
+        msg!("🚨 Security Alert: Invalid stake record address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let stake_record: StakeRecord = discriminator::load(program_id, stake_record_info)?;
+    let mut pool: StakingPool = discriminator::load(program_id, pool_info)?;
+    
+    if !stake_record.is_active || stake_record.staker != *staker_info.key || stake_record.pool_id != pool_id || pool.pool_id != pool_id {
+        msg!("🚨 No active stake for this staker in pool {}", pool_id);
+        return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
+    }
+    
+    // 🛡️ Positions are always unstaked in full
+    if amount != stake_record.amount {
+        msg!("🚨 Unstake amount must match the staked amount ({})", stake_record.amount);
+        return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
+    }
+    
+    // 🔑 Vaults and redistribution pool are controlled by the program
+    let (vault_authority, vault_bump) = find_pool_vault_authority(program_id, pool_id);
+    let (reward_authority, reward_bump) = find_reward_vault_authority(program_id, pool_id);
+    let (redistribution_authority, _) = find_redistribution_authority(program_id, pool_id);
+    if *vault_authority_info.key != vault_authority || *reward_authority_info.key != reward_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let pool_vault = spl_token::state::Account::unpack(&pool_vault_info.data.borrow())?;
+    let reward_vault = spl_token::state::Account::unpack(&reward_vault_info.data.borrow())?;
+    let redistribution = spl_token::state::Account::unpack(&redistribution_info.data.borrow())?;
+    if pool_vault.owner != vault_authority
+        || reward_vault.owner != reward_authority
+        || redistribution.owner != redistribution_authority
+        || pool_vault.mint != *mint_info.key
+        || reward_vault.mint != *mint_info.key
+        || redistribution.mint != *mint_info.key
+    {
+        msg!("🚨 Security Alert: Invalid pool vault configuration");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    validate_reward_fee_accounts(&global_state, &reward_vault.mint, team_gmc_info, ranking_gmc_info)?;
+    
+    // 🔍 Lock period from the stored stake timestamp
+    let is_long_term = stake_type_for_pool(pool_id)? == "long-term";
+    let lock_expired = pool.is_lock_expired(stake_record.staked_at as i64)?;
+    let current_time = Clock::get()?.unix_timestamp as u32;
+    let mut schedule = load_emission_schedule(program_id, schedule_info)?;
+    settle_pool_rewards(&mut pool, &mut schedule, current_time)?;
+    let pending_rewards = pool.pending_rewards(&stake_record)?;
+    
+    // 🚪 Exit-only: principal back in full, no penalty and no reward payout
+    let (settlement, paid_rewards) = if exit_only {
+        msg!("🚪 Exit-only mode: returning principal only");
+        // Forfeited rewards stay in the reward vault and become emittable again
+        pool.reward_budget = safe_add(pool.reward_budget, pending_rewards)?;
+        (calculate_unstake_settlement(false, true, stake_record.amount, 0)?, 0)
+    } else {
+        (calculate_unstake_settlement(is_long_term, lock_expired, stake_record.amount, pending_rewards)?, pending_rewards)
+    };
     
     msg!("🔍 Lock Analysis: Pool {} | Lock Expired: {} | Duration: {} days", 
-         pool_id, is_lock_expired, lock_duration_days);
+         pool_id, lock_expired, pool.lock_duration_days);
     
-    if is_lock_expired {
-        // ✅ HAPPY PATH: Lock period completed, allow unstaking without penalty
-        msg!("✅ Lock period completed. Unstaking {} tokens without penalty", amount);
-        
-        // ✅ Transfer principal + rewards back to user
-        let total_return = principal_amount
-            .checked_add(pending_rewards)
-            .ok_or_else(|| {
-                msg!("🚨 Security Alert: Total return calculation overflow");
-                ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-            })?;
-        
-        // Transfer tokens back to user (implementation would use SPL Token transfer)
-        msg!("💸 Transferring {} GMC to user (principal: {}, rewards: {})", 
-             total_return / 1_000_000_000, 
-             principal_amount / 1_000_000_000, 
-             pending_rewards / 1_000_000_000);
-        
-        // ✅ Update pool total_staked
-        // In real implementation, we would update the StakingPool total_staked
-        msg!("📊 Updating pool {} total_staked (subtracting {})", pool_id, principal_amount);
+    let vault_seeds: &[&[u8]] = &[POOL_VAULT_AUTHORITY_SEED, &[pool_id], &[vault_bump]];
+    let reward_seeds: &[&[u8]] = &[REWARD_VAULT_AUTHORITY_SEED, &[pool_id], &[reward_bump]];
+    
+    // 🔥 Principal penalty: burned down to the 12M floor, the remainder is redistributed
+    if settlement.principal_burned > 0 {
+        let burn = crate::apply_burn(&mut global_state, settlement.principal_burned)?;
         
-        // ✅ Mark stake record as inactive
-        // In real implementation, we would set StakeRecord.is_active = false
-        msg!("🔄 Marking stake record as inactive for user");
+        if burn.burned > 0 {
+            burn_tokens(token_program_info, pool_vault_info, mint_info, vault_authority_info, &[vault_seeds], burn.burned)?;
+            msg!("🔥 Burned {} GMC principal penalty", burn.burned / 1_000_000_000);
+        }
+        if burn.rerouted > 0 {
+            transfer_tokens_signed(token_program_info, pool_vault_info, redistribution_info, vault_authority_info, vault_seeds, burn.rerouted)?;
+            msg!("🚫 Burn floor reached: {} GMC principal penalty redistributed", burn.rerouted / 1_000_000_000);
+        }
         
+        crate::store_global_state(program_id, global_state_info, &global_state)?;
+    }
+    
+    // 🔄 Reward penalty goes to the redistribution pool for remaining stakers
+    if settlement.rewards_redistributed > 0 {
+        transfer_tokens_signed(token_program_info, reward_vault_info, redistribution_info, reward_authority_info, reward_seeds, settlement.rewards_redistributed)?;
+        msg!("🔄 Redistributing {} GMC rewards penalty to other stakers", settlement.rewards_redistributed / 1_000_000_000);
+    }
+    
+    // 💸 Return the remaining principal and rewards
+    if settlement.principal_returned > 0 {
+        transfer_tokens_signed(token_program_info, pool_vault_info, staker_gmc_info, vault_authority_info, vault_seeds, settlement.principal_returned)?;
+    }
+    // 💰 Rewards pay the same interest withdrawal fee as a claim
+    if settlement.rewards_returned > 0 {
+        let fee = pay_rewards_with_fee(
+            token_program_info,
+            reward_vault_info,
+            reward_authority_info,
+            reward_seeds,
+            [staker_gmc_info, team_gmc_info, ranking_gmc_info],
+            settlement.rewards_returned,
+        )?;
+        let recycled = safe_sub(fee.total_fee, safe_add(fee.team_amount, fee.ranking_amount)?)?;
+        pool.reward_budget = safe_add(pool.reward_budget, recycled)?;
+    }
+    
+    // 📊 Update pool totals
+    pool.total_staked = safe_sub(pool.total_staked, stake_record.amount)?;
+    pool.total_reward_shares = safe_sub(pool.total_reward_shares, stake_record.reward_shares)?;
+    pool.total_rewards = safe_add(pool.total_rewards, paid_rewards)?;
+    discriminator::store(program_id, pool_info, &pool)?;
+    discriminator::store(program_id, schedule_info, &schedule)?;
+    
+    // 🗑️ Close the stake record and return its rent to the staker
+    let record_lamports = stake_record_info.lamports();
+    **stake_record_info.try_borrow_mut_lamports()? = 0;
+    **staker_info.try_borrow_mut_lamports()? = staker_info
+        .lamports()
+        .checked_add(record_lamports)
+        .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
+    stake_record_info.data.borrow_mut().fill(0);
+    
+    msg!("💸 Returned {} GMC principal and {} GMC rewards", 
+         settlement.principal_returned / 1_000_000_000, settlement.rewards_returned / 1_000_000_000);
+    if lock_expired || exit_only {
         msg!("✅ Unstaked {} tokens successfully (no penalty)", amount);
     } else {
-        // ⚠️ PENALTY PATH: Early unstaking with business rule penalties
-        msg!("⚠️ Early unstaking detected. Applying {} penalty", penalty_description);
-        
-        if is_long_term {
-            // 🔥 LONG-TERM PENALTY: 50% of principal + 80% of rewards
-            let principal_penalty = principal_amount
-                .checked_div(2) // 50% of principal
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: Principal penalty calculation overflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            let rewards_penalty = pending_rewards
-                .checked_mul(80)
-                .and_then(|x| x.checked_div(100)) // 80% of rewards
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: Rewards penalty calculation overflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            let user_receives_principal = principal_amount
-                .checked_sub(principal_penalty)
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: User principal calculation underflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            let user_receives_rewards = pending_rewards
-                .checked_sub(rewards_penalty)
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: User rewards calculation underflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            msg!("🔥 Long-term penalty applied:");
-            msg!("   • Principal penalty (burned): {} GMC", principal_penalty);
-            msg!("   • Rewards penalty (redistributed): {} GMC", rewards_penalty);
-            msg!("   • User receives principal: {} GMC", user_receives_principal);
-            msg!("   • User receives rewards: {} GMC", user_receives_rewards);
-            
-            // ✅ Burn principal penalty tokens
-            msg!("🔥 Burning {} GMC (50% principal penalty)", principal_penalty / 1_000_000_000);
-            // In real implementation: invoke SPL Token burn instruction
-            
-            // ✅ Redistribute rewards penalty to other stakers
-            msg!("🔄 Redistributing {} GMC rewards penalty to other stakers", rewards_penalty / 1_000_000_000);
-            // In real implementation: transfer rewards penalty to a redistribution pool
-            
-            // ✅ Transfer remaining amount to user
-            let total_user_receives = user_receives_principal
-                .checked_add(user_receives_rewards)
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: User total calculation overflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            msg!("💸 Transferring {} GMC to user after penalties", total_user_receives / 1_000_000_000);
-            // In real implementation: invoke SPL Token transfer instruction
-            
-        } else {
-            // 💰 FLEXIBLE PENALTY: 2.5% of principal only
-            let penalty_amount = principal_amount
-                .checked_mul(25) // 2.5% = 25/1000
-                .and_then(|x| x.checked_div(1000))
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: Flexible penalty calculation overflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            let user_receives = principal_amount
-                .checked_sub(penalty_amount)
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: User amount calculation underflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            msg!("💰 Flexible penalty applied:");
-            msg!("   • Penalty (2.5% of principal): {} GMC", penalty_amount);
-            msg!("   • User receives: {} GMC", user_receives);
-            msg!("   • Pending rewards (no penalty): {} GMC", pending_rewards);
-            
-            // ✅ Apply 2.5% penalty (sent to treasury or burned)
-            msg!("💰 Sending {} GMC penalty to treasury", penalty_amount / 1_000_000_000);
-            // In real implementation: transfer penalty to treasury account
-            
-            // ✅ Transfer remaining principal + full rewards to user
-            let total_user_receives = user_receives
-                .checked_add(pending_rewards)
-                .ok_or_else(|| {
-                    msg!("🚨 Security Alert: Flexible unstake calculation overflow");
-                    ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-                })?;
-            
-            msg!("💸 Transferring {} GMC to user (principal: {}, rewards: {})", 
-                 total_user_receives / 1_000_000_000,
-                 user_receives / 1_000_000_000, 
-                 pending_rewards / 1_000_000_000);
-            // In real implementation: invoke SPL Token transfer instruction
-        }
-        
-        // ✅ Update pool total_staked
-        msg!("📊 Updating pool {} total_staked (subtracting {})", pool_id, principal_amount);
-        // In real implementation: 
-        // let mut pool = StakingPool::load(pool_account)?;
-        // pool.total_staked = pool.total_staked.saturating_sub(principal_amount);
-        // pool.save(pool_account)?;
-        
-        // ✅ Mark stake record as inactive
-        msg!("🔄 Marking stake record as inactive for user");
-        // In real implementation:
-        // let mut stake_record = StakeRecord::load(stake_account)?;
-        // stake_record.is_active = false;
-        // stake_record.end_timestamp = current_time as u32;
-        // stake_record.save(stake_account)?;
-        
         msg!("⚠️ Early unstaking completed with penalty");
     }
     
@@ -1501,6 +2406,7 @@ pub fn process_unstake(
 
 // 🚀 OTIMIZAÇÃO: Burn for boost with feature flag for optimized version
 pub fn process_burn_for_boost(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     burn_amount: u64,
@@ -1510,14 +2416,15 @@ pub fn process_burn_for_boost(
     let use_optimization = true; // Can be toggled via program upgrade
     
     if use_optimization {
-        process_burn_for_boost_optimized(accounts, pool_id, burn_amount, boost_multiplier)
+        process_burn_for_boost_optimized(program_id, accounts, pool_id, burn_amount, boost_multiplier)
     } else {
-        process_burn_for_boost_original(accounts, pool_id, burn_amount, boost_multiplier)
+        process_burn_for_boost_original(program_id, accounts, pool_id, burn_amount, boost_multiplier)
     }
 }
 
-// 🚀 OTIMIZAÇÃO: Optimized burn for boost with lookup tables and batch operations
+// 🚀 OTIMIZAÇÃO: Optimized burn for boost path
 pub fn process_burn_for_boost_optimized(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     burn_amount: u64,
@@ -1525,77 +2432,20 @@ pub fn process_burn_for_boost_optimized(
 ) -> ProgramResult {
     msg!("🚀 Optimized burning {} tokens for boost in pool {} with enhanced compute efficiency", burn_amount, pool_id);
     
-    // 🛡️ OWASP SC02: Input validation (same security level)
-    if burn_amount == 0 {
-        msg!("🚨 Security Alert: Burn amount cannot be zero");
-        return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
-    }
-
-    if boost_multiplier > 50000 { // Max 5.0x boost
-        msg!("🚨 Security Alert: Boost multiplier too high (max 5.0x)");
-        return Err(ProgramError::Custom(GMCError::TransferFeeTooHigh as u32));
-    }
-    
-    // 🚀 OPTIMIZATION: Pre-compute constants to avoid runtime calculations
-    const USDT_FEE_FIXED: u64 = 800_000; // 0.8 USDT em microUSDT (precomputed)
-    const GMC_FEE_DIVISOR: u64 = 10; // 10% fee (precomputed)
-    
-    // 🚀 OPTIMIZATION: Use lookup table for boost calculations
-    let boost_level = (boost_multiplier / 100) as u8; // Convert to lookup index
-    let lookup_boost = if (boost_level as usize) < BURN_BOOST_LOOKUP.len() {
-        BURN_BOOST_LOOKUP[boost_level as usize]
-    } else {
-        boost_multiplier // Fallback to original value
-    };
-    
-    msg!("🚀 Using optimized boost calculation: {} → {} bps", boost_multiplier, lookup_boost);
-    
-    // 🚀 OPTIMIZATION: Fast arithmetic with saturating operations
-    let gmc_fee = burn_amount.saturating_div(GMC_FEE_DIVISOR);
-    let total_gmc_to_burn = burn_amount.saturating_add(gmc_fee);
-    
-    // 🚀 OPTIMIZATION: Batch account validation in one pass
-    if accounts.len() < 7 {
-        return Err(ProgramError::NotEnoughAccountKeys);
-    }
-    
-    let user_info = &accounts[0];
-    
-    // 🛡️ Security: Validate user is signer (same security level)
-    if !user_info.is_signer {
-        msg!("🚨 Security Alert: User must be signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-    
-    msg!("🚀 Optimized calculations:");
-    msg!("   • Principal Burn: {} GMC", burn_amount / 1_000_000_000);
-    msg!("   • GMC Fee (10%): {} GMC", gmc_fee / 1_000_000_000);
-    msg!("   • Total to Burn: {} GMC", total_gmc_to_burn / 1_000_000_000);
-    msg!("   • Optimized Boost: {} bps", lookup_boost);
-    
-    // 🚀 OPTIMIZATION: Use strategic cache for global statistics update
-    let current_slot = Clock::get()?.slot;
-    // Cache manager optimization removed - using standard processing
-    // Statistics update optimization removed - using standard processing
-    
-    // Continue with optimized transfer and state updates...
-    // For now, use original logic to maintain compatibility
-    let boost_factor = lookup_boost as f64 / 10000.0;
-    
-    msg!("✅ Burn-for-boost completed with optimized compute efficiency");
-    msg!("   • USDT Fee: ${:.2}", USDT_FEE_FIXED as f64 / 1_000_000.0);
-    msg!("   • GMC Burned: {} GMC", total_gmc_to_burn / 1_000_000_000);
-    msg!("   • Optimized Boost Applied: {}x", boost_factor);
-    
-    Ok(())
+    // Same validated path as the original implementation (single source of truth)
+    process_burn_for_boost_original(program_id, accounts, pool_id, burn_amount, boost_multiplier)
 }
 
+// 🔥 Fixed USDT fee charged per burn-for-boost
+pub const BURN_FOR_BOOST_USDT_FEE: u64 = 800_000; // 0.8 USDT em microUSDT
+
 // 🚀 Original function preserved for fallback
 pub fn process_burn_for_boost_original(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     pool_id: u8,
     burn_amount: u64,
-    boost_multiplier: u16,
+    _boost_multiplier: u16,
 ) -> ProgramResult {
     msg!("🔥 Burning {} tokens for boost in pool {}", burn_amount, pool_id);
     
@@ -1605,20 +2455,26 @@ pub fn process_burn_for_boost_original(
         return Err(ProgramError::Custom(GMCError::InvalidAmount as u32));
     }
     
-    if boost_multiplier > 50000 { // Max 5.0x boost
-        msg!("🚨 Security Alert: Boost multiplier too high (max 5.0x)");
-        return Err(ProgramError::Custom(GMCError::TransferFeeTooHigh as u32));
+    // 🛡️ Burn-for-boost is only available in long-term pools
+    if stake_type_for_pool(pool_id)? != "long-term" {
+        msg!("🚨 Burn-for-boost is only available for long-term stakes");
+        return Err(ProgramError::Custom(GMCError::OperationNotAllowed as u32));
     }
     
     // 🛡️ Account validation - get accounts in order
     let account_info_iter = &mut accounts.iter();
-    let user_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let user_gmc_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let user_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let stake_record_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let burn_address_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let team_usdt_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
-    let _token_program_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let user_info = next_account_info(account_info_iter)?;
+    let user_gmc_info = next_account_info(account_info_iter)?;
+    let user_usdt_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let stake_record_info = next_account_info(account_info_iter)?;
+    let pool_info = next_account_info(account_info_iter)?;
+    let global_state_info = next_account_info(account_info_iter)?;
+    let team_usdt_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let schedule_info = next_account_info(account_info_iter)?;
+    let reward_vault_info = next_account_info(account_info_iter)?;
+    let affiliate_accounts = account_info_iter.as_slice();
     
     // 🛡️ Security: Validate user is signer
     if !user_info.is_signer {
@@ -1626,102 +2482,109 @@ pub fn process_burn_for_boost_original(
         return Err(ProgramError::MissingRequiredSignature);
     }
     
-    // 💰 Step 1: Calculate and charge USDT fee (0.8 USDT fixed)
-    const USDT_FEE_FIXED: u64 = 800_000; // 0.8 USDT em microUSDT
-    msg!("💰 USDT Entry Fee: ${:.2} USDT", USDT_FEE_FIXED as f64 / 1_000_000.0);
-    
-    // TODO: Implementar transferência USDT via CPI
-    // invoke(
-    //     &spl_token::instruction::transfer(
-    //         token_program_info.key,
-    //         user_usdt_info.key,
-    //         team_usdt_info.key,  // Por simplicidade, enviando toda taxa para equipe
-    //         user_info.key,
-    //         &[],
-    //         USDT_FEE_FIXED,
-    //     )?,
-    //     &[
-    //         user_usdt_info.clone(),
-    //         team_usdt_info.clone(),
-    //         user_info.clone(),
-    //         _token_program_info.clone(),
-    //     ],
-    // )?;
-    
-    msg!("✅ USDT fee charged: ${:.2} USDT", USDT_FEE_FIXED as f64 / 1_000_000.0);
-    
-    // 🔥 Step 2: Calculate and burn GMC (burn_amount + 10% fee)
-    let gmc_fee = burn_amount
-        .checked_div(10)
-        .ok_or_else(|| {
-            msg!("🚨 Security Alert: GMC fee calculation error");
-            ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-        })?;
-    
-    let total_gmc_to_burn = burn_amount
-        .checked_add(gmc_fee)
-        .ok_or_else(|| {
-            msg!("🚨 Security Alert: Total GMC calculation overflow");
-            ProgramError::Custom(GMCError::ArithmeticOverflow as u32)
-        })?;
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    if pool_info.owner != program_id || stake_record_info.owner != program_id {
+        msg!("🚨 Security Alert: State account not owned by program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let (stake_record_key, _) = find_stake_record_address(program_id, pool_id, user_info.key);
+    if *stake_record_info.key != stake_record_key {
+        msg!("🚨 Security Alert: Invalid stake record address");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    let mut stake_record: StakeRecord = discriminator::load(program_id, stake_record_info)?;
+    let mut pool: StakingPool = discriminator::load(program_id, pool_info)?;
+    if !stake_record.is_active || stake_record.staker != *user_info.key || pool.pool_id != pool_id {
+        msg!("🚨 No active stake for this staker in pool {}", pool_id);
+        return Err(ProgramError::Custom(GMCError::InvalidPoolId as u32));
+    }
+    
+    // 🪙 Only the GMC mint configured in the global state can be burned
+    let mut global_state = crate::load_global_state(program_id, global_state_info)?;
+    let user_gmc = spl_token::state::Account::unpack(&user_gmc_info.data.borrow())?;
+    if *mint_info.key != global_state.gmc_mint || user_gmc.mint != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Burn must use the GMC mint");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    // 🏛️ USDT fee goes to the team wallet configured in the global state
+    let user_usdt = spl_token::state::Account::unpack(&user_usdt_info.data.borrow())?;
+    let team_usdt = spl_token::state::Account::unpack(&team_usdt_info.data.borrow())?;
+    if global_state.usdt_mint == Pubkey::default()
+        || user_usdt.mint != global_state.usdt_mint
+        || team_usdt.mint != global_state.usdt_mint
+    {
+        msg!("🚨 Security Alert: USDT fee accounts must use the configured USDT mint");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    if team_usdt.owner != global_state.ecosystem_wallets.team {
+        msg!("🚨 Security Alert: Fee destination is not the team wallet");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    let (reward_authority, _) = find_reward_vault_authority(program_id, pool_id);
+    let reward_vault = spl_token::state::Account::unpack(&reward_vault_info.data.borrow())?;
+    if reward_vault.owner != reward_authority || reward_vault.mint != global_state.gmc_mint {
+        msg!("🚨 Security Alert: Invalid reward vault");
+        return Err(ProgramError::Custom(GMCError::InvalidWallet as u32));
+    }
+    
+    // 🔥 Step 1: burn_amount + 10% GMC fee, down to the 12M supply floor
+    let gmc_fee = safe_div(burn_amount, 10)?;
+    let total_gmc_to_burn = safe_add(burn_amount, gmc_fee)?;
+    let burn = crate::apply_burn(&mut global_state, total_gmc_to_burn)?;
     
     msg!("🔥 GMC Burn Analysis:");
     msg!("   • Principal Burn: {} GMC", burn_amount / 1_000_000_000);
     msg!("   • GMC Fee (10%): {} GMC", gmc_fee / 1_000_000_000);
     msg!("   • Total to Burn: {} GMC", total_gmc_to_burn / 1_000_000_000);
     
-    // TODO: Implementar queima de GMC via CPI
-    // invoke(
-    //     &spl_token::instruction::burn(
-    //         token_program_info.key,
-    //         user_gmc_info.key,
-    //         burn_address_info.key,
-    //         user_info.key,
-    //         &[],
-    //         total_gmc_to_burn,
-    //     )?,
-    //     &[
-    //         user_gmc_info.clone(),
-    //         burn_address_info.clone(),
-    //         user_info.clone(),
-    //         _token_program_info.clone(),
-    //     ],
-    // )?;
-    
-    msg!("✅ Burned {} GMC tokens", total_gmc_to_burn / 1_000_000_000);
-    
-    // 📈 Step 3: Update user's burn boost multiplier
-    // TODO: Implementar atualização do StakeRecord
-    // let mut stake_record = StakeRecord::load(stake_record_info)?;
-    // 
-    // // Adicionar o boost ao multiplicador existente
-    // stake_record.burn_boost_multiplier = stake_record.burn_boost_multiplier
-    //     .saturating_add(boost_multiplier);
-    // 
-    // // Aplicar limite máximo de boost (5.0x = 50000 basis points)
-    // if stake_record.burn_boost_multiplier > 50000 {
-    //     stake_record.burn_boost_multiplier = 50000;
-    //     msg!("⚠️ Burn boost capped at maximum 5.0x");
-    // }
-    // 
-    // stake_record.save(stake_record_info)?;
+    // 💰 Step 2: Charge the fixed USDT fee
+    transfer_usdt_via_cpi(user_usdt_info, team_usdt_info, user_info, token_program_info, BURN_FOR_BOOST_USDT_FEE)?;
     
-    let new_multiplier = boost_multiplier; // Simplificado para demonstração
-    let boost_factor = new_multiplier as f64 / 10000.0;
+    // 🔥 Step 3: Burn from the staker's account (staker signed the transaction)
+    if burn.burned > 0 {
+        burn_tokens(token_program_info, user_gmc_info, mint_info, user_info, &[], burn.burned)?;
+    }
+    crate::store_global_state(program_id, global_state_info, &global_state)?;
+    
+    // 📈 Step 4: Cumulative burn and on-chain multiplier; only GMC actually burned
+    // counts (the 10% fee is burned last and never adds to the boost)
+    stake_record.total_burned = safe_add(stake_record.total_burned, burn.burned.min(burn_amount))?;
+    let new_multiplier = calculate_burn_boost_multiplier(stake_record.total_burned, stake_record.amount)?;
+    
+    // 🧮 Re-weight the record in the accumulator, keeping rewards accrued so far
+    let mut schedule = load_emission_schedule(program_id, schedule_info)?;
+    settle_pool_rewards(&mut pool, &mut schedule, Clock::get()?.unix_timestamp as u32)?;
+    
+    // 🚫 Past the floor the unburnable part funds staking rewards
+    if burn.rerouted > 0 {
+        transfer_tokens(token_program_info, user_gmc_info, reward_vault_info, user_info, burn.rerouted)?;
+        pool.reward_budget = safe_add(pool.reward_budget, burn.rerouted)?;
+        msg!("🚫 Burn floor reached: {} GMC sent to the reward vault", burn.rerouted / 1_000_000_000);
+    }
     
-    msg!("📈 Boost Update:");
-    msg!("   • Applied Boost: {}x", boost_factor);
-    msg!("   • User: {}", user_info.key);
-    msg!("   • Pool ID: {}", pool_id);
+    let affiliate_power = affiliate_power_from_accounts(program_id, user_info.key, affiliate_accounts)?;
+    let pending_rewards = pool.pending_rewards(&stake_record)?;
+    let new_shares = pool.reward_shares(stake_record.amount, new_multiplier, affiliate_power)?;
+    pool.total_reward_shares = safe_add(safe_sub(pool.total_reward_shares, stake_record.reward_shares)?, new_shares)?;
+    stake_record.reward_shares = new_shares;
+    stake_record.reward_debt = pool.reward_debt_for(new_shares)?.saturating_sub(pending_rewards as u128);
+    stake_record.burn_boost_multiplier = new_multiplier;
     
-    // 📊 Step 4: Update global burn statistics
-    // TODO: Implementar atualização de estatísticas globais
-    msg!("📊 Global burn statistics updated (+{} GMC)", total_gmc_to_burn / 1_000_000_000);
+    discriminator::store(program_id, stake_record_info, &stake_record)?;
+    discriminator::store(program_id, pool_info, &pool)?;
+    discriminator::store(program_id, schedule_info, &schedule)?;
     
     msg!("✅ Burn-for-boost completed successfully");
-    msg!("   • USDT Fee: ${:.2}", USDT_FEE_FIXED as f64 / 1_000_000.0);
-    msg!("   • GMC Burned: {} GMC", total_gmc_to_burn / 1_000_000_000);
-    msg!("   • Boost Applied: {}x", boost_factor);
+    msg!("   • USDT Fee: ${:.2}", BURN_FOR_BOOST_USDT_FEE as f64 / 1_000_000.0);
+    msg!("   • Cumulative Burned: {} GMC", stake_record.total_burned / 1_000_000_000);
+    msg!("   • Boost Multiplier: {:.2}x", new_multiplier as f64 / 10000.0);
     
     Ok(())
 }
@@ -1730,72 +2593,99 @@ pub fn process_burn_for_boost_original(
 // 🛠️ UTILITY FUNCTIONS FOR TOKEN OPERATIONS
 // ========================================
 
-/// ✅ Utility function to perform SPL Token transfer
-/// This would be used in real implementation for all token transfers
-#[allow(dead_code)]
-pub fn transfer_tokens(
-    _token_program: &AccountInfo,
-    _source: &AccountInfo,
-    _destination: &AccountInfo,
-    _authority: &AccountInfo,
+/// ✅ Utility function to perform SPL Token transfer signed by `authority`
+pub fn transfer_tokens<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
     amount: u64,
 ) -> ProgramResult {
     msg!("💸 Transferring {} tokens", amount);
     
-    // In real implementation:
-    // let transfer_instruction = spl_token::instruction::transfer(
-    //     token_program.key,
-    //     source.key,
-    //     destination.key,
-    //     authority.key,
-    //     &[],
-    //     amount,
-    // )?;
-    // 
-    // invoke(
-    //     &transfer_instruction,
-    //     &[
-    //         source.clone(),
-    //         destination.clone(),
-    //         authority.clone(),
-    //         token_program.clone(),
-    //     ],
-    // )?;
+    let transfer_instruction = spl_token::instruction::transfer(
+        token_program.key,
+        source.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    
+    invoke(
+        &transfer_instruction,
+        &[
+            source.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+    )?;
     
     msg!("✅ Transfer of {} tokens completed", amount);
     Ok(())
 }
 
-/// ✅ Utility function to burn tokens
-#[allow(dead_code)]
-pub fn burn_tokens(
-    _token_program: &AccountInfo,
-    _account: &AccountInfo,
-    _mint: &AccountInfo,
-    _authority: &AccountInfo,
+/// ✅ Utility function to perform SPL Token transfer signed by a program PDA
+pub fn transfer_tokens_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> ProgramResult {
+    let transfer_instruction = spl_token::instruction::transfer(
+        token_program.key,
+        source.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    
+    invoke_signed(
+        &transfer_instruction,
+        &[
+            source.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )
+}
+
+/// ✅ Utility function to burn tokens held by a program PDA
+pub fn burn_tokens<'a>(
+    token_program: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]], // empty when `authority` signed the transaction
     amount: u64,
 ) -> ProgramResult {
     msg!("🔥 Burning {} tokens", amount);
     
-    // In real implementation:
-    // let burn_instruction = spl_token::instruction::burn(
-    //     token_program.key,
-    //     account.key,
-    //     mint.key,
-    //     authority.key,
-    //     &[],
-    //     amount,
-    // )?;
-    // 
-    // invoke(
-    //     &burn_instruction,
-    //     &[
-    //         account.clone(),
-    //         mint.clone(),
-    //         authority.clone(),
-    //         token_program.clone(),
-    //     ],
-    // )?;
+    let burn_instruction = spl_token::instruction::burn(
+        token_program.key,
+        account.key,
+        mint.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    
+    invoke_signed(
+        &burn_instruction,
+        &[
+            account.clone(),
+            mint.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
     
     msg!("✅ Burned {} tokens successfully", amount);
     Ok(())
@@ -1872,7 +2762,11 @@ mod tests {
             minimum_stake: 1_000_000,
             maximum_stake: 1_000_000_000,
             is_active: true,
-            _padding: [0; 6],
+            acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
+        _padding: [0; 6],
         };
 
         let amount = 100_000_000; // 100 GMC
@@ -1899,7 +2793,11 @@ mod tests {
             minimum_stake: 1_000_000,
             maximum_stake: 100_000_000_000,
             is_active: true,
-            _padding: [0; 6],
+            acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
+        _padding: [0; 6],
         };
         
         // Act
@@ -1922,7 +2820,11 @@ mod tests {
             minimum_stake: 1_000_000,
             maximum_stake: 100_000_000_000,
             is_active: true,
-            _padding: [0; 6],
+            acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
+        _padding: [0; 6],
         };
         
         // Act
@@ -1945,13 +2847,20 @@ mod tests {
             minimum_stake: 1_000_000,
             maximum_stake: 100_000_000_000,
             is_active: true,
-            _padding: [0; 6],
+            acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
+        _padding: [0; 6],
         };
         
         let stake_record = StakeRecord {
             staker: Pubkey::new_unique(),
             amount: 1_000_000,
             total_claimed: 0,
+            reward_debt: 0,
+            reward_shares: 0,
+            total_burned: 0,
             staked_at: 0,
             last_claim_at: 0,
             burn_boost_multiplier: 15000, // 1.5x boost
@@ -1997,4 +2906,282 @@ mod tests {
             assert_eq!(pool.lock_duration_days, 30);
         }
     }
+    
+    #[test]
+    fn test_stake_accounts_layout_and_addresses() {
+        // 🛡️ Persisted layouts must match the declared account sizes
+        let pool = LONG_TERM_POOLS[0].clone();
+        assert_eq!(pool.try_to_vec().unwrap().len(), StakingPool::LEN);
+        
+        let record = StakeRecord {
+            staker: Pubkey::new_unique(),
+            amount: LONG_TERM_MIN_STAKE,
+            total_claimed: 0,
+            reward_debt: 0,
+            reward_shares: 0,
+            total_burned: 0,
+            staked_at: 1,
+            last_claim_at: 1,
+            burn_boost_multiplier: 10000,
+            pool_id: LONG_TERM_POOL_ID,
+            is_active: true,
+        };
+        assert_eq!(record.try_to_vec().unwrap().len(), StakeRecord::LEN);
+        
+        // Stake records are unique per (pool, staker)
+        let program_id = Pubkey::new_unique();
+        let staker = Pubkey::new_unique();
+        let (record_a, _) = find_stake_record_address(&program_id, 1, &staker);
+        let (record_b, _) = find_stake_record_address(&program_id, 4, &staker);
+        assert_ne!(record_a, record_b);
+        assert_eq!(record_a, find_stake_record_address(&program_id, 1, &staker).0);
+        assert_ne!(find_pool_vault_authority(&program_id, 1).0, find_pool_vault_authority(&program_id, 4).0);
+        
+        // Only predefined pools can be created
+        assert_eq!(find_predefined_pool(FLEXIBLE_POOL_ID).unwrap().lock_duration_days, 30);
+        assert!(find_predefined_pool(0).is_none());
+        assert!(find_predefined_pool(7).is_none());
+    }
+    
+    #[test]
+    fn test_reward_accumulator_is_bounded_by_budget() {
+        let mut pool = LONG_TERM_POOLS[0].clone(); // 10% APY
+        let mut alice = StakeRecord {
+            staker: Pubkey::new_unique(),
+            amount: 0,
+            total_claimed: 0,
+            reward_debt: 0,
+            reward_shares: 0,
+            total_burned: 0,
+            staked_at: 0,
+            last_claim_at: 0,
+            burn_boost_multiplier: 10000,
+            pool_id: pool.pool_id,
+            is_active: true,
+        };
+        let mut bob = alice.clone();
+        
+        // Shares: principal weighted by burn boost and affiliate-boosted APY
+        alice.reward_shares = pool.reward_shares(365_000_000_000, 10000, 0).unwrap();
+        bob.reward_shares = pool.reward_shares(365_000_000_000, 15000, 0).unwrap();
+        assert_eq!(alice.reward_shares, 365_000_000_000);
+        assert_eq!(bob.reward_shares, 547_500_000_000);
+        assert_eq!(pool.reward_shares(365_000_000_000, 10000, 10).unwrap(), 730_000_000_000); // 10% -> 20% APY
+        pool.total_reward_shares = alice.reward_shares + bob.reward_shares;
+        
+        // Without budget nothing is emitted
+        assert_eq!(pool.update_rewards(86400).unwrap(), 0);
+        assert_eq!(pool.pending_rewards(&alice).unwrap(), 0);
+        
+        // Funded: emission follows the APY target (0.1 GMC/day per 365 shares)
+        pool.reward_budget = 1_000_000_000_000;
+        assert_eq!(pool.update_rewards(2 * 86400).unwrap(), 250_000_000);
+        let (alice_pending, bob_pending) = (pool.pending_rewards(&alice).unwrap(), pool.pending_rewards(&bob).unwrap());
+        assert!(alice_pending + bob_pending <= 250_000_000); // rounding dust stays in the vault
+        assert!(100_000_000 - alice_pending <= 1 && 150_000_000 - bob_pending <= 1);
+        
+        // Settled records owe nothing until the accumulator moves again
+        alice.reward_debt = pool.reward_debt_for(alice.reward_shares).unwrap();
+        assert_eq!(pool.pending_rewards(&alice).unwrap(), 0);
+        
+        // Budget running low throttles emission: owed rewards never exceed funding
+        pool.reward_budget = 50_000_000;
+        assert_eq!(pool.update_rewards(12 * 86400).unwrap(), 50_000_000);
+        assert_eq!(pool.reward_budget, 0);
+        let owed = pool.pending_rewards(&alice).unwrap() + pool.pending_rewards(&bob).unwrap();
+        assert!(owed <= 150_000_000 + 50_000_000);
+    }
+    
+    #[test]
+    fn test_unstake_settlement_penalties() {
+        let principal = 10_000 * 1_000_000_000u64;
+        let rewards = 1_000_000_000u64;
+        
+        // Lock expired: everything returned
+        let settled = calculate_unstake_settlement(true, true, principal, rewards).unwrap();
+        assert_eq!(settled.principal_returned, principal);
+        assert_eq!(settled.rewards_returned, rewards);
+        assert_eq!(settled.principal_burned + settled.rewards_redistributed, 0);
+        
+        // Early long-term: 50% principal burned, 80% rewards redistributed
+        let settled = calculate_unstake_settlement(true, false, principal, rewards).unwrap();
+        assert_eq!(settled.principal_burned, principal / 2);
+        assert_eq!(settled.rewards_redistributed, 800_000_000);
+        assert_eq!(settled.rewards_returned, 200_000_000);
+        
+        // Early flexible: 2.5% principal burned, rewards untouched
+        let settled = calculate_unstake_settlement(false, false, principal, rewards).unwrap();
+        assert_eq!(settled.principal_burned, 250 * 1_000_000_000);
+        assert_eq!(settled.principal_returned, 9_750 * 1_000_000_000);
+        assert_eq!(settled.rewards_returned, rewards);
+    }
+    
+    #[test]
+    fn test_unstake_and_claim_pay_the_same_net_rewards() {
+        let principal = 10_000 * 1_000_000_000u64;
+        let rewards = 3_000_000_000u64;
+        
+        // Claim: 1% interest withdrawal fee on the pending rewards
+        let claimed = crate::calculate_interest_withdrawal_fee(rewards).unwrap();
+        
+        // Unstake after the lock: the returned rewards go through the same fee
+        let settled = calculate_unstake_settlement(true, true, principal, rewards).unwrap();
+        let unstaked = crate::calculate_interest_withdrawal_fee(settled.rewards_returned).unwrap();
+        assert_eq!(unstaked.net_amount, claimed.net_amount);
+        assert_eq!(unstaked.total_fee, claimed.total_fee);
+        assert_eq!(claimed.net_amount, rewards - rewards / 100);
+    }
+    
+    #[test]
+    fn test_emission_schedule_caps_and_runway() {
+        const GMC: u64 = 1_000_000_000;
+        let mut schedule = EmissionSchedule {
+            authority: Pubkey::new_unique(),
+            total_allocation: crate::STAKING_POOL_ALLOCATION,
+            total_emitted: 0,
+            initial_epoch_cap: 10_000_000 * GMC,
+            emitted_in_epoch: 0,
+            start_timestamp: 0,
+            epoch_duration: 365 * 86400,
+            current_epoch: 0,
+            decay_basis_points: 5000, // halving every year
+            is_initialized: true,
+        };
+        assert_eq!(schedule.try_to_vec().unwrap().len(), EmissionSchedule::LEN);
+        
+        // Halving curve: 10M, 5M, 2.5M...
+        assert_eq!(schedule.epoch_cap(0), 10_000_000 * GMC);
+        assert_eq!(schedule.epoch_cap(2), 2_500_000 * GMC);
+        
+        // Per-epoch cap is enforced and resets on the next epoch
+        schedule.record_emission(100, 9_000_000 * GMC).unwrap();
+        assert_eq!(schedule.available(200), 1_000_000 * GMC);
+        assert!(schedule.record_emission(200, 2_000_000 * GMC).is_err());
+        assert_eq!(schedule.available(365 * 86400), 5_000_000 * GMC);
+        
+        // Accumulator emission is throttled by the schedule
+        let mut pool = LONG_TERM_POOLS[0].clone();
+        pool.total_reward_shares = 1_000_000_000 * GMC;
+        pool.reward_budget = 70_000_000 * GMC;
+        let emitted = settle_pool_rewards(&mut pool, &mut schedule, 300).unwrap();
+        assert!(emitted <= 1_000_000 * GMC);
+        
+        // A halving curve never exhausts 70M; a flat 10M/year lasts 7 years
+        assert_eq!(schedule.runway(0).epochs_remaining, u32::MAX);
+        schedule.decay_basis_points = 0;
+        schedule.total_emitted = 0;
+        schedule.emitted_in_epoch = 0;
+        let runway = schedule.runway(0);
+        assert_eq!(runway.epochs_remaining, 7);
+        assert_eq!(runway.exhausted_at, 7 * 365 * 86400);
+        
+        // APY is limited to what the schedule sustains: 10M/year over 50M staked = 20%
+        assert_eq!(schedule.max_sustainable_apy(0, 50_000_000 * GMC), 2000);
+        
+        // Far epochs are computed in closed form: a 1% decay still leaves a cap after 1000 epochs
+        schedule.decay_basis_points = 100;
+        assert_eq!(schedule.epoch_cap(1), 9_900_000 * GMC);
+        assert!(schedule.epoch_cap(1_000) > 0);
+        assert_eq!(schedule.epoch_cap(u32::MAX), 0);
+    }
+    
+    #[test]
+    fn test_cumulative_burn_boost_preserves_accrued_rewards() {
+        const GMC: u64 = 1_000_000_000;
+        let mut pool = LONG_TERM_POOLS[0].clone();
+        let mut record = StakeRecord {
+            staker: Pubkey::new_unique(),
+            amount: 10_000 * GMC,
+            total_claimed: 0,
+            reward_debt: 0,
+            reward_shares: 0,
+            total_burned: 0,
+            staked_at: 0,
+            last_claim_at: 0,
+            burn_boost_multiplier: 10000,
+            pool_id: pool.pool_id,
+            is_active: true,
+        };
+        record.reward_shares = pool.reward_shares(record.amount, 10000, 0).unwrap();
+        pool.total_reward_shares = record.reward_shares;
+        pool.reward_budget = 1_000_000 * GMC;
+        pool.update_rewards(30 * 86400).unwrap();
+        let accrued = pool.pending_rewards(&record).unwrap();
+        
+        // Two burns of 10% each accumulate into a 20% burn -> 1.0 + 20 * 2.7% = 1.54x
+        record.total_burned += 1_000 * GMC;
+        assert_eq!(calculate_burn_boost_multiplier(record.total_burned, record.amount).unwrap(), 12700);
+        record.total_burned += 1_000 * GMC;
+        let multiplier = calculate_burn_boost_multiplier(record.total_burned, record.amount).unwrap();
+        assert_eq!(multiplier, 15400);
+        
+        // Re-weighting keeps what was already accrued
+        let new_shares = pool.reward_shares(record.amount, multiplier, 0).unwrap();
+        record.reward_debt = pool.reward_debt_for(new_shares).unwrap() - accrued as u128;
+        record.reward_shares = new_shares;
+        assert_eq!(pool.pending_rewards(&record).unwrap(), accrued);
+    }
+    
+    #[test]
+    fn test_referral_tree_levels_and_rejections() {
+        use crate::affiliate::AffiliateRecord;
+        const GMC: u64 = 1_000_000_000;
+        let entry = |affiliate_id: Pubkey, referrer: Pubkey, amount: u64, total_burned: u64| {
+            let affiliate = AffiliateRecord {
+                affiliate_id,
+                referrer: Some(referrer),
+                total_volume: 0,
+                total_commissions_earned: 0,
+                last_activity_timestamp: 0,
+                registration_timestamp: 0,
+                total_referrals: 0,
+                anti_sybil_score: 100,
+                current_level: 1,
+                is_active: true,
+            };
+            let stake = StakeRecord {
+                staker: affiliate_id,
+                amount,
+                total_claimed: 0,
+                reward_debt: 0,
+                reward_shares: 0,
+                total_burned,
+                staked_at: 0,
+                last_claim_at: 0,
+                burn_boost_multiplier: 10000,
+                pool_id: 1,
+                is_active: amount > 0,
+            };
+            (affiliate, stake)
+        };
+        
+        // Chain staker -> a -> b -> c -> d -> e -> f covers all six levels
+        let staker = Pubkey::new_unique();
+        let keys: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let mut entries = vec![entry(keys[0], staker, 20_000_000, 10_000_000)];
+        for i in 1..6 {
+            entries.push(entry(keys[i], keys[i - 1], 1_000 * GMC, 0));
+        }
+        let stakes = verify_referral_tree(&staker, &entries).unwrap();
+        assert_eq!(stakes.iter().map(|s| s.0).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(stakes[0], (1, 20_000_000, 50));
+        
+        // A seventh level is rejected
+        let mut too_deep = entries.clone();
+        too_deep.push(entry(keys[6], keys[5], GMC, 0));
+        assert!(verify_referral_tree(&staker, &too_deep).is_err());
+        
+        // Children listed before their referrer, duplicates and foreign stakes are rejected
+        let unordered = vec![entries[1].clone(), entries[0].clone()];
+        assert!(verify_referral_tree(&staker, &unordered).is_err());
+        let duplicated = vec![entries[0].clone(), entries[0].clone()];
+        assert!(verify_referral_tree(&staker, &duplicated).is_err());
+        let mut foreign = entries[0].clone();
+        foreign.1.staker = Pubkey::new_unique();
+        assert!(verify_referral_tree(&staker, &[foreign]).is_err());
+        
+        // An affiliate without stake links the tree but adds no boost
+        let inactive = vec![entry(keys[0], staker, 0, 0), entry(keys[1], keys[0], GMC, 0)];
+        assert_eq!(verify_referral_tree(&staker, &inactive).unwrap(), vec![(2, GMC, 0)]);
+    }
 }
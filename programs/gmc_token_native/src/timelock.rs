@@ -0,0 +1,440 @@
+// ⏳ GMC Token - Time-locked parameter changes
+// Critical parameters (transfer fee, ecosystem wallets, pool APYs, admin) only change
+// through a queued `TimeLockedOperation` stored in a PDA, executable after a public
+// delay enforced by `Clock`: 24h for rates, 48h for wallets and admin rotation.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{
+    create_time_locked_operation, discriminator, emit_event, load_global_state, staking, store_global_state,
+    validate_pubkey_not_default, EcosystemWallets, GMCError, GlobalState, TimeLockedOperation, CRITICAL_TIME_LOCK_HOURS, MAX_TRANSFER_FEE_BASIS_POINTS, MIN_TIME_LOCK_HOURS,
+};
+
+/// Seed of the queued operation PDAs (`["timelock", global_state, operation_id]`)
+pub const TIMELOCK_SEED: &[u8] = b"timelock";
+
+/// Longest `operation_type` label stored in a queued operation
+pub const MAX_OPERATION_TYPE_LEN: usize = 32;
+
+/// Parameter change carried by a queued operation
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub enum TimeLockedChange {
+    /// Transfer fee rate in basis points (max `MAX_TRANSFER_FEE_BASIS_POINTS`)
+    SetTransferFee { basis_points: u16 },
+    /// Replace every ecosystem wallet
+    SetEcosystemWallets { wallets: EcosystemWallets },
+    /// Base APY of a staking pool
+    SetPoolApy { pool_id: u8, apy_basis_points: u16 },
+    /// Rotate the GlobalState admin (the new admin co-signs the execution)
+    SetAdmin { new_admin: Pubkey },
+    /// USDT mint accepted for entry fees
+    SetUsdtMint { mint: Pubkey },
+    /// Reference price for entry fees, in micro-USDT per whole GMC
+    SetUsdtPrice { usdt_price_per_gmc: u64 },
+}
+
+impl TimeLockedChange {
+    /// Largest serialized change (`SetEcosystemWallets`)
+    pub const MAX_LEN: usize = 1 + 7 * 32;
+
+    pub fn operation_type(&self) -> &'static str {
+        match self {
+            TimeLockedChange::SetTransferFee { .. } => "set_transfer_fee",
+            TimeLockedChange::SetEcosystemWallets { .. } => "set_ecosystem_wallets",
+            TimeLockedChange::SetPoolApy { .. } => "set_pool_apy",
+            TimeLockedChange::SetAdmin { .. } => "set_admin",
+            TimeLockedChange::SetUsdtMint { .. } => "set_usdt_mint",
+            TimeLockedChange::SetUsdtPrice { .. } => "set_usdt_price",
+        }
+    }
+
+    /// Wallets, the fee mint and admin move funds or control; they get the critical delay
+    pub fn delay_hours(&self) -> u16 {
+        match self {
+            TimeLockedChange::SetEcosystemWallets { .. }
+            | TimeLockedChange::SetAdmin { .. }
+            | TimeLockedChange::SetUsdtMint { .. } => CRITICAL_TIME_LOCK_HOURS,
+            TimeLockedChange::SetTransferFee { .. }
+            | TimeLockedChange::SetPoolApy { .. }
+            | TimeLockedChange::SetUsdtPrice { .. } => MIN_TIME_LOCK_HOURS,
+        }
+    }
+
+    /// Reject values that could never be executed
+    pub fn validate(&self) -> Result<(), GMCError> {
+        match self {
+            TimeLockedChange::SetTransferFee { basis_points } => {
+                if *basis_points > MAX_TRANSFER_FEE_BASIS_POINTS {
+                    return Err(GMCError::TransferFeeTooHigh);
+                }
+            }
+            TimeLockedChange::SetEcosystemWallets { wallets } => {
+                for wallet in [
+                    &wallets.team,
+                    &wallets.treasury,
+                    &wallets.marketing,
+                    &wallets.airdrop,
+                    &wallets.presale,
+                    &wallets.staking_fund,
+                    &wallets.ranking_fund,
+                ] {
+                    validate_pubkey_not_default(wallet)?;
+                }
+            }
+            TimeLockedChange::SetPoolApy { pool_id, apy_basis_points } => {
+                if staking::find_predefined_pool(*pool_id).is_none() {
+                    return Err(GMCError::InvalidPoolId);
+                }
+                if *apy_basis_points == 0 || *apy_basis_points > 50000 {
+                    return Err(GMCError::InvalidInput);
+                }
+            }
+            TimeLockedChange::SetAdmin { new_admin } => validate_pubkey_not_default(new_admin)?,
+            TimeLockedChange::SetUsdtMint { mint } => validate_pubkey_not_default(mint)?,
+            TimeLockedChange::SetUsdtPrice { usdt_price_per_gmc } => {
+                if *usdt_price_per_gmc == 0 {
+                    return Err(GMCError::InvalidInput);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a change that only touches GlobalState; returns false for pool changes
+    pub fn apply_to_global_state(&self, global_state: &mut GlobalState) -> bool {
+        match self {
+            TimeLockedChange::SetTransferFee { basis_points } => global_state.transfer_fee_basis_points = *basis_points,
+            TimeLockedChange::SetEcosystemWallets { wallets } => global_state.ecosystem_wallets = wallets.clone(),
+            TimeLockedChange::SetAdmin { new_admin } => global_state.admin = *new_admin,
+            TimeLockedChange::SetUsdtMint { mint } => global_state.usdt_mint = *mint,
+            TimeLockedChange::SetUsdtPrice { usdt_price_per_gmc } => global_state.usdt_price_per_gmc = *usdt_price_per_gmc,
+            TimeLockedChange::SetPoolApy { .. } => return false,
+        }
+        true
+    }
+}
+
+/// Queued operation stored at `["timelock", global_state, operation_id]`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct TimeLockAccount {
+    pub is_initialized: bool,
+    pub operation_id: u64,
+    pub operation: TimeLockedOperation,
+    pub change: TimeLockedChange,
+    pub cancelled: bool,
+}
+
+impl TimeLockAccount {
+    pub const LEN: usize = 1 + 8 + (4 + MAX_OPERATION_TYPE_LEN + 2 + 8 + 8 + 1 + 32) + TimeLockedChange::MAX_LEN + 1;
+
+    pub fn is_pending(&self) -> bool {
+        self.is_initialized && !self.operation.executed && !self.cancelled
+    }
+}
+
+/// 📣 Event logged (as `sol_log_data`) for every state change of a queued operation
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct TimeLockEvent {
+    pub operation_id: u64,
+    pub operation_type: String,
+    pub execution_timestamp: u64,
+    pub authority: Pubkey,
+}
+
+fn log_event(name: &str, timelock: &TimeLockAccount, authority: &Pubkey) {
+    emit_event(name, &TimeLockEvent {
+        operation_id: timelock.operation_id,
+        operation_type: timelock.operation.operation_type.clone(),
+        execution_timestamp: timelock.operation.execution_timestamp,
+        authority: *authority,
+    });
+    msg!("📣 {}: operation {} ({}), executable at {}",
+         name, timelock.operation_id, timelock.operation.operation_type, timelock.operation.execution_timestamp);
+}
+
+/// Derive the address of a queued operation governing `global_state`
+pub fn find_timelock_address(program_id: &Pubkey, global_state: &Pubkey, operation_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TIMELOCK_SEED, global_state.as_ref(), &operation_id.to_le_bytes()], program_id)
+}
+
+fn load_admin(
+    program_id: &Pubkey,
+    global_state_info: &AccountInfo,
+    admin_info: &AccountInfo,
+) -> Result<GlobalState, ProgramError> {
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can manage time-locked changes");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    Ok(global_state)
+}
+
+fn load_pending(
+    program_id: &Pubkey,
+    global_state: &Pubkey,
+    timelock_info: &AccountInfo,
+    operation_id: u64,
+) -> Result<TimeLockAccount, ProgramError> {
+    if *timelock_info.key != find_timelock_address(program_id, global_state, operation_id).0 {
+        msg!("🚨 Security Alert: Invalid time-lock account");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let timelock: TimeLockAccount = discriminator::load(program_id, timelock_info)?;
+    if !timelock.is_pending() {
+        msg!("❌ Operation {} is not pending", operation_id);
+        return Err(GMCError::OperationNotAllowed.into());
+    }
+
+    Ok(timelock)
+}
+
+/// ⏳ Queue a parameter change
+///
+/// Accounts expected:
+/// 0. `[]` Global state PDA (`["global_state"]`)
+/// 1. `[writable, signer]` Admin (payer)
+/// 2. `[writable]` Time-lock PDA (`["timelock", global_state, operation_id]`)
+/// 3. `[]` System program
+pub fn process_queue(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    operation_id: u64,
+    change: TimeLockedChange,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let timelock_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    load_admin(program_id, global_state_info, admin_info)?;
+    change.validate()?;
+
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (timelock_key, bump) = find_timelock_address(program_id, global_state_info.key, operation_id);
+    if *timelock_info.key != timelock_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if timelock_info.owner == program_id && timelock_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut operation = create_time_locked_operation(change.operation_type(), change.delay_hours(), now as u64)?;
+    operation.authority = *admin_info.key;
+
+    crate::create_pda_account(
+        program_id,
+        admin_info,
+        timelock_info,
+        system_program_info,
+        discriminator::ACCOUNT_HEADER_LEN + TimeLockAccount::LEN,
+        &[TIMELOCK_SEED, global_state_info.key.as_ref(), &operation_id.to_le_bytes(), &[bump]],
+    )?;
+
+    let timelock = TimeLockAccount {
+        is_initialized: true,
+        operation_id,
+        operation,
+        change,
+        cancelled: false,
+    };
+    discriminator::store(program_id, timelock_info, &timelock)?;
+
+    log_event("TimeLockQueued", &timelock, admin_info.key);
+    Ok(())
+}
+
+/// ✅ Execute a queued change once its delay has elapsed
+///
+/// Accounts expected:
+/// 0. `[writable]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Time-lock PDA
+/// 3. `[writable]` Staking pool PDA (`["staking_pool", pool_id]`, `SetPoolApy` only)
+/// 4. `[writable]` Emission schedule PDA (`SetPoolApy` only)
+/// 3. `[signer]` New admin (`SetAdmin` only)
+pub fn process_execute(program_id: &Pubkey, accounts: &[AccountInfo], operation_id: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let timelock_info = next_account_info(account_info_iter)?;
+
+    let mut global_state = load_admin(program_id, global_state_info, admin_info)?;
+    let mut timelock = load_pending(program_id, global_state_info.key, timelock_info, operation_id)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if !timelock.operation.can_execute(now as u64) {
+        msg!("⏳ Operation {} executable at {}", operation_id, timelock.operation.execution_timestamp);
+        return Err(GMCError::InsufficientTimelock.into());
+    }
+
+    // 🔑 Admin rotation stays two-step: the new key must accept by signing
+    if let TimeLockedChange::SetAdmin { new_admin } = timelock.change {
+        let new_admin_info = next_account_info(account_info_iter)?;
+        if *new_admin_info.key != new_admin || !new_admin_info.is_signer {
+            msg!("🚨 Security Alert: The new admin must sign the rotation");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    if timelock.change.apply_to_global_state(&mut global_state) {
+        store_global_state(program_id, global_state_info, &global_state)?;
+    } else if let TimeLockedChange::SetPoolApy { pool_id, apy_basis_points } = timelock.change {
+        let pool_info = next_account_info(account_info_iter)?;
+        let schedule_info = next_account_info(account_info_iter)?;
+
+        if *pool_info.key != staking::find_pool_address(program_id, pool_id).0 {
+            msg!("🚨 Security Alert: Invalid pool address");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let mut pool: staking::StakingPool = discriminator::load(program_id, pool_info)?;
+        if pool.pool_id != pool_id {
+            return Err(GMCError::InvalidPoolId.into());
+        }
+
+        // 🧮 Rewards accrued so far are settled at the old APY
+        let mut schedule = staking::load_emission_schedule(program_id, schedule_info)?;
+        staking::settle_pool_rewards(&mut pool, &mut schedule, now as u32)?;
+        pool.apy_basis_points = apy_basis_points;
+
+        discriminator::store(program_id, pool_info, &pool)?;
+        discriminator::store(program_id, schedule_info, &schedule)?;
+    }
+
+    timelock.operation.executed = true;
+    discriminator::store(program_id, timelock_info, &timelock)?;
+
+    log_event("TimeLockExecuted", &timelock, admin_info.key);
+    Ok(())
+}
+
+/// 🚫 Cancel a queued change
+///
+/// Accounts expected:
+/// 0. `[]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[writable]` Time-lock PDA
+pub fn process_cancel(program_id: &Pubkey, accounts: &[AccountInfo], operation_id: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+    let timelock_info = next_account_info(account_info_iter)?;
+
+    load_admin(program_id, global_state_info, admin_info)?;
+    let mut timelock = load_pending(program_id, global_state_info.key, timelock_info, operation_id)?;
+
+    timelock.cancelled = true;
+    discriminator::store(program_id, timelock_info, &timelock)?;
+
+    log_event("TimeLockCancelled", &timelock, admin_info.key);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{token_mode::TokenMode, GMC_TOTAL_SUPPLY, TRANSFER_FEE_BASIS_POINTS};
+
+    fn wallets() -> EcosystemWallets {
+        EcosystemWallets {
+            team: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            marketing: Pubkey::new_unique(),
+            airdrop: Pubkey::new_unique(),
+            presale: Pubkey::new_unique(),
+            staking_fund: Pubkey::new_unique(),
+            ranking_fund: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_changes_are_validated_and_delayed_by_criticality() {
+        let fee = TimeLockedChange::SetTransferFee { basis_points: 80 };
+        assert!(fee.validate().is_ok());
+        assert_eq!(fee.delay_hours(), MIN_TIME_LOCK_HOURS);
+        assert_eq!(
+            TimeLockedChange::SetTransferFee { basis_points: MAX_TRANSFER_FEE_BASIS_POINTS + 1 }.validate(),
+            Err(GMCError::TransferFeeTooHigh)
+        );
+
+        let admin = TimeLockedChange::SetAdmin { new_admin: Pubkey::new_unique() };
+        assert_eq!(admin.delay_hours(), CRITICAL_TIME_LOCK_HOURS);
+        assert_eq!(
+            TimeLockedChange::SetAdmin { new_admin: Pubkey::default() }.validate(),
+            Err(GMCError::DefaultPubkeyNotAllowed)
+        );
+
+        let mut unset = wallets();
+        unset.ranking_fund = Pubkey::default();
+        assert!(TimeLockedChange::SetEcosystemWallets { wallets: unset }.validate().is_err());
+        assert!(TimeLockedChange::SetPoolApy { pool_id: 9, apy_basis_points: 1000 }.validate().is_err());
+        assert!(TimeLockedChange::SetPoolApy { pool_id: staking::LONG_TERM_POOL_ID, apy_basis_points: 1000 }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_queued_operation_fits_account_and_applies_after_delay() {
+        let now = 1_700_000_000u64;
+        let change = TimeLockedChange::SetEcosystemWallets { wallets: wallets() };
+        let operation = create_time_locked_operation(change.operation_type(), change.delay_hours(), now).unwrap();
+        let mut timelock = TimeLockAccount { is_initialized: true, operation_id: 7, operation, change, cancelled: false };
+
+        assert!(timelock.try_to_vec().unwrap().len() <= TimeLockAccount::LEN);
+        assert!(!timelock.operation.can_execute(now + 47 * 3600));
+        assert!(timelock.operation.can_execute(now + 48 * 3600));
+
+        let mut state = GlobalState {
+            total_supply: GMC_TOTAL_SUPPLY,
+            circulating_supply: GMC_TOTAL_SUPPLY,
+            burned_supply: 0,
+            admin: Pubkey::new_unique(),
+            ecosystem_wallets: wallets(),
+            is_initialized: true,
+            burn_stopped: false,
+            mint_authority_revoked: false,
+            token_mode: TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
+        };
+        assert!(timelock.change.apply_to_global_state(&mut state));
+        if let TimeLockedChange::SetEcosystemWallets { wallets } = &timelock.change {
+            assert_eq!(state.ecosystem_wallets.treasury, wallets.treasury);
+        }
+        assert!(TimeLockedChange::SetTransferFee { basis_points: 80 }.apply_to_global_state(&mut state));
+        assert_eq!(state.transfer_fee_basis_points, 80);
+        let usdt_mint = Pubkey::new_unique();
+        assert!(TimeLockedChange::SetUsdtMint { mint: usdt_mint }.apply_to_global_state(&mut state));
+        assert_eq!(state.usdt_mint, usdt_mint);
+        assert!(TimeLockedChange::SetUsdtPrice { usdt_price_per_gmc: 0 }.validate().is_err());
+        assert!(TimeLockedChange::SetUsdtPrice { usdt_price_per_gmc: 250_000 }.apply_to_global_state(&mut state));
+        assert_eq!(state.usdt_price_per_gmc, 250_000);
+        assert!(!TimeLockedChange::SetPoolApy { pool_id: 1, apy_basis_points: 1 }.apply_to_global_state(&mut state));
+
+        // Executed or cancelled operations are no longer pending
+        timelock.operation.executed = true;
+        assert!(!timelock.is_pending());
+    }
+}
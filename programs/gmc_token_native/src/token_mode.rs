@@ -0,0 +1,546 @@
+// 🪙 GMC Token - SPL Token / Token-2022 backed transfer mode
+// The custom `TokenAccount` ledger stays the default; once the admin switches
+// the mode, the transfer-with-fee path moves balances of the real GMC mint.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey,
+    pubkey::Pubkey,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{
+    apply_fee_distribution, apply_transfer_fee, load_global_state, split_collected_fee, store_global_state, GMCError,
+    GlobalState, TransferFeeDistribution,
+};
+
+/// Token-2022 program (not a dependency of this crate, instructions are encoded below)
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Mainnet GMC mint
+pub const GMC_MAINNET_MINT: Pubkey = pubkey!("AUz16jzWuxSpZ3aKANmpocxWUUG6w5qDd1hiGnoUFMXb");
+
+/// Seed of the PDA that is the Token-2022 withdraw-withheld authority and owns the fee vault
+pub const FEE_AUTHORITY_SEED: &[u8] = b"fee_authority";
+
+// 🔢 Token instruction tags shared by SPL Token and Token-2022
+const TRANSFER_CHECKED_TAG: u8 = 12;
+const BURN_CHECKED_TAG: u8 = 15;
+const TRANSFER_FEE_EXTENSION_TAG: u8 = 26;
+const WITHDRAW_WITHHELD_TOKENS_FROM_MINT_TAG: u8 = 2;
+const HARVEST_WITHHELD_TOKENS_TO_MINT_TAG: u8 = 4;
+
+// 🧩 Token-2022 mint layout: base mint padded to the account size, account type, then TLV extensions
+const EXTENSIONS_OFFSET: usize = spl_token::state::Account::LEN + 1;
+const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+const TRANSFER_FEE_CONFIG_LEN: usize = 32 + 32 + 8 + 18 + 18;
+
+/// Which ledger backs the GMC transfer path
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenMode {
+    /// Custom `TokenAccount` ledger owned by this program
+    #[default]
+    Native,
+    /// SPL Token mint; the program splits and burns the 0.5% fee itself
+    SplToken,
+    /// Token-2022 mint with TransferFeeConfig; fees are withheld by the mint
+    /// and swept by `HarvestAndDistribute`
+    Token2022,
+}
+
+impl TokenMode {
+    /// Token program that owns the GMC mint in this mode
+    pub fn token_program_id(&self) -> Option<Pubkey> {
+        match self {
+            TokenMode::Native => None,
+            TokenMode::SplToken => Some(spl_token::id()),
+            TokenMode::Token2022 => Some(TOKEN_2022_PROGRAM_ID),
+        }
+    }
+}
+
+/// Derive the fee authority PDA
+pub fn find_fee_authority(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_AUTHORITY_SEED], program_id)
+}
+
+/// Read (withdraw_withheld_authority, newer transfer fee basis points) from a Token-2022 mint
+pub fn transfer_fee_config(mint_data: &[u8]) -> Option<(Pubkey, u16)> {
+    let mut offset = EXTENSIONS_OFFSET;
+    while offset + 4 <= mint_data.len() {
+        let extension_type = u16::from_le_bytes([mint_data[offset], mint_data[offset + 1]]);
+        let length = u16::from_le_bytes([mint_data[offset + 2], mint_data[offset + 3]]) as usize;
+        let value = mint_data.get(offset + 4..offset + 4 + length)?;
+
+        if extension_type == TRANSFER_FEE_CONFIG_EXTENSION && length == TRANSFER_FEE_CONFIG_LEN {
+            let withdraw_authority = Pubkey::new_from_array(value[32..64].try_into().ok()?);
+            // newer_transfer_fee = (epoch u64, maximum_fee u64, basis_points u16) at offset 90
+            let basis_points = u16::from_le_bytes([value[106], value[107]]);
+            return Some((withdraw_authority, basis_points));
+        }
+
+        offset += 4 + length;
+    }
+
+    None
+}
+
+pub(crate) fn mint_decimals(mint_info: &AccountInfo) -> Result<u8, ProgramError> {
+    let data = mint_info.data.borrow();
+    let base = data.get(..spl_token::state::Mint::LEN).ok_or(ProgramError::InvalidAccountData)?;
+    let mint = spl_token::state::Mint::unpack_from_slice(base)?;
+    if !mint.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(mint.decimals)
+}
+
+pub(crate) fn token_account_state(info: &AccountInfo) -> Result<spl_token::state::Account, ProgramError> {
+    let data = info.data.borrow();
+    let base = data.get(..spl_token::state::Account::LEN).ok_or(ProgramError::InvalidAccountData)?;
+    spl_token::state::Account::unpack_from_slice(base)
+}
+
+// 🔧 Instruction builders valid for both token programs
+
+fn checked_amount_data(tag: u8, amount: u64, decimals: u8) -> Vec<u8> {
+    let mut data = Vec::with_capacity(10);
+    data.push(tag);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    data
+}
+
+pub fn transfer_checked_instruction(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: checked_amount_data(TRANSFER_CHECKED_TAG, amount, decimals),
+    }
+}
+
+pub fn burn_checked_instruction(
+    token_program: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: checked_amount_data(BURN_CHECKED_TAG, amount, decimals),
+    }
+}
+
+pub fn harvest_withheld_tokens_to_mint_instruction(mint: &Pubkey, sources: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*mint, false)];
+    accounts.extend(sources.iter().map(|source| AccountMeta::new(*source, false)));
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts,
+        data: vec![TRANSFER_FEE_EXTENSION_TAG, HARVEST_WITHHELD_TOKENS_TO_MINT_TAG],
+    }
+}
+
+pub fn withdraw_withheld_tokens_from_mint_instruction(
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: vec![TRANSFER_FEE_EXTENSION_TAG, WITHDRAW_WITHHELD_TOKENS_FROM_MINT_TAG],
+    }
+}
+
+/// Accounts of a transfer in SPL-backed mode (same order as the native path, plus mint and token program)
+pub struct SplTransferAccounts<'a, 'info> {
+    pub from: &'a AccountInfo<'info>,
+    pub to: &'a AccountInfo<'info>,
+    pub authority: &'a AccountInfo<'info>,
+    pub staking_fund: &'a AccountInfo<'info>,
+    pub ranking_fund: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+pub(crate) fn validate_mint_and_program(
+    global_state: &GlobalState,
+    mint: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<Pubkey, ProgramError> {
+    let token_program_id = global_state.token_mode.token_program_id()
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if *token_program.key != token_program_id || *mint.owner != token_program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if *mint.key != global_state.gmc_mint {
+        msg!("❌ Mint is not the configured GMC mint");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(token_program_id)
+}
+
+/// 📤 Transfer with fee on SPL token accounts. The token program enforces the
+/// owner/delegate of the source, so `Transfer` and `TransferFrom` share this path.
+/// `fee_exempt` waives the program fee; a Token-2022 mint still withholds its own.
+pub fn spl_transfer_with_fee<'info>(
+    accounts: &SplTransferAccounts<'_, 'info>,
+    global_state: &mut GlobalState,
+    amount: u64,
+    fee_exempt: bool,
+) -> ProgramResult {
+    let token_program_id = validate_mint_and_program(global_state, accounts.mint, accounts.token_program)?;
+    let decimals = mint_decimals(accounts.mint)?;
+
+    let transfer = |destination: &AccountInfo<'info>, amount: u64| -> ProgramResult {
+        if amount == 0 {
+            return Ok(());
+        }
+        invoke(
+            &transfer_checked_instruction(
+                &token_program_id,
+                accounts.from.key,
+                accounts.mint.key,
+                destination.key,
+                accounts.authority.key,
+                amount,
+                decimals,
+            ),
+            &[
+                accounts.from.clone(),
+                accounts.mint.clone(),
+                destination.clone(),
+                accounts.authority.clone(),
+                accounts.token_program.clone(),
+            ],
+        )
+    };
+
+    if global_state.token_mode == TokenMode::Token2022 {
+        // 🧾 The mint withholds the fee at the destination; HarvestAndDistribute sweeps it
+        transfer(accounts.to, amount)?;
+        msg!("✅ Token-2022 transfer: {} GMC (fee withheld by the mint)", amount);
+        return Ok(());
+    }
+
+    let fee_distribution = if fee_exempt {
+        TransferFeeDistribution::default()
+    } else {
+        apply_transfer_fee(global_state, amount)?
+    };
+    let net_amount = amount
+        .checked_sub(fee_distribution.total_fee)
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    // Rounding dust of the fee goes to the staking fund
+    let staking_credit = fee_distribution.total_fee
+        .checked_sub(fee_distribution.burn_amount)
+        .and_then(|x| x.checked_sub(fee_distribution.ranking_amount))
+        .ok_or(GMCError::ArithmeticOverflow)?;
+
+    transfer(accounts.to, net_amount)?;
+    transfer(accounts.staking_fund, staking_credit)?;
+    transfer(accounts.ranking_fund, fee_distribution.ranking_amount)?;
+
+    if fee_distribution.burn_amount > 0 {
+        invoke(
+            &burn_checked_instruction(
+                &token_program_id,
+                accounts.from.key,
+                accounts.mint.key,
+                accounts.authority.key,
+                fee_distribution.burn_amount,
+                decimals,
+            ),
+            &[
+                accounts.from.clone(),
+                accounts.mint.clone(),
+                accounts.authority.clone(),
+                accounts.token_program.clone(),
+            ],
+        )?;
+    }
+
+    msg!("✅ SPL transfer: {} GMC net, {} GMC fee", net_amount, fee_distribution.total_fee);
+    Ok(())
+}
+
+/// ⚙️ Switch the ledger backing the transfer path (admin only)
+///
+/// Accounts expected:
+/// 0. `[writable]` Global state PDA (`["global_state"]`)
+/// 1. `[signer]` Admin
+/// 2. `[]` GMC mint (not needed for `Native`)
+pub fn process_set_token_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mode: TokenMode,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+
+    if !admin_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.admin != *admin_info.key {
+        msg!("🚨 Security Alert: Only the admin can change the token mode");
+        return Err(GMCError::AccessDenied.into());
+    }
+
+    global_state.gmc_mint = match mode.token_program_id() {
+        None => Pubkey::default(),
+        Some(token_program_id) => {
+            let mint_info = next_account_info(account_info_iter)?;
+            if *mint_info.owner != token_program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            mint_decimals(mint_info)?;
+
+            // 🛡️ Token-2022 fees must match the configured rate and be withdrawable only by this program
+            if mode == TokenMode::Token2022 {
+                let (withdraw_authority, basis_points) = transfer_fee_config(&mint_info.data.borrow())
+                    .ok_or(ProgramError::InvalidAccountData)?;
+                if withdraw_authority != find_fee_authority(program_id).0 || basis_points != global_state.transfer_fee_basis_points {
+                    msg!("❌ Mint TransferFeeConfig does not match the GMC fee rules");
+                    return Err(ProgramError::InvalidAccountData);
+                }
+            }
+
+            *mint_info.key
+        }
+    };
+    global_state.token_mode = mode;
+
+    store_global_state(program_id, global_state_info, &global_state)?;
+
+    msg!("🪙 Token mode set to {:?} (mint {})", mode, global_state.gmc_mint);
+    Ok(())
+}
+
+/// 🧾 Sweep Token-2022 withheld fees and split them 50% burn / 40% staking / 10% ranking
+///
+/// Accounts expected:
+/// 0. `[writable]` Global state PDA (`["global_state"]`)
+/// 1. `[writable]` GMC mint
+/// 2. `[writable]` Fee vault (token account owned by the fee authority PDA)
+/// 3. `[]` Fee authority PDA (`["fee_authority"]`)
+/// 4. `[writable]` Staking fund token account (`ecosystem_wallets.staking_fund`)
+/// 5. `[writable]` Ranking fund token account (`ecosystem_wallets.ranking_fund`)
+/// 6. `[]` Token-2022 program
+/// 7. `[writable]` Token accounts to harvest withheld fees from (any number)
+pub fn process_harvest_and_distribute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let global_state_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let fee_vault_info = next_account_info(account_info_iter)?;
+    let fee_authority_info = next_account_info(account_info_iter)?;
+    let staking_fund_info = next_account_info(account_info_iter)?;
+    let ranking_fund_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let source_infos = account_info_iter.as_slice();
+
+    let mut global_state = load_global_state(program_id, global_state_info)?;
+    if global_state.token_mode != TokenMode::Token2022 {
+        msg!("❌ HarvestAndDistribute requires the Token-2022 mode");
+        return Err(GMCError::OperationNotAllowed.into());
+    }
+    validate_mint_and_program(&global_state, mint_info, token_program_info)?;
+
+    let (fee_authority, fee_authority_bump) = find_fee_authority(program_id);
+    if *fee_authority_info.key != fee_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if *staking_fund_info.key != global_state.ecosystem_wallets.staking_fund
+        || *ranking_fund_info.key != global_state.ecosystem_wallets.ranking_fund
+    {
+        return Err(GMCError::InvalidWallet.into());
+    }
+
+    if *fee_vault_info.owner != TOKEN_2022_PROGRAM_ID || token_account_state(fee_vault_info)?.owner != fee_authority {
+        msg!("❌ Fee vault must be owned by the fee authority PDA");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // 🧲 Step 1: harvest withheld fees from the given accounts into the mint (permissionless)
+    if !source_infos.is_empty() {
+        let sources: Vec<Pubkey> = source_infos.iter().map(|info| *info.key).collect();
+        let mut harvest_accounts = vec![mint_info.clone()];
+        harvest_accounts.extend(source_infos.iter().cloned());
+        harvest_accounts.push(token_program_info.clone());
+        invoke(&harvest_withheld_tokens_to_mint_instruction(mint_info.key, &sources), &harvest_accounts)?;
+    }
+
+    // 🏦 Step 2: withdraw everything withheld in the mint into the fee vault
+    let signer_seeds: &[&[u8]] = &[FEE_AUTHORITY_SEED, &[fee_authority_bump]];
+    invoke_signed(
+        &withdraw_withheld_tokens_from_mint_instruction(mint_info.key, fee_vault_info.key, &fee_authority),
+        &[
+            mint_info.clone(),
+            fee_vault_info.clone(),
+            fee_authority_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    let collected = token_account_state(fee_vault_info)?.amount;
+    if collected == 0 {
+        msg!("🧾 No withheld fees to distribute");
+        return Ok(());
+    }
+
+    // 🔥 Step 3: split the vault balance; the burn share respects the 12M floor
+    let fee_distribution = apply_fee_distribution(&mut global_state, split_collected_fee(collected)?)?;
+    let decimals = mint_decimals(mint_info)?;
+
+    for (destination, amount) in [
+        (staking_fund_info, fee_distribution.staking_amount),
+        (ranking_fund_info, fee_distribution.ranking_amount),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        invoke_signed(
+            &transfer_checked_instruction(
+                &TOKEN_2022_PROGRAM_ID,
+                fee_vault_info.key,
+                mint_info.key,
+                destination.key,
+                &fee_authority,
+                amount,
+                decimals,
+            ),
+            &[
+                fee_vault_info.clone(),
+                mint_info.clone(),
+                destination.clone(),
+                fee_authority_info.clone(),
+                token_program_info.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+    }
+
+    if fee_distribution.burn_amount > 0 {
+        invoke_signed(
+            &burn_checked_instruction(
+                &TOKEN_2022_PROGRAM_ID,
+                fee_vault_info.key,
+                mint_info.key,
+                &fee_authority,
+                fee_distribution.burn_amount,
+                decimals,
+            ),
+            &[
+                fee_vault_info.clone(),
+                mint_info.clone(),
+                fee_authority_info.clone(),
+                token_program_info.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+    }
+
+    store_global_state(program_id, global_state_info, &global_state)?;
+
+    msg!("🧾 Distributed {} GMC of withheld fees: {} burned, {} staking, {} ranking",
+         collected, fee_distribution.burn_amount, fee_distribution.staking_amount, fee_distribution.ranking_amount);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TRANSFER_FEE_BASIS_POINTS;
+
+    fn token_2022_mint_data(withdraw_authority: &Pubkey, basis_points: u16) -> Vec<u8> {
+        let mut data = vec![0u8; EXTENSIONS_OFFSET];
+        data[EXTENSIONS_OFFSET - 1] = 1; // AccountType::Mint
+
+        // An unrelated extension first, then TransferFeeConfig
+        data.extend_from_slice(&3u16.to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.extend_from_slice(&[7u8; 32]);
+
+        let mut config = vec![0u8; TRANSFER_FEE_CONFIG_LEN];
+        config[32..64].copy_from_slice(withdraw_authority.as_ref());
+        config[106..108].copy_from_slice(&basis_points.to_le_bytes());
+        data.extend_from_slice(&TRANSFER_FEE_CONFIG_EXTENSION.to_le_bytes());
+        data.extend_from_slice(&(TRANSFER_FEE_CONFIG_LEN as u16).to_le_bytes());
+        data.extend_from_slice(&config);
+        data
+    }
+
+    #[test]
+    fn test_transfer_fee_config_is_read_from_mint_extensions() {
+        let authority = Pubkey::new_unique();
+        let data = token_2022_mint_data(&authority, TRANSFER_FEE_BASIS_POINTS);
+        assert_eq!(transfer_fee_config(&data), Some((authority, TRANSFER_FEE_BASIS_POINTS)));
+
+        // Plain SPL mints and truncated data have no fee config
+        assert_eq!(transfer_fee_config(&[0u8; 82]), None);
+        assert_eq!(transfer_fee_config(&data[..data.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_token_instruction_encoding() {
+        let (a, b, c, d) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let transfer = transfer_checked_instruction(&spl_token::id(), &a, &b, &c, &d, 1_000, 9);
+        let expected = spl_token::instruction::transfer_checked(&spl_token::id(), &a, &b, &c, &d, &[], 1_000, 9).unwrap();
+        assert_eq!(transfer, expected);
+
+        let burn = burn_checked_instruction(&spl_token::id(), &a, &b, &c, 500, 9);
+        let expected = spl_token::instruction::burn_checked(&spl_token::id(), &a, &b, &c, &[], 500, 9).unwrap();
+        assert_eq!(burn, expected);
+
+        assert_eq!(harvest_withheld_tokens_to_mint_instruction(&a, &[b, c]).data, vec![26, 4]);
+        assert_eq!(withdraw_withheld_tokens_from_mint_instruction(&a, &b, &c).data, vec![26, 2]);
+    }
+
+    #[test]
+    fn test_collected_fee_split_is_exact() {
+        let split = split_collected_fee(1_001).unwrap();
+        assert_eq!((split.burn_amount, split.staking_amount, split.ranking_amount), (500, 400, 101));
+        assert_eq!(split.burn_amount + split.staking_amount + split.ranking_amount, split.total_fee);
+    }
+}
@@ -8,7 +8,7 @@ mod transfer_fee_tests {
     use solana_program::pubkey::Pubkey;
     use crate::{
         calculate_transfer_fee, apply_transfer_fee, GlobalState, EcosystemWallets,
-        GMC_TOTAL_SUPPLY, GMC_MINIMUM_SUPPLY, TransferFeeDistribution
+        GMC_TOTAL_SUPPLY, GMC_MINIMUM_SUPPLY, TransferFeeDistribution, TRANSFER_FEE_BASIS_POINTS
     };
 
     // 🔴 RED: Teste falha primeiro (função não existe)
@@ -79,6 +79,12 @@ mod transfer_fee_tests {
             is_initialized: true,
             burn_stopped: false,
             mint_authority_revoked: false,
+            token_mode: crate::token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
         };
         
         let transfer_amount = 2_000_000_000_000_000; // 2M GMC
@@ -169,6 +175,12 @@ mod transfer_fee_tests {
             is_initialized: true,
             burn_stopped: false,
             mint_authority_revoked: false,
+            token_mode: crate::token_mode::TokenMode::Native,
+            gmc_mint: Pubkey::default(),
+            genesis_distributed: false,
+            transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+            usdt_mint: Pubkey::default(),
+            usdt_price_per_gmc: 0,
         }
     }
 }
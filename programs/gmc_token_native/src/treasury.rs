@@ -8,11 +8,16 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
-    sysvar::{clock::Clock, Sysvar},
+    system_program,
+    sysvar::{self, clock::Clock, Sysvar},
 };
-use crate::GMCError;
+use crate::cpi_batch_optimization::{BatchTransfer, OptimizedBatchProcessor};
+use crate::{discriminator, GMCError};
 
 /// 🏛️ Constantes do Treasury
 pub const MAX_SIGNERS: usize = 10;
@@ -22,6 +27,20 @@ pub const REQUIRED_SIGNATURES: usize = 3; // 3-of-N multisig
 pub const MAX_PENDING_TRANSACTIONS: usize = 50;
 pub const TRANSACTION_EXPIRY_SECONDS: i64 = 7 * 24 * 3600; // 7 dias
 
+/// 🔑 Seeds dos cofres (PDA) do Treasury
+pub const TREASURY_VAULT_SEED: &[u8] = b"treasury_vault";
+pub const TREASURY_VAULT_AUTHORITY_SEED: &[u8] = b"treasury_vault_authority";
+pub const TREASURY_AUTHORITY_SEED: &[u8] = b"treasury_authority";
+/// 🔑 Seed das propostas (PDA) de um Treasury
+pub const TREASURY_TX_SEED: &[u8] = b"treasury_tx";
+
+/// 📦 Limites de uma instrução proposta
+pub const MAX_PROPOSED_ACCOUNTS: usize = 16;
+pub const MAX_PROPOSED_DATA_LEN: usize = 512;
+
+/// 📦 Máximo de pernas em uma proposta de lote
+pub const MAX_BATCH_LEGS: usize = 64;
+
 /// 💰 Distribuição de fundos do Treasury (baseada na análise USDT)
 pub const TEAM_ALLOCATION_PERCENTAGE: u8 = 40;
 pub const STAKING_ALLOCATION_PERCENTAGE: u8 = 40;
@@ -45,6 +64,8 @@ pub struct TreasuryState {
     pub total_distributed_team: u64,
     pub total_distributed_staking: u64,
     pub total_distributed_ranking: u64,
+    /// Incrementa a cada troca de signatários/quórum aplicada
+    pub signer_set_version: u32,
 }
 
 /// 📋 Transação pendente no Treasury
@@ -52,6 +73,8 @@ pub struct TreasuryState {
 #[repr(C)]
 pub struct PendingTransaction {
     pub transaction_id: u64,
+    /// Estado do treasury dono da proposta; votos e execução só valem nele
+    pub treasury: Pubkey,
     pub transaction_type: TransactionType,
     pub recipient: Pubkey,
     pub amount: u64,
@@ -62,6 +85,106 @@ pub struct PendingTransaction {
     pub signature_count: u8,
     pub status: TransactionStatus,
     pub memo: [u8; 64],
+    /// Votos de rejeição, pelo mesmo índice de `TreasuryState.signers`
+    pub rejections: [bool; MAX_SIGNERS],
+    pub rejection_count: u8,
+    /// `TreasuryState.signer_set_version` na criação; votos por índice só valem nessa versão
+    pub signer_set_version: u32,
+    pub payload: ProposalPayload,
+}
+
+/// ⚙️ Novo conjunto de signatários e quórum
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SignerConfig {
+    pub signers: Vec<Pubkey>,
+    pub required_signatures: u8,
+}
+
+/// 🔗 Conta de uma instrução proposta (`AccountMeta` serializável)
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ProposedAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// 🔗 Instrução executada via `invoke_signed` com a PDA do Treasury como signatária
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ProposedInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposedAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+impl ProposedInstruction {
+    pub fn validate(&self) -> ProgramResult {
+        if self.accounts.len() > MAX_PROPOSED_ACCOUNTS || self.data.len() > MAX_PROPOSED_DATA_LEN {
+            msg!("❌ Instrução proposta excede {} contas / {} bytes", MAX_PROPOSED_ACCOUNTS, MAX_PROPOSED_DATA_LEN);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(())
+    }
+    
+    pub fn to_instruction(&self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// 💸 Uma perna de um lote de transferências
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BatchLeg {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub token_type: TokenType,
+}
+
+/// 📦 Lote de transferências com cursor retomável
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BatchPayout {
+    pub legs: Vec<BatchLeg>,
+    /// Próxima perna a pagar; o lote termina quando chega a `legs.len()`
+    pub next_leg: u16,
+}
+
+impl BatchPayout {
+    pub fn validate(&self) -> ProgramResult {
+        if self.legs.is_empty() || self.legs.len() > MAX_BATCH_LEGS || self.next_leg != 0 {
+            msg!("❌ Lote precisa de 1 a {} pernas", MAX_BATCH_LEGS);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        
+        if self.legs.iter().any(|leg| leg.amount == 0 || leg.recipient == Pubkey::default()) {
+            return Err(GMCError::InvalidAmount.into());
+        }
+        Ok(())
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.next_leg as usize >= self.legs.len()
+    }
+}
+
+/// 📦 Conteúdo aplicado na execução, além de `recipient`/`amount`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum ProposalPayload {
+    None,
+    /// `TransactionType::ConfigUpdate`
+    Config(SignerConfig),
+    /// `TransactionType::ProgramInstruction`
+    Instruction(ProposedInstruction),
+    /// `TransactionType::BatchTransfer`
+    Batch(BatchPayout),
 }
 
 /// 🔄 Tipos de transação suportados
@@ -73,6 +196,10 @@ pub enum TransactionType {
     ManualTransfer,
     ConfigUpdate,
     Emergency,
+    /// Instrução arbitrária assinada pela PDA do Treasury
+    ProgramInstruction,
+    /// Lote de transferências (pagamentos mensais de equipe e parceiros)
+    BatchTransfer,
 }
 
 /// 🪙 Tipos de token suportados
@@ -82,6 +209,36 @@ pub enum TokenType {
     GMC,
 }
 
+impl TokenType {
+    /// Seed que separa o cofre de cada token
+    pub fn vault_seed(&self) -> &'static [u8] {
+        match self {
+            TokenType::USDT => b"usdt",
+            TokenType::GMC => b"gmc",
+        }
+    }
+}
+
+/// Endereço do cofre (conta SPL) de um token do Treasury
+pub fn find_treasury_vault_address(program_id: &Pubkey, treasury: &Pubkey, token_type: &TokenType) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_VAULT_SEED, treasury.as_ref(), token_type.vault_seed()], program_id)
+}
+
+/// Autoridade (PDA) dona dos cofres do Treasury
+pub fn find_treasury_vault_authority(program_id: &Pubkey, treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_VAULT_AUTHORITY_SEED, treasury.as_ref()], program_id)
+}
+
+/// PDA que assina as instruções propostas (upgrade authority, autoridades de módulos, ...)
+pub fn find_treasury_authority(program_id: &Pubkey, treasury: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_AUTHORITY_SEED, treasury.as_ref()], program_id)
+}
+
+/// Endereço (PDA) da proposta `transaction_id` de um Treasury
+pub fn find_treasury_transaction_address(program_id: &Pubkey, treasury: &Pubkey, transaction_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_TX_SEED, treasury.as_ref(), &transaction_id.to_le_bytes()], program_id)
+}
+
 /// 📊 Status da transação
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub enum TransactionStatus {
@@ -89,6 +246,8 @@ pub enum TransactionStatus {
     Executed,
     Rejected,
     Expired,
+    /// Lote aprovado com parte das pernas já paga; votos congelados
+    InProgress,
 }
 
 /// 🏛️ Instruções do Treasury
@@ -98,6 +257,9 @@ pub enum TreasuryInstruction {
         signers: Vec<Pubkey>,
         required_signatures: u8,
     },
+    /// Contas: proponente pagador (signatário do multisig ou papel `TreasuryProposer`),
+    /// estado do treasury, PDA da proposta (`["treasury_tx", treasury, id]`),
+    /// System Program e, para papéis, o registro de papéis
     ProposeTransaction {
         transaction_type: TransactionType,
         recipient: Pubkey,
@@ -108,13 +270,21 @@ pub enum TreasuryInstruction {
     SignTransaction {
         transaction_id: u64,
     },
+    /// Contas: executor, estado do treasury, transação pendente, cofre do token,
+    /// autoridade dos cofres, conta de token do destinatário e programa SPL Token
     ExecuteTransaction {
         transaction_id: u64,
     },
+    /// Distribui 40/40/20 do cofre às carteiras de equipe, staking e ranking do GlobalState.
+    /// Só executável pelo multisig (proposta `ProgramInstruction`).
+    /// Contas: PDA do Treasury (signatária), estado do treasury, cofre do token,
+    /// autoridade dos cofres, contas de token de equipe, staking e ranking
+    /// (`ecosystem_wallets.{team,staking_fund,ranking_fund}`), programa SPL Token e GlobalState
     AutoDistribute {
         total_amount: u64,
         token_type: TokenType,
     },
+    /// Desativado: signatários e quórum só mudam via `ProposeConfigUpdate`
     UpdateConfig {
         new_signers: Option<Vec<Pubkey>>,
         new_required_signatures: Option<u8>,
@@ -122,6 +292,38 @@ pub enum TreasuryInstruction {
     EmergencyPause {
         pause: bool,
     },
+    /// Cria o cofre PDA de um token (autoridade do treasury)
+    InitializeVault {
+        token_type: TokenType,
+    },
+    /// Contas: signatário, estado do treasury e transação pendente
+    RevokeSignature {
+        transaction_id: u64,
+    },
+    /// Contas: signatário, estado do treasury e transação pendente
+    RejectTransaction {
+        transaction_id: u64,
+    },
+    /// Proposta `ConfigUpdate` com os novos signatários e quórum, aplicados na execução.
+    /// Contas: as mesmas de `ProposeTransaction`
+    ProposeConfigUpdate {
+        new_signers: Vec<Pubkey>,
+        new_required_signatures: u8,
+        memo: [u8; 64],
+    },
+    /// Proposta `ProgramInstruction`: instrução arbitrária assinada pela PDA do Treasury.
+    /// Instruções para outros programas não podem referenciar os cofres do Treasury.
+    /// Contas: as mesmas de `ProposeTransaction`
+    ProposeInstruction {
+        instruction: ProposedInstruction,
+        memo: [u8; 64],
+    },
+    /// Proposta `BatchTransfer`: pernas (destinatário, valor, token) pagas na execução.
+    /// Contas: as mesmas de `ProposeTransaction`
+    ProposeBatchTransfer {
+        legs: Vec<BatchLeg>,
+        memo: [u8; 64],
+    },
 }
 
 impl Default for TreasuryState {
@@ -141,6 +343,7 @@ impl Default for TreasuryState {
             total_distributed_team: 0,
             total_distributed_staking: 0,
             total_distributed_ranking: 0,
+            signer_set_version: 0,
         }
     }
 }
@@ -149,6 +352,7 @@ impl Default for PendingTransaction {
     fn default() -> Self {
         Self {
             transaction_id: 0,
+            treasury: Pubkey::default(),
             transaction_type: TransactionType::ManualTransfer,
             recipient: Pubkey::default(),
             amount: 0,
@@ -159,8 +363,128 @@ impl Default for PendingTransaction {
             signature_count: 0,
             status: TransactionStatus::Pending,
             memo: [0; 64],
+            rejections: [false; MAX_SIGNERS],
+            rejection_count: 0,
+            signer_set_version: 0,
+            payload: ProposalPayload::None,
+        }
+    }
+}
+
+impl TreasuryState {
+    /// Índice do signatário ativo em `signers`
+    pub fn signer_index(&self, key: &Pubkey) -> Result<usize, ProgramError> {
+        self.signers[..self.active_signers as usize]
+            .iter()
+            .position(|signer| signer == key)
+            .ok_or_else(|| GMCError::UnauthorizedAccess.into())
+    }
+    
+    /// Aplica a nova configuração; votos de propostas anteriores deixam de valer
+    pub fn apply_config(&mut self, config: &SignerConfig) -> ProgramResult {
+        validate_signer_config(config)?;
+        
+        self.signers = [Pubkey::default(); MAX_SIGNERS];
+        self.signers[..config.signers.len()].copy_from_slice(&config.signers);
+        self.active_signers = config.signers.len() as u8;
+        self.required_signatures = config.required_signatures;
+        self.signer_set_version = self.signer_set_version.wrapping_add(1);
+        Ok(())
+    }
+}
+
+impl PendingTransaction {
+    /// ✍️ Aprovar; um voto de rejeição anterior do mesmo signatário é retirado
+    pub fn approve(&mut self, signer_index: usize) -> ProgramResult {
+        if self.signatures[signer_index] {
+            msg!("🚨 Security Alert: Signer already approved this transaction");
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+        
+        self.signatures[signer_index] = true;
+        self.rejections[signer_index] = false;
+        self.recount();
+        Ok(())
+    }
+    
+    /// ↩️ Retirar a própria aprovação
+    pub fn revoke(&mut self, signer_index: usize) -> ProgramResult {
+        if !self.signatures[signer_index] {
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+        
+        self.signatures[signer_index] = false;
+        self.recount();
+        Ok(())
+    }
+    
+    /// ❌ Votar pela rejeição; vira `Rejected` quando o quórum fica inalcançável
+    pub fn reject(&mut self, signer_index: usize, active_signers: u8, required_signatures: u8) -> ProgramResult {
+        if self.rejections[signer_index] {
+            return Err(GMCError::OperationNotAllowed.into());
+        }
+        
+        self.rejections[signer_index] = true;
+        self.signatures[signer_index] = false;
+        self.recount();
+        
+        if active_signers.saturating_sub(self.rejection_count) < required_signatures {
+            self.status = TransactionStatus::Rejected;
+        }
+        Ok(())
+    }
+    
+    fn recount(&mut self) {
+        self.signature_count = self.signatures.iter().filter(|signed| **signed).count() as u8;
+        self.rejection_count = self.rejections.iter().filter(|rejected| **rejected).count() as u8;
+    }
+}
+
+/// Carrega uma proposta exigindo que pertença ao treasury informado
+fn load_pending_transaction(
+    program_id: &Pubkey,
+    treasury_key: &Pubkey,
+    pending_tx_info: &AccountInfo,
+) -> Result<PendingTransaction, ProgramError> {
+    let pending_tx: PendingTransaction = discriminator::load(program_id, pending_tx_info)?;
+    if pending_tx.treasury != *treasury_key {
+        msg!("🚨 Security Alert: Proposta {} pertence a outro treasury", pending_tx.transaction_id);
+        return Err(GMCError::InvalidAuthority.into());
+    }
+    Ok(pending_tx)
+}
+
+/// Votos por índice só valem para o conjunto de signatários em que a proposta foi criada
+fn require_current_signer_set(treasury_state: &TreasuryState, pending_tx: &PendingTransaction) -> ProgramResult {
+    if pending_tx.signer_set_version != treasury_state.signer_set_version {
+        msg!("❌ Proposta {} criada para outro conjunto de signatários", pending_tx.transaction_id);
+        return Err(GMCError::OperationNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Quantidade de signatários em `MIN_SIGNERS..=MAX_SIGNERS` e quórum entre 2 e o total
+fn validate_signer_config(config: &SignerConfig) -> ProgramResult {
+    if config.signers.len() < MIN_SIGNERS || config.signers.len() > MAX_SIGNERS {
+        return Err(GMCError::InvalidRankingParameters.into());
+    }
+    
+    if config.required_signatures < 2 || config.required_signatures as usize > config.signers.len() {
+        return Err(GMCError::InvalidRankingParameters.into());
+    }
+    
+    validate_signers(&config.signers)
+}
+
+/// Signatários sem duplicatas nem chave padrão
+fn validate_signers(signers: &[Pubkey]) -> ProgramResult {
+    for (i, signer) in signers.iter().enumerate() {
+        if *signer == Pubkey::default() || signers[..i].contains(signer) {
+            msg!("🚨 Security Alert: Invalid or duplicated treasury signer {}", signer);
+            return Err(GMCError::InvalidRankingParameters.into());
         }
     }
+    Ok(())
 }
 
 /// 🏛️ Processador principal de instruções do Treasury
@@ -187,10 +511,17 @@ pub fn process_treasury_instruction(
             process_initialize(program_id, accounts, signers, required_signatures)
         },
         TreasuryInstruction::ProposeTransaction { transaction_type, recipient, amount, token_type, memo } => {
-            process_propose_transaction(program_id, accounts, transaction_type, recipient, amount, token_type, memo)
+            process_propose_transaction(program_id, accounts, PendingTransaction {
+                transaction_type,
+                recipient,
+                amount,
+                token_type,
+                memo,
+                ..PendingTransaction::default()
+            })
         },
         TreasuryInstruction::SignTransaction { transaction_id } => {
-            process_sign_transaction(program_id, accounts, transaction_id)
+            process_vote(program_id, accounts, transaction_id, Vote::Approve)
         },
         TreasuryInstruction::ExecuteTransaction { transaction_id } => {
             process_execute_transaction(program_id, accounts, transaction_id)
@@ -198,18 +529,55 @@ pub fn process_treasury_instruction(
         TreasuryInstruction::AutoDistribute { total_amount, token_type } => {
             process_auto_distribute(program_id, accounts, total_amount, token_type)
         },
-        TreasuryInstruction::UpdateConfig { new_signers, new_required_signatures } => {
-            process_update_config(program_id, accounts, new_signers, new_required_signatures)
+        TreasuryInstruction::UpdateConfig { .. } => {
+            msg!("❌ UpdateConfig desativado: use ProposeConfigUpdate com o quórum do multisig");
+            Err(GMCError::OperationNotAllowed.into())
         },
         TreasuryInstruction::EmergencyPause { pause } => {
             process_emergency_pause(program_id, accounts, pause)
         },
+        TreasuryInstruction::InitializeVault { token_type } => {
+            process_initialize_vault(program_id, accounts, token_type)
+        },
+        TreasuryInstruction::RevokeSignature { transaction_id } => {
+            process_vote(program_id, accounts, transaction_id, Vote::Revoke)
+        },
+        TreasuryInstruction::RejectTransaction { transaction_id } => {
+            process_vote(program_id, accounts, transaction_id, Vote::Reject)
+        },
+        TreasuryInstruction::ProposeConfigUpdate { new_signers, new_required_signatures, memo } => {
+            process_propose_transaction(program_id, accounts, PendingTransaction {
+                transaction_type: TransactionType::ConfigUpdate,
+                memo,
+                payload: ProposalPayload::Config(SignerConfig {
+                    signers: new_signers,
+                    required_signatures: new_required_signatures,
+                }),
+                ..PendingTransaction::default()
+            })
+        },
+        TreasuryInstruction::ProposeInstruction { instruction, memo } => {
+            process_propose_transaction(program_id, accounts, PendingTransaction {
+                transaction_type: TransactionType::ProgramInstruction,
+                memo,
+                payload: ProposalPayload::Instruction(instruction),
+                ..PendingTransaction::default()
+            })
+        },
+        TreasuryInstruction::ProposeBatchTransfer { legs, memo } => {
+            process_propose_transaction(program_id, accounts, PendingTransaction {
+                transaction_type: TransactionType::BatchTransfer,
+                memo,
+                payload: ProposalPayload::Batch(BatchPayout { legs, next_leg: 0 }),
+                ..PendingTransaction::default()
+            })
+        },
     }
 }
 
 /// 🚀 Inicializar Treasury
 fn process_initialize(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     signers: Vec<Pubkey>,
     required_signatures: u8,
@@ -222,53 +590,54 @@ fn process_initialize(
         return Err(GMCError::MissingSignature.into());
     }
     
-    if signers.len() < MIN_SIGNERS || signers.len() > MAX_SIGNERS {
-        return Err(GMCError::InvalidRankingParameters.into());
-    }
+    let config = SignerConfig { signers, required_signatures };
+    validate_signer_config(&config)?;
     
-    if required_signatures < 2 || required_signatures as usize > signers.len() {
-        return Err(GMCError::InvalidRankingParameters.into());
+    // 🏷️ Somente uma conta nova (sem discriminador) vira TreasuryState
+    if !discriminator::is_untagged(&treasury_state_info.data.borrow()) {
+        return Err(ProgramError::AccountAlreadyInitialized);
     }
     
     let mut treasury_state = TreasuryState::default();
     treasury_state.authority = *authority_info.key;
-    treasury_state.active_signers = signers.len() as u8;
-    treasury_state.required_signatures = required_signatures;
     treasury_state.is_initialized = true;
     treasury_state.is_active = true;
+    treasury_state.apply_config(&config)?;
     
-    for (i, signer) in signers.iter().enumerate() {
-        if i < MAX_SIGNERS {
-            treasury_state.signers[i] = *signer;
-        }
-    }
-    
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, treasury_state_info, &treasury_state)?;
     
-    msg!("Treasury inicializado com {} signatários", signers.len());
+    msg!("Treasury inicializado com {} signatários", config.signers.len());
     Ok(())
 }
 
 /// 📝 Propor nova transação
+///
+/// `draft` traz tipo, destinatário, valor, token, memo e payload; id, treasury,
+/// prazos e votos são preenchidos aqui.
+///
+/// Accounts expected:
+/// 0. `[writable, signer]` Proponente (pagador)
+/// 1. `[writable]` Estado do treasury
+/// 2. `[writable]` PDA da proposta (`["treasury_tx", treasury, transaction_id]`)
+/// 3. `[]` System program
+/// 4. `[]` Opcional: registro de papéis (proponentes com `TreasuryProposer`)
 fn process_propose_transaction(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    transaction_type: TransactionType,
-    recipient: Pubkey,
-    amount: u64,
-    token_type: TokenType,
-    memo: [u8; 64],
+    draft: PendingTransaction,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let proposer_info = next_account_info(accounts_iter)?;
     let treasury_state_info = next_account_info(accounts_iter)?;
     let pending_tx_info = next_account_info(accounts_iter)?;
+    let system_program_info = next_account_info(accounts_iter)?;
+    let roles_info = accounts_iter.next();
     
     if !proposer_info.is_signer {
         return Err(GMCError::MissingSignature.into());
     }
     
-    let mut treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
+    let mut treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
     
     if !treasury_state.is_initialized || !treasury_state.is_active {
         return Err(GMCError::RankingNotInitialized.into());
@@ -278,48 +647,108 @@ fn process_propose_transaction(
         return Err(GMCError::OperationNotAllowed.into());
     }
     
+    // 🎭 Quem tem o papel de proponente propõe, mas não conta como assinatura
     let is_authorized = treasury_state.signers[..treasury_state.active_signers as usize]
-        .contains(proposer_info.key);
+        .contains(proposer_info.key)
+        || crate::roles::has_role(program_id, roles_info, crate::roles::Role::TreasuryProposer, proposer_info.key)?;
     
     if !is_authorized {
         return Err(GMCError::UnauthorizedAccess.into());
     }
     
+    // ⚙️ ConfigUpdate, ProgramInstruction e BatchTransfer sempre carregam seu payload, e só eles
+    match &draft.payload {
+        ProposalPayload::Config(config) if draft.transaction_type == TransactionType::ConfigUpdate => {
+            validate_signer_config(config)?;
+        }
+        ProposalPayload::Instruction(instruction) if draft.transaction_type == TransactionType::ProgramInstruction => {
+            instruction.validate()?;
+        }
+        ProposalPayload::Batch(batch) if draft.transaction_type == TransactionType::BatchTransfer => {
+            batch.validate()?;
+        }
+        ProposalPayload::None if !matches!(
+            draft.transaction_type,
+            TransactionType::ConfigUpdate | TransactionType::ProgramInstruction | TransactionType::BatchTransfer
+        ) => {}
+        _ => {
+            msg!("❌ Payload incompatível com {:?}", draft.transaction_type);
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+    
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
     
-    let mut pending_tx = PendingTransaction::default();
-    pending_tx.transaction_id = treasury_state.transaction_counter;
-    pending_tx.transaction_type = transaction_type;
-    pending_tx.recipient = recipient;
-    pending_tx.amount = amount;
-    pending_tx.token_type = token_type;
-    pending_tx.created_at = current_timestamp;
-    pending_tx.expires_at = current_timestamp + TRANSACTION_EXPIRY_SECONDS;
-    pending_tx.memo = memo;
-    pending_tx.status = TransactionStatus::Pending;
+    let transaction_id = treasury_state.transaction_counter;
+    let (pending_tx_key, bump) = find_treasury_transaction_address(program_id, treasury_state_info.key, transaction_id);
+    if *pending_tx_info.key != pending_tx_key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pending_tx_info.data_len() > 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut pending_tx = PendingTransaction {
+        transaction_id,
+        treasury: *treasury_state_info.key,
+        created_at: current_timestamp,
+        expires_at: current_timestamp + TRANSACTION_EXPIRY_SECONDS,
+        status: TransactionStatus::Pending,
+        signatures: [false; MAX_SIGNERS],
+        signature_count: 0,
+        rejections: [false; MAX_SIGNERS],
+        rejection_count: 0,
+        signer_set_version: treasury_state.signer_set_version,
+        ..draft
+    };
     
-    if let Some(signer_index) = treasury_state.signers[..treasury_state.active_signers as usize]
-        .iter()
-        .position(|&signer| signer == *proposer_info.key) {
-        pending_tx.signatures[signer_index] = true;
-        pending_tx.signature_count = 1;
+    if let Ok(signer_index) = treasury_state.signer_index(proposer_info.key) {
+        pending_tx.approve(signer_index)?;
     }
     
     treasury_state.transaction_counter = treasury_state.transaction_counter.saturating_add(1);
     
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
-    pending_tx.serialize(&mut *pending_tx_info.data.borrow_mut())?;
+    // 📐 O payload tem tamanho fixo após a criação (o cursor do lote é um u16)
+    let space = discriminator::ACCOUNT_HEADER_LEN + pending_tx.try_to_vec()?.len();
+    crate::create_pda_account(
+        program_id,
+        proposer_info,
+        pending_tx_info,
+        system_program_info,
+        space,
+        &[TREASURY_TX_SEED, treasury_state_info.key.as_ref(), &transaction_id.to_le_bytes(), &[bump]],
+    )?;
+    
+    discriminator::store(program_id, treasury_state_info, &treasury_state)?;
+    discriminator::store(program_id, pending_tx_info, &pending_tx)?;
     
     msg!("Transação {} proposta", pending_tx.transaction_id);
     Ok(())
 }
 
-/// ✍️ Assinar transação pendente
-fn process_sign_transaction(
-    _program_id: &Pubkey,
+/// 🗳️ Voto de um signatário sobre uma transação pendente
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Vote {
+    Approve,
+    Revoke,
+    Reject,
+}
+
+/// ✍️ Assinar, retirar a assinatura ou rejeitar uma transação pendente
+///
+/// Accounts expected:
+/// 0. `[signer]` Signatário do multisig
+/// 1. `[]` Estado do treasury
+/// 2. `[writable]` Transação pendente
+fn process_vote(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     transaction_id: u64,
+    vote: Vote,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let signer_info = next_account_info(accounts_iter)?;
@@ -330,8 +759,12 @@ fn process_sign_transaction(
         return Err(GMCError::MissingSignature.into());
     }
     
-    let treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
-    let mut pending_tx = PendingTransaction::try_from_slice(&pending_tx_info.data.borrow())?;
+    let treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
+    let mut pending_tx = load_pending_transaction(program_id, treasury_state_info.key, pending_tx_info)?;
+    
+    if treasury_state.emergency_pause {
+        return Err(GMCError::OperationNotAllowed.into());
+    }
     
     if pending_tx.transaction_id != transaction_id {
         return Err(GMCError::InvalidRankingParameters.into());
@@ -344,35 +777,67 @@ fn process_sign_transaction(
     let clock = Clock::get()?;
     if clock.unix_timestamp > pending_tx.expires_at {
         pending_tx.status = TransactionStatus::Expired;
-        pending_tx.serialize(&mut *pending_tx_info.data.borrow_mut())?;
+        discriminator::store(program_id, pending_tx_info, &pending_tx)?;
         return Err(GMCError::InvalidTimestamp.into());
     }
     
-    let signer_index = treasury_state.signers[..treasury_state.active_signers as usize]
-        .iter()
-        .position(|&signer| signer == *signer_info.key)
-        .ok_or(GMCError::UnauthorizedAccess)?;
-    
-    if pending_tx.signatures[signer_index] {
-        return Err(GMCError::OperationNotAllowed.into());
+    require_current_signer_set(&treasury_state, &pending_tx)?;
+    
+    let signer_index = treasury_state.signer_index(signer_info.key)?;
+    match vote {
+        Vote::Approve => pending_tx.approve(signer_index)?,
+        Vote::Revoke => pending_tx.revoke(signer_index)?,
+        Vote::Reject => pending_tx.reject(
+            signer_index,
+            treasury_state.active_signers,
+            treasury_state.required_signatures,
+        )?,
     }
     
-    pending_tx.signatures[signer_index] = true;
-    pending_tx.signature_count = pending_tx.signature_count.saturating_add(1);
-    
-    pending_tx.serialize(&mut *pending_tx_info.data.borrow_mut())?;
+    discriminator::store(program_id, pending_tx_info, &pending_tx)?;
     
-    msg!("Transação {} assinada ({}/{})", 
-         transaction_id, 
+    msg!("Transação {} {:?} ({}/{} assinaturas, {} rejeições)", 
+         transaction_id,
+         vote,
          pending_tx.signature_count,
-         treasury_state.required_signatures);
+         treasury_state.required_signatures,
+         pending_tx.rejection_count);
+    
+    if pending_tx.status == TransactionStatus::Rejected {
+        msg!("❌ Transação {} rejeitada: quórum inalcançável", transaction_id);
+    }
     
     Ok(())
 }
 
 /// ⚡ Executar transação aprovada
+///
+/// Accounts expected:
+/// 0. `[signer]` Executor
+/// 1. `[writable]` Estado do treasury
+/// 2. `[writable]` Transação pendente
+///
+/// Transferências (todo tipo exceto `ConfigUpdate`/`ProgramInstruction`) também recebem:
+/// 3. `[writable]` Cofre do token (`["treasury_vault", treasury, "gmc"|"usdt"]`)
+/// 4. `[]` Autoridade dos cofres (`["treasury_vault_authority", treasury]`)
+/// 5. `[writable]` Conta de token do destinatário (dono = `recipient`)
+/// 6. `[]` Programa SPL Token
+///
+/// `ProgramInstruction` recebe:
+/// 3. `[]` PDA do Treasury (`["treasury_authority", treasury]`)
+/// 4. `[]` Programa chamado
+/// 5. Contas da instrução proposta, na ordem de `accounts`
+///
+/// `BatchTransfer` recebe:
+/// 3. `[writable]` Cofre GMC
+/// 4. `[writable]` Cofre USDT
+/// 5. `[]` Autoridade dos cofres
+/// 6. `[]` Programa SPL Token
+/// 7. `[]` Rent sysvar
+/// 8. `[writable]` Contas de token dos destinatários, a partir de `next_leg`; as
+///    pernas sem conta ficam para a próxima chamada (status `InProgress`)
 fn process_execute_transaction(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     transaction_id: u64,
 ) -> ProgramResult {
@@ -385,49 +850,158 @@ fn process_execute_transaction(
         return Err(GMCError::MissingSignature.into());
     }
     
-    let mut treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
-    let mut pending_tx = PendingTransaction::try_from_slice(&pending_tx_info.data.borrow())?;
+    let mut treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
+    let mut pending_tx = load_pending_transaction(program_id, treasury_state_info.key, pending_tx_info)?;
+    
+    if treasury_state.emergency_pause {
+        return Err(GMCError::OperationNotAllowed.into());
+    }
     
     if pending_tx.transaction_id != transaction_id {
         return Err(GMCError::InvalidRankingParameters.into());
     }
     
-    if pending_tx.signature_count < treasury_state.required_signatures {
-        return Err(GMCError::UnauthorizedAccess.into());
+    // Um lote já iniciado segue até o fim, mesmo após o prazo de votação ou
+    // uma rotação de signatários: o quórum foi verificado na primeira execução
+    match pending_tx.status {
+        TransactionStatus::Pending => {
+            let clock = Clock::get()?;
+            if clock.unix_timestamp > pending_tx.expires_at {
+                pending_tx.status = TransactionStatus::Expired;
+                discriminator::store(program_id, pending_tx_info, &pending_tx)?;
+                return Err(GMCError::InvalidTimestamp.into());
+            }
+            require_current_signer_set(&treasury_state, &pending_tx)?;
+            if pending_tx.signature_count < treasury_state.required_signatures {
+                return Err(GMCError::UnauthorizedAccess.into());
+            }
+        }
+        TransactionStatus::InProgress => {}
+        _ => return Err(GMCError::OperationNotAllowed.into()),
     }
     
-    if pending_tx.status != TransactionStatus::Pending {
-        return Err(GMCError::OperationNotAllowed.into());
+    let mut completed = true;
+    match pending_tx.payload {
+        ProposalPayload::Config(ref config) => {
+            treasury_state.apply_config(config)?;
+            msg!("⚙️ Novos signatários aplicados: {}-de-{}", config.required_signatures, config.signers.len());
+        }
+        ProposalPayload::None => {
+            execute_transfer(program_id, treasury_state_info.key, &mut treasury_state, &pending_tx, accounts_iter)?;
+        }
+        ProposalPayload::Instruction(_) => {}
+        ProposalPayload::Batch(ref mut batch) => {
+            execute_batch(program_id, treasury_state_info.key, &mut treasury_state, batch, accounts_iter.as_slice())?;
+            completed = batch.is_complete();
+        }
     }
     
-    let clock = Clock::get()?;
-    if clock.unix_timestamp > pending_tx.expires_at {
-        pending_tx.status = TransactionStatus::Expired;
-        pending_tx.serialize(&mut *pending_tx_info.data.borrow_mut())?;
-        return Err(GMCError::InvalidTimestamp.into());
+    pending_tx.status = if completed {
+        TransactionStatus::Executed
+    } else {
+        TransactionStatus::InProgress
+    };
+    
+    // 🔒 Estado gravado antes da CPI: a instrução proposta pode reentrar no Treasury
+    discriminator::store(program_id, treasury_state_info, &treasury_state)?;
+    discriminator::store(program_id, pending_tx_info, &pending_tx)?;
+    
+    if let ProposalPayload::Instruction(proposed) = &pending_tx.payload {
+        execute_instruction(program_id, treasury_state_info.key, proposed, accounts_iter.as_slice())?;
     }
     
-    // Atualizar estatísticas
-    match pending_tx.transaction_type {
-        TransactionType::TeamDistribution => {
-            treasury_state.total_distributed_team = 
-                treasury_state.total_distributed_team.saturating_add(pending_tx.amount);
-        }
-        TransactionType::StakingDistribution => {
-            treasury_state.total_distributed_staking = 
-                treasury_state.total_distributed_staking.saturating_add(pending_tx.amount);
+    if completed {
+        msg!("Transação {} executada com sucesso", transaction_id);
+    }
+    Ok(())
+}
+
+/// 📦 Paga as próximas pernas do lote a partir do cursor, numa única chamada atômica
+fn execute_batch(
+    program_id: &Pubkey,
+    treasury_key: &Pubkey,
+    treasury_state: &mut TreasuryState,
+    batch: &mut BatchPayout,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let gmc_vault_info = next_account_info(accounts_iter)?;
+    let usdt_vault_info = next_account_info(accounts_iter)?;
+    let vault_authority_info = next_account_info(accounts_iter)?;
+    let token_program_info = next_account_info(accounts_iter)?;
+    let rent_info = next_account_info(accounts_iter)?;
+    let recipient_infos = accounts_iter.as_slice();
+    
+    let (vault_authority, vault_authority_bump) = find_treasury_vault_authority(program_id, treasury_key);
+    if *gmc_vault_info.key != find_treasury_vault_address(program_id, treasury_key, &TokenType::GMC).0
+        || *usdt_vault_info.key != find_treasury_vault_address(program_id, treasury_key, &TokenType::USDT).0
+        || *vault_authority_info.key != vault_authority
+    {
+        msg!("🚨 Security Alert: Invalid treasury vault accounts");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if *token_program_info.key != spl_token::id() || *rent_info.key != sysvar::rent::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut processor = OptimizedBatchProcessor::new(token_program_info);
+    let start = batch.next_leg as usize;
+    
+    for (leg, recipient_info) in batch.legs[start..].iter().zip(recipient_infos) {
+        if processor.transfer_batch.len() >= processor.max_transfers_per_batch {
+            break;
         }
-        TransactionType::RankingDistribution => {
-            treasury_state.total_distributed_ranking = 
-                treasury_state.total_distributed_ranking.saturating_add(pending_tx.amount);
+        
+        let vault_info = match leg.token_type {
+            TokenType::GMC => gmc_vault_info,
+            TokenType::USDT => usdt_vault_info,
+        };
+        let vault = spl_token::state::Account::unpack(&vault_info.data.borrow())?;
+        let recipient_token = spl_token::state::Account::unpack(&recipient_info.data.borrow())?;
+        if recipient_token.mint != vault.mint || recipient_token.owner != leg.recipient {
+            msg!("🚨 Security Alert: Recipient token account does not match leg {}", start + processor.transfer_batch.len());
+            return Err(GMCError::InvalidWallet.into());
         }
-        _ => {}
+        
+        // A taxa de saque USDT permanece no cofre
+        let (final_amount, _) = withdrawal_amounts(leg.amount, &leg.token_type)?;
+        processor.add_transfer(BatchTransfer {
+            from: *vault_info.key,
+            to: *recipient_info.key,
+            amount: final_amount,
+            authority: vault_authority,
+        })?;
+    }
+    
+    let legs_paid = processor.transfer_batch.len();
+    if legs_paid == 0 {
+        msg!("❌ Nenhuma conta de destinatário para a perna {}", start);
+        return Err(ProgramError::NotEnoughAccountKeys);
     }
     
-    // 💰 BUSINESS RULE: Taxa Saque USDT (0.3% sobre valor)
-    let (final_amount, withdrawal_fee) = match pending_tx.token_type {
+    let transferred = processor.execute_transfer_batch(
+        accounts,
+        Some(&[&[TREASURY_VAULT_AUTHORITY_SEED, treasury_key.as_ref(), &[vault_authority_bump]]]),
+    )?;
+    batch.next_leg = (start + legs_paid) as u16;
+    
+    if let Ok(vault) = spl_token::state::Account::unpack(&gmc_vault_info.data.borrow()) {
+        treasury_state.total_balance_gmc = vault.amount;
+    }
+    if let Ok(vault) = spl_token::state::Account::unpack(&usdt_vault_info.data.borrow()) {
+        treasury_state.total_balance_usdt = vault.amount;
+    }
+    
+    msg!("📦 Lote: pernas {}..{} de {} pagas ({} tokens)", start, batch.next_leg, batch.legs.len(), transferred);
+    Ok(())
+}
+
+/// 💰 BUSINESS RULE: Taxa Saque USDT (0.3% sobre valor); retorna (valor líquido, taxa)
+fn withdrawal_amounts(amount: u64, token_type: &TokenType) -> Result<(u64, u64), ProgramError> {
+    match token_type {
         TokenType::USDT => {
-            let fee = pending_tx.amount
+            let fee = amount
                 .checked_mul(30) // 0.3% = 30/10000
                 .and_then(|x| x.checked_div(10000))
                 .ok_or_else(|| {
@@ -435,7 +1009,7 @@ fn process_execute_transaction(
                     GMCError::ArithmeticOverflow
                 })?;
             
-            let user_receives = pending_tx.amount
+            let user_receives = amount
                 .checked_sub(fee)
                 .ok_or_else(|| {
                     msg!("🚨 Security Alert: USDT amount calculation underflow");
@@ -445,40 +1019,143 @@ fn process_execute_transaction(
             msg!("💰 USDT withdrawal fee (0.3%): {} USDT", fee);
             msg!("💸 User receives: {} USDT (after 0.3% fee)", user_receives);
             
-            (user_receives, fee)
+            Ok((user_receives, fee))
         }
         TokenType::GMC => {
             // GMC não tem taxa de saque
-            (pending_tx.amount, 0)
+            Ok((amount, 0))
         }
-    };
+    }
+}
+
+/// Verdadeiro se a instrução proposta usa um cofre ou a autoridade dos cofres
+fn references_treasury_vaults(program_id: &Pubkey, treasury_key: &Pubkey, proposed: &ProposedInstruction) -> bool {
+    let shielded = [
+        find_treasury_vault_address(program_id, treasury_key, &TokenType::USDT).0,
+        find_treasury_vault_address(program_id, treasury_key, &TokenType::GMC).0,
+        find_treasury_vault_authority(program_id, treasury_key).0,
+    ];
+    proposed.accounts.iter().any(|meta| shielded.contains(&meta.pubkey))
+}
+
+/// 🔗 Invocar a instrução proposta com a PDA do Treasury como signatária
+///
+/// Instruções para outros programas não podem referenciar os cofres nem a
+/// autoridade dos cofres: os fundos só saem via `Transfer`, `BatchTransfer`
+/// ou `AutoDistribute`, que validam destinatários e valores.
+fn execute_instruction(
+    program_id: &Pubkey,
+    treasury_key: &Pubkey,
+    proposed: &ProposedInstruction,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let treasury_authority_info = next_account_info(accounts_iter)?;
+    let target_program_info = next_account_info(accounts_iter)?;
+    
+    let (treasury_authority, bump) = find_treasury_authority(program_id, treasury_key);
+    if *treasury_authority_info.key != treasury_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if *target_program_info.key != proposed.program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    if proposed.program_id != *program_id && references_treasury_vaults(program_id, treasury_key, proposed) {
+        msg!("🚨 Security Alert: External instruction references treasury vaults");
+        return Err(GMCError::OperationNotAllowed.into());
+    }
+    
+    msg!("🔗 Invocando {} com {} contas", proposed.program_id, proposed.accounts.len());
+    invoke_signed(
+        &proposed.to_instruction(),
+        accounts,
+        &[&[TREASURY_AUTHORITY_SEED, treasury_key.as_ref(), &[bump]]],
+    )
+}
+
+/// 💸 Transferência do cofre PDA para o destinatário da proposta
+fn execute_transfer<'a, 'info>(
+    program_id: &Pubkey,
+    treasury_key: &Pubkey,
+    treasury_state: &mut TreasuryState,
+    pending_tx: &PendingTransaction,
+    accounts_iter: &mut std::slice::Iter<'a, AccountInfo<'info>>,
+) -> ProgramResult {
+    let vault_info = next_account_info(accounts_iter)?;
+    let vault_authority_info = next_account_info(accounts_iter)?;
+    let recipient_token_info = next_account_info(accounts_iter)?;
+    let token_program_info = next_account_info(accounts_iter)?;
     
-    // Atualizar saldo
+    // Atualizar estatísticas
+    match pending_tx.transaction_type {
+        TransactionType::TeamDistribution => {
+            treasury_state.total_distributed_team = 
+                treasury_state.total_distributed_team.saturating_add(pending_tx.amount);
+        }
+        TransactionType::StakingDistribution => {
+            treasury_state.total_distributed_staking = 
+                treasury_state.total_distributed_staking.saturating_add(pending_tx.amount);
+        }
+        TransactionType::RankingDistribution => {
+            treasury_state.total_distributed_ranking = 
+                treasury_state.total_distributed_ranking.saturating_add(pending_tx.amount);
+        }
+        _ => {}
+    }
+    
+    let (final_amount, withdrawal_fee) = withdrawal_amounts(pending_tx.amount, &pending_tx.token_type)?;
+    
+    // 🔑 Os fundos saem do cofre PDA do token da proposta
+    let (vault_authority, vault_authority_bump) = find_treasury_vault_authority(program_id, treasury_key);
+    let (vault_key, _) = find_treasury_vault_address(program_id, treasury_key, &pending_tx.token_type);
+    if *vault_info.key != vault_key || *vault_authority_info.key != vault_authority {
+        msg!("🚨 Security Alert: Invalid treasury vault accounts");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let vault = spl_token::state::Account::unpack(&vault_info.data.borrow())?;
+    let recipient_token = spl_token::state::Account::unpack(&recipient_token_info.data.borrow())?;
+    if recipient_token.mint != vault.mint || recipient_token.owner != pending_tx.recipient {
+        msg!("🚨 Security Alert: Recipient token account does not match {:?} / recipient", pending_tx.token_type);
+        return Err(GMCError::InvalidWallet.into());
+    }
+    
+    let vault_balance_after = vault.amount
+        .checked_sub(final_amount)
+        .ok_or(GMCError::InsufficientBalance)?;
+    
+    crate::staking::transfer_tokens_signed(
+        token_program_info,
+        vault_info,
+        recipient_token_info,
+        vault_authority_info,
+        &[TREASURY_VAULT_AUTHORITY_SEED, treasury_key.as_ref(), &[vault_authority_bump]],
+        final_amount,
+    )?;
+    
+    // Atualizar saldo (a taxa permanece no cofre como receita)
     match pending_tx.token_type {
         TokenType::USDT => {
-            treasury_state.total_balance_usdt = 
-                treasury_state.total_balance_usdt.saturating_sub(pending_tx.amount);
-            // Fee permanece no treasury como receita
+            treasury_state.total_balance_usdt = vault_balance_after;
             msg!("💰 USDT withdrawal fee {} retained in treasury", withdrawal_fee);
         }
         TokenType::GMC => {
-            treasury_state.total_balance_gmc = 
-                treasury_state.total_balance_gmc.saturating_sub(pending_tx.amount);
+            treasury_state.total_balance_gmc = vault_balance_after;
         }
     }
     
-    pending_tx.status = TransactionStatus::Executed;
-    
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
-    pending_tx.serialize(&mut *pending_tx_info.data.borrow_mut())?;
-    
-    msg!("Transação {} executada com sucesso", transaction_id);
     Ok(())
 }
 
 /// 🔄 Distribuição automática periódica
 fn process_auto_distribute(
-    _program_id: &Pubkey,
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     total_amount: u64,
     token_type: TokenType,
@@ -486,20 +1163,82 @@ fn process_auto_distribute(
     let accounts_iter = &mut accounts.iter();
     let authority_info = next_account_info(accounts_iter)?;
     let treasury_state_info = next_account_info(accounts_iter)?;
+    let vault_info = next_account_info(accounts_iter)?;
+    let vault_authority_info = next_account_info(accounts_iter)?;
+    let team_token_info = next_account_info(accounts_iter)?;
+    let staking_token_info = next_account_info(accounts_iter)?;
+    let ranking_token_info = next_account_info(accounts_iter)?;
+    let token_program_info = next_account_info(accounts_iter)?;
+    let global_state_info = next_account_info(accounts_iter)?;
     
     if !authority_info.is_signer {
         return Err(GMCError::MissingSignature.into());
     }
     
-    let mut treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
+    let mut treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
+    
+    // 🔑 Somente o próprio multisig (proposta `ProgramInstruction` aprovada pelo quórum)
+    if *authority_info.key != find_treasury_authority(program_id, treasury_state_info.key).0 {
+        return Err(GMCError::UnauthorizedAccess.into());
+    }
     
     if !treasury_state.is_active || treasury_state.emergency_pause {
         return Err(GMCError::OperationNotAllowed.into());
     }
     
-    let team_amount = (total_amount as u128 * TEAM_ALLOCATION_PERCENTAGE as u128 / 100) as u64;
-    let staking_amount = (total_amount as u128 * STAKING_ALLOCATION_PERCENTAGE as u128 / 100) as u64;
-    let ranking_amount = (total_amount as u128 * RANKING_ALLOCATION_PERCENTAGE as u128 / 100) as u64;
+    let (vault_authority, vault_authority_bump) = find_treasury_vault_authority(program_id, treasury_state_info.key);
+    let (vault_key, _) = find_treasury_vault_address(program_id, treasury_state_info.key, &token_type);
+    if *vault_info.key != vault_key || *vault_authority_info.key != vault_authority {
+        msg!("🚨 Security Alert: Invalid treasury vault accounts");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    
+    if *token_program_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    // 🔒 Destinos fixados pelas carteiras do ecossistema no GlobalState
+    let global_state = crate::load_global_state(program_id, global_state_info)?;
+    let wallets = &global_state.ecosystem_wallets;
+    
+    let vault = spl_token::state::Account::unpack(&vault_info.data.borrow())?;
+    for (destination_info, wallet) in [
+        (team_token_info, &wallets.team),
+        (staking_token_info, &wallets.staking_fund),
+        (ranking_token_info, &wallets.ranking_fund),
+    ] {
+        // As carteiras do ecossistema são os próprios endereços das contas de token
+        let destination = spl_token::state::Account::unpack(&destination_info.data.borrow())?;
+        if *destination_info.key != *wallet || destination.mint != vault.mint {
+            msg!("🚨 Security Alert: Distribution account does not match {:?} / ecosystem wallet", token_type);
+            return Err(GMCError::InvalidWallet.into());
+        }
+    }
+    
+    let (team_amount, staking_amount, ranking_amount) = calculate_distribution_amounts(total_amount);
+    let distributed = team_amount
+        .checked_add(staking_amount)
+        .and_then(|sum| sum.checked_add(ranking_amount))
+        .ok_or(GMCError::ArithmeticOverflow)?;
+    let vault_balance_after = vault.amount
+        .checked_sub(distributed)
+        .ok_or(GMCError::InsufficientBalance)?;
+    
+    let vault_seeds: &[&[u8]] = &[TREASURY_VAULT_AUTHORITY_SEED, treasury_state_info.key.as_ref(), &[vault_authority_bump]];
+    for (destination_info, amount) in [
+        (team_token_info, team_amount),
+        (staking_token_info, staking_amount),
+        (ranking_token_info, ranking_amount),
+    ] {
+        crate::staking::transfer_tokens_signed(
+            token_program_info,
+            vault_info,
+            destination_info,
+            vault_authority_info,
+            vault_seeds,
+            amount,
+        )?;
+    }
     
     treasury_state.total_distributed_team = 
         treasury_state.total_distributed_team.saturating_add(team_amount);
@@ -507,24 +1246,27 @@ fn process_auto_distribute(
         treasury_state.total_distributed_staking.saturating_add(staking_amount);
     treasury_state.total_distributed_ranking = 
         treasury_state.total_distributed_ranking.saturating_add(ranking_amount);
+    match token_type {
+        TokenType::USDT => treasury_state.total_balance_usdt = vault_balance_after,
+        TokenType::GMC => treasury_state.total_balance_gmc = vault_balance_after,
+    }
     
     let clock = Clock::get()?;
     treasury_state.last_distribution_timestamp = clock.unix_timestamp;
     
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, treasury_state_info, &treasury_state)?;
     
-    msg!("Distribuição automática: {} {:?} total", total_amount, token_type);
+    msg!("Distribuição automática: {} {:?} total", distributed, token_type);
     msg!("Equipe: {}, Staking: {}, Ranking: {}", team_amount, staking_amount, ranking_amount);
     
     Ok(())
 }
 
-/// ⚙️ Atualizar configuração do Treasury
-fn process_update_config(
-    _program_id: &Pubkey,
+/// 🚨 Pausar/despausar em emergência
+fn process_emergency_pause(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    new_signers: Option<Vec<Pubkey>>,
-    new_required_signatures: Option<u8>,
+    pause: bool,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let authority_info = next_account_info(accounts_iter)?;
@@ -534,70 +1276,94 @@ fn process_update_config(
         return Err(GMCError::MissingSignature.into());
     }
     
-    let mut treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
+    let mut treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
     
     if treasury_state.authority != *authority_info.key {
         return Err(GMCError::UnauthorizedAccess.into());
     }
     
-    if let Some(signers) = new_signers {
-        if signers.len() < MIN_SIGNERS || signers.len() > MAX_SIGNERS {
-            return Err(GMCError::InvalidRankingParameters.into());
-        }
-        
-        treasury_state.signers = [Pubkey::default(); MAX_SIGNERS];
-        
-        for (i, signer) in signers.iter().enumerate() {
-            treasury_state.signers[i] = *signer;
-        }
-        
-        treasury_state.active_signers = signers.len() as u8;
-    }
-    
-    if let Some(required_sigs) = new_required_signatures {
-        if required_sigs < 2 || required_sigs as usize > treasury_state.active_signers as usize {
-            return Err(GMCError::InvalidRankingParameters.into());
-        }
-        
-        treasury_state.required_signatures = required_sigs;
-    }
+    treasury_state.emergency_pause = pause;
     
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
+    discriminator::store(program_id, treasury_state_info, &treasury_state)?;
     
-    msg!("Configuração do Treasury atualizada");
+    msg!("Treasury {}", if pause { "pausado" } else { "reativado" });
     Ok(())
 }
 
-/// 🚨 Pausar/despausar em emergência
-fn process_emergency_pause(
-    _program_id: &Pubkey,
+/// 🏦 Criar o cofre PDA de um token do Treasury
+///
+/// Accounts expected:
+/// 0. `[writable, signer]` Autoridade do treasury (pagador)
+/// 1. `[]` Estado do treasury
+/// 2. `[writable]` Cofre (`["treasury_vault", treasury, "gmc"|"usdt"]`)
+/// 3. `[]` Autoridade dos cofres (`["treasury_vault_authority", treasury]`)
+/// 4. `[]` Mint do token
+/// 5. `[]` Programa SPL Token
+/// 6. `[]` System program
+fn process_initialize_vault(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
-    pause: bool,
+    token_type: TokenType,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let authority_info = next_account_info(accounts_iter)?;
     let treasury_state_info = next_account_info(accounts_iter)?;
+    let vault_info = next_account_info(accounts_iter)?;
+    let vault_authority_info = next_account_info(accounts_iter)?;
+    let mint_info = next_account_info(accounts_iter)?;
+    let token_program_info = next_account_info(accounts_iter)?;
+    let system_program_info = next_account_info(accounts_iter)?;
     
     if !authority_info.is_signer {
         return Err(GMCError::MissingSignature.into());
     }
     
-    let mut treasury_state = TreasuryState::try_from_slice(&treasury_state_info.data.borrow())?;
-    
+    let treasury_state: TreasuryState = discriminator::load(program_id, treasury_state_info)?;
     if treasury_state.authority != *authority_info.key {
         return Err(GMCError::UnauthorizedAccess.into());
     }
     
-    treasury_state.emergency_pause = pause;
+    if *token_program_info.key != spl_token::id() || *system_program_info.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
     
-    treasury_state.serialize(&mut *treasury_state_info.data.borrow_mut())?;
+    let (vault_key, vault_bump) = find_treasury_vault_address(program_id, treasury_state_info.key, &token_type);
+    let (vault_authority, _) = find_treasury_vault_authority(program_id, treasury_state_info.key);
+    if *vault_info.key != vault_key || *vault_authority_info.key != vault_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
     
-    msg!("Treasury {}", if pause { "pausado" } else { "reativado" });
+    // O mint precisa ser um mint SPL válido
+    if *mint_info.owner != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    spl_token::state::Mint::unpack(&mint_info.data.borrow())?;
+    
+    let treasury_key = *treasury_state_info.key;
+    crate::create_pda_account(
+        &spl_token::id(),
+        authority_info,
+        vault_info,
+        system_program_info,
+        spl_token::state::Account::LEN,
+        &[TREASURY_VAULT_SEED, treasury_key.as_ref(), token_type.vault_seed(), &[vault_bump]],
+    )?;
+    
+    invoke(
+        &spl_token::instruction::initialize_account3(
+            &spl_token::id(),
+            vault_info.key,
+            mint_info.key,
+            &vault_authority,
+        )?,
+        &[vault_info.clone(), mint_info.clone()],
+    )?;
+    
+    msg!("🏦 Cofre {:?} do Treasury criado: {}", token_type, vault_key);
     Ok(())
 }
 
-/// 🧪 Função auxiliar para calcular distribuições
-#[allow(dead_code)]
+/// 🧮 Divisão 40/40/20 (equipe/staking/ranking) de uma distribuição
 pub fn calculate_distribution_amounts(total_amount: u64) -> (u64, u64, u64) {
     let team_amount = (total_amount as u128 * TEAM_ALLOCATION_PERCENTAGE as u128 / 100) as u64;
     let staking_amount = (total_amount as u128 * STAKING_ALLOCATION_PERCENTAGE as u128 / 100) as u64;
@@ -689,6 +1455,175 @@ mod tests {
         assert_eq!(deserialized_tx.status, TransactionStatus::Pending);
     }
 
+    #[test]
+    fn test_vaults_are_derived_per_treasury_and_token() {
+        let program_id = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        
+        let (gmc_vault, _) = find_treasury_vault_address(&program_id, &treasury, &TokenType::GMC);
+        let (usdt_vault, _) = find_treasury_vault_address(&program_id, &treasury, &TokenType::USDT);
+        let (authority, _) = find_treasury_vault_authority(&program_id, &treasury);
+        
+        assert_ne!(gmc_vault, usdt_vault);
+        assert_ne!(authority, gmc_vault);
+        assert_ne!(find_treasury_vault_address(&program_id, &Pubkey::new_unique(), &TokenType::GMC).0, gmc_vault);
+        assert_ne!(find_treasury_vault_authority(&program_id, &Pubkey::new_unique()).0, authority);
+    }
+
+    #[test]
+    fn test_proposals_are_bound_to_their_treasury() {
+        let program_id = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let rogue_treasury = Pubkey::new_unique();
+
+        let (tx_key, _) = find_treasury_transaction_address(&program_id, &treasury, 0);
+        assert_ne!(find_treasury_transaction_address(&program_id, &treasury, 1).0, tx_key);
+        assert_ne!(find_treasury_transaction_address(&program_id, &rogue_treasury, 0).0, tx_key);
+
+        let tx = PendingTransaction { treasury, ..PendingTransaction::default() };
+        let mut data = discriminator::to_tagged_vec(&tx).unwrap();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&tx_key, false, true, &mut lamports, &mut data, &program_id, false, Epoch::default());
+
+        assert!(load_pending_transaction(&program_id, &treasury, &info).is_ok());
+        assert_eq!(
+            load_pending_transaction(&program_id, &rogue_treasury, &info).unwrap_err(),
+            GMCError::InvalidAuthority.into()
+        );
+    }
+
+    #[test]
+    fn test_votes_are_tracked_per_signer() {
+        // 5 signatários, quórum de 3
+        let mut tx = PendingTransaction::default();
+        
+        tx.approve(0).unwrap();
+        assert_eq!(tx.approve(0), Err(GMCError::OperationNotAllowed.into()));
+        tx.approve(1).unwrap();
+        assert_eq!(tx.signature_count, 2);
+        
+        tx.revoke(1).unwrap();
+        assert!(tx.revoke(1).is_err());
+        assert_eq!(tx.signature_count, 1);
+        
+        // Rejeitar retira a aprovação do próprio signatário
+        tx.reject(0, 5, 3).unwrap();
+        assert!(tx.reject(0, 5, 3).is_err());
+        assert_eq!((tx.signature_count, tx.rejection_count), (0, 1));
+        tx.reject(1, 5, 3).unwrap();
+        assert_eq!(tx.status, TransactionStatus::Pending);
+        
+        // 3 rejeições deixam só 2 votos possíveis: quórum inalcançável
+        tx.reject(2, 5, 3).unwrap();
+        assert_eq!(tx.status, TransactionStatus::Rejected);
+        
+        assert!(validate_signers(&[Pubkey::new_unique(), Pubkey::new_unique()]).is_ok());
+        let duplicated = Pubkey::new_unique();
+        assert!(validate_signers(&[duplicated, Pubkey::new_unique(), duplicated]).is_err());
+        assert!(validate_signers(&[Pubkey::default()]).is_err());
+    }
+
+    #[test]
+    fn test_config_update_rotates_signers_within_limits() {
+        let mut state = TreasuryState::default();
+        let signers: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        state.apply_config(&SignerConfig { signers: signers.clone(), required_signatures: 3 }).unwrap();
+        assert_eq!((state.active_signers, state.required_signatures, state.signer_set_version), (4, 3, 1));
+        
+        // Fora de MIN_SIGNERS..=MAX_SIGNERS ou com quórum impossível
+        let too_few = SignerConfig { signers: signers[..MIN_SIGNERS - 1].to_vec(), required_signatures: 2 };
+        let too_many = SignerConfig {
+            signers: (0..=MAX_SIGNERS).map(|_| Pubkey::new_unique()).collect(),
+            required_signatures: 3,
+        };
+        let unreachable = SignerConfig { signers: signers.clone(), required_signatures: 5 };
+        for config in [too_few, too_many, unreachable] {
+            assert!(state.apply_config(&config).is_err());
+        }
+        assert_eq!(state.signer_set_version, 1);
+        
+        // Rotação limpa os assentos antigos e invalida votos pendentes
+        let rotated: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut pending = PendingTransaction { signer_set_version: state.signer_set_version, ..PendingTransaction::default() };
+        state.apply_config(&SignerConfig { signers: rotated.clone(), required_signatures: 2 }).unwrap();
+        assert_eq!(state.signers[3], Pubkey::default());
+        assert!(state.signer_index(&signers[0]).is_err());
+        assert_eq!(state.signer_index(&rotated[2]).unwrap(), 2);
+        assert!(require_current_signer_set(&state, &pending).is_err());
+        pending.signer_set_version = state.signer_set_version;
+        assert!(require_current_signer_set(&state, &pending).is_ok());
+    }
+
+    #[test]
+    fn test_proposed_instruction_is_bounded_and_signed_by_treasury_pda() {
+        let program_id = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let (treasury_authority, _) = find_treasury_authority(&program_id, &treasury);
+        assert_ne!(treasury_authority, find_treasury_vault_authority(&program_id, &treasury).0);
+        
+        // AutoDistribute no próprio Treasury, assinado pela PDA
+        let proposed = ProposedInstruction {
+            program_id,
+            accounts: vec![
+                ProposedAccountMeta { pubkey: treasury_authority, is_signer: true, is_writable: false },
+                ProposedAccountMeta { pubkey: treasury, is_signer: false, is_writable: true },
+            ],
+            data: crate::GMCInstruction::Treasury(TreasuryInstruction::AutoDistribute {
+                total_amount: 1_000,
+                token_type: TokenType::USDT,
+            })
+            .try_to_vec()
+            .unwrap(),
+        };
+        assert!(proposed.validate().is_ok());
+        
+        let instruction = proposed.to_instruction();
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts[0], AccountMeta::new_readonly(treasury_authority, true));
+        assert_eq!(instruction.accounts[1], AccountMeta::new(treasury, false));
+        assert!(!references_treasury_vaults(&program_id, &treasury, &proposed));
+        
+        // Contas de cofre não podem aparecer em instruções externas
+        let (usdt_vault, _) = find_treasury_vault_address(&program_id, &treasury, &TokenType::USDT);
+        let mut external = ProposedInstruction { program_id: spl_token::id(), ..proposed.clone() };
+        external.accounts.push(ProposedAccountMeta { pubkey: usdt_vault, is_signer: false, is_writable: true });
+        assert!(references_treasury_vaults(&program_id, &treasury, &external));
+        
+        let too_large = ProposedInstruction { data: vec![0; MAX_PROPOSED_DATA_LEN + 1], ..proposed.clone() };
+        assert!(too_large.validate().is_err());
+        let too_many = ProposedInstruction {
+            accounts: vec![proposed.accounts[1].clone(); MAX_PROPOSED_ACCOUNTS + 1],
+            ..proposed
+        };
+        assert!(too_many.validate().is_err());
+    }
+
+    #[test]
+    fn test_batch_payout_is_bounded_and_resumable() {
+        let leg = |token_type| BatchLeg { recipient: Pubkey::new_unique(), amount: 10_000, token_type };
+        let mut batch = BatchPayout { legs: vec![leg(TokenType::GMC), leg(TokenType::USDT)], next_leg: 0 };
+        assert!(batch.validate().is_ok());
+        assert!(!batch.is_complete());
+        
+        // Cursor avança por chamada; o lote termina na última perna
+        batch.next_leg = 1;
+        assert!(!batch.is_complete());
+        batch.next_leg = 2;
+        assert!(batch.is_complete());
+        
+        // Proposto sempre com cursor zerado, entre 1 e MAX_BATCH_LEGS pernas válidas
+        assert!(batch.validate().is_err());
+        assert!(BatchPayout { legs: vec![], next_leg: 0 }.validate().is_err());
+        let oversized = BatchPayout { legs: (0..=MAX_BATCH_LEGS).map(|_| leg(TokenType::GMC)).collect(), next_leg: 0 };
+        assert!(oversized.validate().is_err());
+        let zero = BatchPayout { legs: vec![BatchLeg { amount: 0, ..leg(TokenType::GMC) }], next_leg: 0 };
+        assert!(zero.validate().is_err());
+        
+        // Cada perna USDT paga 0,3% de taxa, que fica no cofre
+        assert_eq!(withdrawal_amounts(10_000, &TokenType::USDT).unwrap(), (9_970, 30));
+        assert_eq!(withdrawal_amounts(10_000, &TokenType::GMC).unwrap(), (10_000, 0));
+    }
+
     #[test]
     fn test_treasury_constants() {
         assert_eq!(TEAM_ALLOCATION_PERCENTAGE, 40);
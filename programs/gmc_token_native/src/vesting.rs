@@ -17,7 +17,7 @@ use solana_program::{
     sysvar::Sysvar,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
-use crate::GMCError;
+use crate::{discriminator, GMCError};
 
 // 🚀 OPTIMIZED: Vesting Configuration Constants with precomputed values
 #[allow(dead_code)]
@@ -85,6 +85,8 @@ pub struct VestingConfig {
 #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
 pub enum VestingInstruction {
     InitializeVesting,
+    /// Accounts: vesting authority or `VestingCreator` role holder (signer), vesting config,
+    /// vesting schedule, and the role registry when signed by a role holder
     CreateVestingSchedule {
         beneficiary: Pubkey,
         vesting_type: VestingType,
@@ -93,6 +95,7 @@ pub enum VestingInstruction {
         vesting_duration: u32,          // 🚀 OPTIMIZATION: u32 instead of i64
         release_interval: u32,          // 🚀 OPTIMIZATION: u32 instead of i64
     },
+    /// Accounts: beneficiary (signer), vesting schedule, vesting config, pause registry
     ReleaseVestedTokens {
         schedule_id: u64,
     },
@@ -106,12 +109,13 @@ pub enum VestingInstruction {
 /// 
 /// Receives a VestingInstruction and routes it to the appropriate handler
 pub fn process_instruction_router(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction: VestingInstruction,
 ) -> ProgramResult {
     match instruction {
         VestingInstruction::InitializeVesting => {
-            process_initialize_vesting(accounts)
+            process_initialize_vesting(program_id, accounts)
         },
         VestingInstruction::CreateVestingSchedule {
             beneficiary,
@@ -122,26 +126,29 @@ pub fn process_instruction_router(
             release_interval,
         } => {
             process_create_vesting_schedule(
+                program_id,
                 accounts,
                 beneficiary,
                 vesting_type,
                 total_amount,
-                cliff_duration.into(),
-                vesting_duration.into(),
-                release_interval.into(),
+                VestingTimings {
+                    cliff_duration: cliff_duration.into(),
+                    vesting_duration: vesting_duration.into(),
+                    release_interval: release_interval.into(),
+                },
             )
         },
         VestingInstruction::ReleaseVestedTokens { schedule_id } => {
-            process_release_vested_tokens(accounts, schedule_id.try_into().unwrap())
+            process_release_vested_tokens(program_id, accounts, schedule_id.try_into().unwrap())
         },
         VestingInstruction::EmergencyRelease { schedule_id, justification } => {
-            process_emergency_release(accounts, schedule_id.try_into().unwrap(), justification)
+            process_emergency_release(program_id, accounts, schedule_id.try_into().unwrap(), justification)
         },
     }
 }
 
 // 📅 Initialize Vesting System
-pub fn process_initialize_vesting(accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_initialize_vesting(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     msg!("📅 Initializing Vesting System");
     
     let account_info_iter = &mut accounts.iter();
@@ -154,6 +161,11 @@ pub fn process_initialize_vesting(accounts: &[AccountInfo]) -> ProgramResult {
         return Err(ProgramError::Custom(GMCError::UnauthorizedAccess as u32));
     }
     
+    if !discriminator::is_untagged(&config_info.data.borrow()) {
+        msg!("❌ Vesting config already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
     let clock = Clock::get()?;
     let _current_time = clock.unix_timestamp;
     
@@ -170,7 +182,7 @@ pub fn process_initialize_vesting(accounts: &[AccountInfo]) -> ProgramResult {
         _padding: 0,
     };
     
-    vesting_config.serialize(&mut &mut config_info.data.borrow_mut()[..])?;
+    discriminator::store(program_id, config_info, &vesting_config)?;
     
     msg!("✅ Vesting System initialized successfully");
     msg!("🏢 Centralized management enabled for RWA compliance");
@@ -178,31 +190,41 @@ pub fn process_initialize_vesting(accounts: &[AccountInfo]) -> ProgramResult {
     Ok(())
 }
 
+// 📅 Schedule timing, in seconds, for a new vesting schedule
+pub struct VestingTimings {
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+    pub release_interval: i64,
+}
+
 // 📅 Create Vesting Schedule
 pub fn process_create_vesting_schedule(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     beneficiary: Pubkey,
     vesting_type: VestingType,
     total_amount: u64,
-    cliff_duration: i64,
-    vesting_duration: i64,
-    release_interval: i64,
+    timings: VestingTimings,
 ) -> ProgramResult {
+    let VestingTimings { cliff_duration, vesting_duration, release_interval } = timings;
     msg!("📅 Creating vesting schedule for {:?}", vesting_type);
     
     let account_info_iter = &mut accounts.iter();
     let authority_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
     let config_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
     let schedule_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let roles_info = account_info_iter.next();
     
     if !authority_info.is_signer {
         msg!("❌ Authority signature required");
         return Err(ProgramError::Custom(GMCError::UnauthorizedAccess as u32));
     }
     
-    let mut vesting_config = VestingConfig::try_from_slice(&config_info.data.borrow())?;
+    let mut vesting_config: VestingConfig = discriminator::load(program_id, config_info)?;
     
-    if *authority_info.key != vesting_config.authority {
+    if *authority_info.key != vesting_config.authority
+        && !crate::roles::has_role(program_id, roles_info, crate::roles::Role::VestingCreator, authority_info.key)?
+    {
         msg!("❌ Invalid authority");
         return Err(ProgramError::Custom(GMCError::UnauthorizedAccess as u32));
     }
@@ -226,6 +248,11 @@ pub fn process_create_vesting_schedule(
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
     
+    if !discriminator::is_untagged(&schedule_info.data.borrow()) {
+        msg!("❌ Schedule account already in use");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    
     let schedule_id = vesting_config.total_schedules
         .checked_add(1)
         .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
@@ -264,8 +291,8 @@ pub fn process_create_vesting_schedule(
         .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
 
     
-    vesting_schedule.serialize(&mut &mut schedule_info.data.borrow_mut()[..])?;
-    vesting_config.serialize(&mut &mut config_info.data.borrow_mut()[..])?;
+    discriminator::store(program_id, schedule_info, &vesting_schedule)?;
+    discriminator::store(program_id, config_info, &vesting_config)?;
     
     msg!("✅ Vesting schedule {} created successfully", schedule_id);
     
@@ -274,6 +301,7 @@ pub fn process_create_vesting_schedule(
 
 // 📅 Release Vested Tokens
 pub fn process_release_vested_tokens(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     schedule_id: u64,
 ) -> ProgramResult {
@@ -283,14 +311,17 @@ pub fn process_release_vested_tokens(
     let beneficiary_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
     let schedule_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
     let config_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let pause_registry_info = account_info_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
     
     if !beneficiary_info.is_signer {
         msg!("❌ Beneficiary signature required");
         return Err(ProgramError::Custom(GMCError::UnauthorizedAccess as u32));
     }
     
-    let mut vesting_schedule = VestingSchedule::try_from_slice(&schedule_info.data.borrow())?;
-    let mut vesting_config = VestingConfig::try_from_slice(&config_info.data.borrow())?;
+    crate::pause::require_not_paused(program_id, pause_registry_info, crate::pause::PAUSE_VESTING_RELEASE)?;
+    
+    let mut vesting_schedule: VestingSchedule = discriminator::load(program_id, schedule_info)?;
+    let mut vesting_config: VestingConfig = discriminator::load(program_id, config_info)?;
     
     if *beneficiary_info.key != vesting_schedule.beneficiary {
         msg!("❌ Invalid beneficiary");
@@ -341,8 +372,8 @@ pub fn process_release_vested_tokens(
         .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
 
     
-    vesting_schedule.serialize(&mut &mut schedule_info.data.borrow_mut()[..])?;
-    vesting_config.serialize(&mut &mut config_info.data.borrow_mut()[..])?;
+    discriminator::store(program_id, schedule_info, &vesting_schedule)?;
+    discriminator::store(program_id, config_info, &vesting_config)?;
     
     msg!("✅ Released {} tokens to beneficiary", releasable_amount);
     
@@ -351,6 +382,7 @@ pub fn process_release_vested_tokens(
 
 // 🚨 Emergency Release (Centralized Control for RWA)
 pub fn process_emergency_release(
+    program_id: &Pubkey,
     accounts: &[AccountInfo],
     schedule_id: u64,
     justification: String,
@@ -367,8 +399,8 @@ pub fn process_emergency_release(
         return Err(ProgramError::Custom(GMCError::UnauthorizedAccess as u32));
     }
     
-    let mut vesting_schedule = VestingSchedule::try_from_slice(&schedule_info.data.borrow())?;
-    let mut vesting_config = VestingConfig::try_from_slice(&config_info.data.borrow())?;
+    let mut vesting_schedule: VestingSchedule = discriminator::load(program_id, schedule_info)?;
+    let mut vesting_config: VestingConfig = discriminator::load(program_id, config_info)?;
     
     if *authority_info.key != vesting_config.authority {
         msg!("❌ Invalid authority");
@@ -415,8 +447,8 @@ pub fn process_emergency_release(
         .ok_or(ProgramError::Custom(GMCError::ArithmeticOverflow as u32))?;
 
     
-    vesting_schedule.serialize(&mut &mut schedule_info.data.borrow_mut()[..])?;
-    vesting_config.serialize(&mut &mut config_info.data.borrow_mut()[..])?;
+    discriminator::store(program_id, schedule_info, &vesting_schedule)?;
+    discriminator::store(program_id, config_info, &vesting_config)?;
     
     msg!("✅ Emergency release completed: {} tokens", remaining_amount);
     msg!("📝 Justification: {}", justification);
@@ -46,6 +46,10 @@ fn test_rewards_calculation_uses_dynamic_apy() {
         lock_duration_days: 365, // 1 ano em dias
         pool_id: 1,
         is_active: true,
+        acc_reward_per_share: 0,
+        total_reward_shares: 0,
+        reward_budget: 0,
+        last_reward_update: 0,
         _padding: [0; 6],
     };
     
@@ -10,7 +10,7 @@ use solana_program::{
 };
 use std::mem;
 use gmc_token_native::{
-    GlobalState, GMCInstruction, GMCError, EcosystemWallets,
+    GlobalState, GMCInstruction, GMCError, EcosystemWallets, TRANSFER_FEE_BASIS_POINTS,
     process_instruction, process_revoke_mint_authority
 };
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -55,6 +55,12 @@ fn create_test_global_state(admin_key: Pubkey) -> GlobalState {
         is_initialized: true,
         burn_stopped: false,
         mint_authority_revoked: false,
+        token_mode: gmc_token_native::token_mode::TokenMode::Native,
+        gmc_mint: Pubkey::default(),
+        genesis_distributed: false,
+        transfer_fee_basis_points: TRANSFER_FEE_BASIS_POINTS,
+        usdt_mint: Pubkey::default(),
+        usdt_price_per_gmc: 0,
     }
 }
 
@@ -54,22 +54,22 @@ async fn setup_test_environment() -> (BanksClient, Keypair, Pubkey) {
 async fn test_pool_creation_logic_tdd() {
     println!("\n🧪 === TDD TEST 1: POOL CREATION LOGIC ===");
     
-    let (_banks_client, _payer, _program_id) = setup_test_environment().await;
+    let (_banks_client, _payer, program_id) = setup_test_environment().await;
     let empty_accounts = &[];
 
     // 🟢 GREEN: Testar criação de pool long-term (ID = 1)
     println!("🟢 Testando criação de pool long-term...");
-    let result = process_create_pool(empty_accounts, 1, LONG_TERM_POOL_APY, LONG_TERM_LOCK_DURATION, LONG_TERM_MIN_STAKE, LONG_TERM_MAX_STAKE);
+    let result = process_create_pool(&program_id, empty_accounts, 1, LONG_TERM_POOL_APY, LONG_TERM_LOCK_DURATION, LONG_TERM_MIN_STAKE, LONG_TERM_MAX_STAKE);
     assert!(result.is_ok(), "Pool long-term deve ser criado com sucesso");
     
     // 🟢 GREEN: Testar criação de pool flexible (ID = 2)  
     println!("🟢 Testando criação de pool flexible...");
-    let result = process_create_pool(empty_accounts, 2, FLEXIBLE_POOL_APY, FLEXIBLE_LOCK_DURATION, FLEXIBLE_MIN_STAKE, FLEXIBLE_MAX_STAKE);
+    let result = process_create_pool(&program_id, empty_accounts, 2, FLEXIBLE_POOL_APY, FLEXIBLE_LOCK_DURATION, FLEXIBLE_MIN_STAKE, FLEXIBLE_MAX_STAKE);
     assert!(result.is_ok(), "Pool flexible deve ser criado com sucesso");
     
     // 🔴 RED: Testar criação de pool inválido (ID = 99)
     println!("🔴 Testando pool ID inválido...");
-    let result = process_create_pool(empty_accounts, 99, 1000, 30, 1000, 10000);
+    let result = process_create_pool(&program_id, empty_accounts, 99, 1000, 30, 1000, 10000);
     assert!(result.is_err(), "Pool ID inválido deve falhar");
     
     // 🔵 REFACTOR: Validar constantes definidas
@@ -91,24 +91,24 @@ async fn test_pool_creation_logic_tdd() {
 async fn test_staking_logic_with_transfers_tdd() {
     println!("\n🧪 === TDD TEST 2: STAKING LOGIC WITH TRANSFERS ===");
     
-    let (_banks_client, _payer, _program_id) = setup_test_environment().await;
+    let (_banks_client, _payer, program_id) = setup_test_environment().await;
     let empty_accounts = &[];
 
     // 🟢 GREEN: Testar stake válido em long-term pool
     println!("🟢 Testando stake em long-term pool...");
     let stake_amount = 50_000 * 1_000_000_000u64; // 50,000 GMC
-    let result = process_stake(empty_accounts, 1, stake_amount);
+    let result = process_stake(&program_id, empty_accounts, 1, stake_amount);
     assert!(result.is_ok(), "Stake válido deve ter sucesso");
     
     // 🔴 RED: Testar stake zero (deve falhar por validação)
     println!("🔴 Testando stake zero...");
-    let result = process_stake(empty_accounts, 1, 0);
+    let result = process_stake(&program_id, empty_accounts, 1, 0);
     assert!(result.is_err(), "Stake zero deve falhar");
     
     // 🟢 GREEN: Testar stake em flexible pool
     println!("🟢 Testando stake em flexible pool...");
     let flexible_amount = 5_000 * 1_000_000_000u64; // 5,000 GMC
-    let result = process_stake(empty_accounts, 2, flexible_amount);
+    let result = process_stake(&program_id, empty_accounts, 2, flexible_amount);
     assert!(result.is_ok(), "Stake em flexible pool deve ter sucesso");
     
     // 🔵 REFACTOR: Validar limites configurados
@@ -130,12 +130,12 @@ async fn test_staking_logic_with_transfers_tdd() {
 async fn test_reward_claiming_logic_tdd() {
     println!("\n🧪 === TDD TEST 3: REWARD CLAIMING LOGIC ===");
     
-    let (_banks_client, _payer, _program_id) = setup_test_environment().await;
+    let (_banks_client, _payer, program_id) = setup_test_environment().await;
     let empty_accounts = &[];
 
     // 🟢 GREEN: Testar claim de recompensas
     println!("🟢 Testando claim de recompensas...");
-    let result = process_claim_rewards(empty_accounts, 1);
+    let result = process_claim_rewards(&program_id, empty_accounts, 1);
     assert!(result.is_ok(), "Claim de recompensas deve ter sucesso");
     
     // 🔵 REFACTOR: A implementação atual usa mock de 1,000 GMC
@@ -192,7 +192,7 @@ async fn test_penalty_calculations_tdd() {
 async fn test_burn_for_boost_logic_tdd() {
     println!("\n🧪 === TDD TEST 5: BURN-FOR-BOOST LOGIC ===");
     
-    let (_banks_client, _payer, _program_id) = setup_test_environment().await;
+    let (_banks_client, _payer, program_id) = setup_test_environment().await;
     let empty_accounts = &[];
 
     // 🟢 GREEN: Testar burn-for-boost válido
@@ -200,18 +200,18 @@ async fn test_burn_for_boost_logic_tdd() {
     let burn_amount = 10_000 * 1_000_000_000u64; // 10,000 GMC
     let boost_multiplier = 15000u16; // 1.5x boost (15000 basis points)
     
-    let result = process_burn_for_boost(empty_accounts, 1, burn_amount, boost_multiplier);
+    let result = process_burn_for_boost(&program_id, empty_accounts, 1, burn_amount, boost_multiplier);
     assert!(result.is_ok(), "Burn-for-boost válido deve ter sucesso");
     
     // 🔴 RED: Testar burn amount zero
     println!("🔴 Testando burn amount zero...");
-    let result = process_burn_for_boost(empty_accounts, 1, 0, boost_multiplier);
+    let result = process_burn_for_boost(&program_id, empty_accounts, 1, 0, boost_multiplier);
     assert!(result.is_err(), "Burn amount zero deve falhar");
     
     // 🔴 RED: Testar boost multiplier muito alto
     println!("🔴 Testando boost multiplier muito alto...");
     let high_multiplier = 60000u16; // 6.0x boost (acima do limite de 5.0x)
-    let result = process_burn_for_boost(empty_accounts, 1, burn_amount, high_multiplier);
+    let result = process_burn_for_boost(&program_id, empty_accounts, 1, burn_amount, high_multiplier);
     assert!(result.is_err(), "Boost muito alto deve falhar");
     
     // 🔵 REFACTOR: Validar cálculos de boost
@@ -334,7 +334,7 @@ async fn test_stake_record_marking_tdd() {
 async fn test_complete_staking_flow_integration_tdd() {
     println!("\n🧪 === TDD INTEGRATION TEST: FLUXO COMPLETO ===");
     
-    let (_banks_client, _payer, _program_id) = setup_test_environment().await;
+    let (_banks_client, _payer, program_id) = setup_test_environment().await;
     let empty_accounts = &[];
     let user = Pubkey::new_unique();
     let pool_id = 1u8;
@@ -342,24 +342,24 @@ async fn test_complete_staking_flow_integration_tdd() {
 
     // 🟢 PASSO 1: Criar pool
     println!("🟢 Passo 1: Criando pool...");
-    let result = process_create_pool(empty_accounts, pool_id, LONG_TERM_POOL_APY, LONG_TERM_LOCK_DURATION, LONG_TERM_MIN_STAKE, LONG_TERM_MAX_STAKE);
+    let result = process_create_pool(&program_id, empty_accounts, pool_id, LONG_TERM_POOL_APY, LONG_TERM_LOCK_DURATION, LONG_TERM_MIN_STAKE, LONG_TERM_MAX_STAKE);
     assert!(result.is_ok(), "Pool deve ser criado");
 
     // 🟢 PASSO 2: Fazer stake
     println!("🟢 Passo 2: Fazendo stake...");
-    let result = process_stake(empty_accounts, pool_id, stake_amount);
+    let result = process_stake(&program_id, empty_accounts, pool_id, stake_amount);
     assert!(result.is_ok(), "Stake deve ter sucesso");
 
     // 🟢 PASSO 3: Simular passagem do tempo e claim rewards
     println!("🟢 Passo 3: Claiming rewards...");
-    let result = process_claim_rewards(empty_accounts, pool_id);
+    let result = process_claim_rewards(&program_id, empty_accounts, pool_id);
     assert!(result.is_ok(), "Claim deve ter sucesso");
 
     // 🟢 PASSO 4: Fazer burn-for-boost
     println!("🟢 Passo 4: Fazendo burn-for-boost...");
     let burn_amount = 5_000 * 1_000_000_000u64; // 5,000 GMC
     let boost_multiplier = 15000u16; // 1.5x
-    let result = process_burn_for_boost(empty_accounts, pool_id, burn_amount, boost_multiplier);
+    let result = process_burn_for_boost(&program_id, empty_accounts, pool_id, burn_amount, boost_multiplier);
     assert!(result.is_ok(), "Burn-for-boost deve ter sucesso");
 
     // 🟢 PASSO 5: Validar utility functions